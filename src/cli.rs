@@ -1,5 +1,9 @@
 // CLI module for handling command-line interface
 
+use crate::commands::export::ExportFormat;
+use crate::commands::generate::ChecksumFormat;
+use crate::commands::migrate::MigrateFormat;
+use crate::manifest::ServerType;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -12,10 +16,44 @@ use clap::{Parser, Subcommand};
 #[command(version)]
 #[command(arg_required_else_help = true)]
 pub struct Cli {
-    /// Enable debug logging
+    /// Enable debug logging (same as -v)
     #[arg(long, global = true)]
     pub debug: bool,
 
+    /// Show debug logging on stderr; -vv also logs HTTP headers
+    ///
+    /// Spinners and progress bars are hidden so they don't interleave with
+    /// the log. `mpm outdated` and `mpm list` also show more detail.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Use an environment's plugins (`[env.<NAME>.plugins]` in plugins.toml)
+    ///
+    /// The environment's plugins are merged into `[plugins]`, and its lockfile
+    /// is kept separately as plugins.<NAME>.lock.
+    #[arg(long, global = true, value_name = "NAME")]
+    pub env: Option<String>,
+
+    /// Timeout for each HTTP request in seconds, overriding `http_timeout_secs` (0 disables it)
+    #[arg(long, global = true, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Times a failed HTTP request is retried, overriding `http.max_retries`
+    #[arg(long, global = true, value_name = "N")]
+    pub retries: Option<u32>,
+
+    /// Print errors as JSON on stderr: `{"error_code": ..., "message": ..., "context": {...}}`
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+
+    /// Also append every message to this file as JSON lines
+    ///
+    /// Each line is `{"timestamp": ..., "level": "info|warn|error", "message": ...}`.
+    /// The first line records the command and the last its duration and exit
+    /// code. Terminal output is unchanged.
+    #[arg(long, global = true, value_name = "FILE")]
+    pub log: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -32,47 +70,270 @@ pub enum Commands {
     Init {
         /// Minecraft version (e.g., 1.20.2). If not provided, attempts auto-detection from Paper JAR.
         version: Option<String>,
+        /// Server software, which decides the loaders plugin builds are resolved for
+        ///
+        /// Defaults to paper, which accepts paper, spigot, and bukkit builds.
+        /// Proxies (bungeecord, waterfall, velocity) also pick the Hangar
+        /// platform to download for.
+        #[arg(
+            long = "type",
+            visible_alias = "platform",
+            value_name = "TYPE",
+            value_enum
+        )]
+        server_type: Option<ServerType>,
+        /// Overwrite an existing plugins.toml (the lockfile is left alone)
+        ///
+        /// Plugins, groups, and environments from the old manifest are kept,
+        /// as is its Minecraft version unless a new one is given. Asks for
+        /// confirmation unless --yes is passed.
+        #[arg(long, short)]
+        force: bool,
+        /// Overwrite without asking (with --force)
+        #[arg(long, requires = "force")]
+        yes: bool,
+        /// Add these plugins once the manifest is written, then lock them
+        ///
+        /// Takes the same specs as `mpm add`. A plugin that can't be added
+        /// is reported without stopping the others.
+        #[arg(long, value_name = "SPEC", num_args = 1..)]
+        plugins: Vec<String>,
     },
-    /// Add a plugin to the manifest
+    /// Add plugins to the manifest
     ///
-    /// Adds a plugin specification to plugins.toml. The spec format is:
+    /// Adds plugin specifications to plugins.toml. The spec format is:
     /// source:id, source:id@version, id, or id@version
     ///
     /// If no source is specified, searches through all sources in priority order.
+    /// Several specs can be given; the lockfile is updated once after all of them.
     ///
     /// Examples:
     ///   mpm add fabric-api
     ///   mpm add worldedit@7.3.0
     ///   mpm add modrinth:fabric-api
     ///   mpm add modrinth:worldedit@7.3.0
+    ///   mpm add modrinth:worldedit modrinth:luckperms
     Add {
-        /// Plugin specification (id[@version] or source:id[@version])
-        spec: String,
+        /// Plugin specifications (id[@version] or source:id[@version])
+        #[arg(required = true, value_name = "SPEC")]
+        specs: Vec<String>,
         /// Skip automatic lockfile update after adding
         #[arg(long)]
         no_update: bool,
         /// Skip Minecraft version compatibility check
         #[arg(long)]
         skip_compatibility: bool,
+        /// Replace the plugin if it is already in the manifest
+        #[arg(long)]
+        upgrade: bool,
+        /// Annotation saved with the plugin as `notes` (kept on --upgrade unless replaced)
+        #[arg(long, value_name = "TEXT")]
+        note: Option<String>,
+        /// Resolve the plugins and show what would be added without changing any files
+        #[arg(long)]
+        dry_run: bool,
+        /// List the plugin's versions compatible with the manifest's Minecraft
+        /// version instead of adding it
+        ///
+        /// Shows each version with its publication date and supported Minecraft
+        /// versions, newest first. The manifest is not changed.
+        #[arg(long, conflicts_with_all = ["no_update", "upgrade", "note", "dry_run"])]
+        list_versions: bool,
+        /// Print a JSON report instead of progress output
+        #[arg(long)]
+        json: bool,
     },
-    /// Remove a plugin from the manifest
+    /// Remove plugins from the manifest
     ///
-    /// Removes a plugin from plugins.toml by its name (the key in the manifest).
+    /// Removes plugins from plugins.toml by their names (the keys in the manifest).
+    /// The lockfile is updated once after all of them are removed.
     Remove {
-        /// Plugin name to remove
-        spec: String,
+        /// Plugin names to remove
+        #[arg(required = true, value_name = "NAME")]
+        specs: Vec<String>,
         /// Skip automatic lockfile update after removing
         #[arg(long)]
         no_update: bool,
+        /// Also delete the plugin's JAR from the plugins directory
+        #[arg(long)]
+        purge: bool,
+        /// Print a JSON report instead of progress output
+        #[arg(long)]
+        json: bool,
     },
     /// Generate or update the lockfile
     ///
     /// Resolves plugin versions and generates plugins.lock with exact versions,
     /// filenames, URLs, and hashes. This ensures reproducible installations.
+    ///
+    /// With --verbose, also shows for each plugin the API endpoint queried,
+    /// how many versions it returned and how many are compatible, and the
+    /// selected version's publication date, file, and hash.
     Lock {
         /// Preview changes without writing the lockfile
         #[arg(long)]
         dry_run: bool,
+        /// Maximum number of plugins to resolve in parallel
+        ///
+        /// Defaults to `lock.concurrency` from the global config, or 4.
+        /// Use 1 to resolve plugins one at a time.
+        #[arg(long, value_name = "N")]
+        concurrency: Option<usize>,
+        /// Only resolve plugins in this group (plus plugins in no group)
+        ///
+        /// Lock entries for plugins outside the group are kept as they are.
+        #[arg(long, value_name = "NAME")]
+        group: Option<String>,
+        /// Re-resolve only this plugin, keeping every other lock entry (repeatable)
+        ///
+        /// Requires an existing lockfile. Unlike `mpm update`, the manifest is
+        /// left alone, so version pins still apply.
+        #[arg(long = "update", value_name = "NAME")]
+        update: Vec<String>,
+        /// Write a CycloneDX 1.4 SBOM (JSON) to this path after locking
+        #[cfg(feature = "sbom")]
+        #[arg(long, value_name = "FILE")]
+        sbom: Option<String>,
+        /// Also write a `sha256sum` checksum file for the locked plugins
+        ///
+        /// Written to plugins.sha256 next to the lockfile unless
+        /// --checksums-file names another path. Plugins locked with SHA-512
+        /// go to a `.sha512` file beside it.
+        #[arg(long, conflicts_with_all = ["dry_run", "check"])]
+        save_checksums: bool,
+        /// Path of the checksum file written by --save-checksums
+        #[arg(long, value_name = "FILE", requires = "save_checksums")]
+        checksums_file: Option<String>,
+        /// Print a JSON report including every locked plugin
+        #[arg(long)]
+        json: bool,
+        /// Exit 1 if the lockfile is out of date, without writing it
+        ///
+        /// Resolves every plugin without showing progress and lists the
+        /// plugins whose lock entry would change.
+        #[arg(long, conflicts_with_all = ["dry_run", "json"])]
+        check: bool,
+        /// Exit 2 without writing the lockfile if it would gain a plugin
+        ///
+        /// Version bumps of plugins already in the lockfile are still allowed.
+        #[arg(long, conflicts_with = "check")]
+        fail_on_new: bool,
+        /// Exit 2 without writing the lockfile if it would lose a plugin
+        #[arg(long, conflicts_with = "check")]
+        fail_on_removed: bool,
+    },
+    /// Update plugins to their latest compatible versions
+    ///
+    /// Clears the pinned version of each named plugin (or every plugin if no
+    /// names are given), resolves the latest version compatible with the
+    /// manifest's Minecraft version, and regenerates the lockfile.
+    ///
+    /// Plugins that fail to resolve are reported individually and keep their
+    /// current pin.
+    Update {
+        /// Plugin names to update (defaults to all plugins)
+        names: Vec<String>,
+        /// Preview updates without modifying the manifest or lockfile
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Freeze plugins at their locked versions
+    ///
+    /// Copies each plugin's version from plugins.lock into plugins.toml, so
+    /// later runs of `mpm lock` keep resolving the same version.
+    Pin {
+        /// Plugin names to pin
+        #[arg(required_unless_present = "all")]
+        names: Vec<String>,
+        /// Pin every plugin in the manifest
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+        /// Skip automatic lockfile update after pinning
+        #[arg(long)]
+        no_update: bool,
+    },
+    /// Clear pinned versions so plugins follow the latest compatible release
+    Unpin {
+        /// Plugin names to unpin
+        #[arg(required_unless_present = "all")]
+        names: Vec<String>,
+        /// Unpin every plugin in the manifest
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+        /// Skip automatic lockfile update after unpinning
+        #[arg(long)]
+        no_update: bool,
+    },
+    /// Leave plugins out of the lockfile and plugins directory, keeping them in the manifest
+    ///
+    /// Sets `disabled = true` on each plugin and re-locks, so the next
+    /// `mpm sync` removes their files. Undo with `mpm enable`.
+    Disable {
+        /// Plugin names to disable
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Skip automatic lockfile update after disabling
+        #[arg(long)]
+        no_update: bool,
+    },
+    /// Turn disabled plugins back on and re-lock
+    Enable {
+        /// Plugin names to enable
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Skip automatic lockfile update after enabling
+        #[arg(long)]
+        no_update: bool,
+    },
+    /// Show plugins with newer compatible versions available
+    ///
+    /// Resolves the latest version of every locked plugin that is compatible
+    /// with the manifest's Minecraft version and reports any that differ from
+    /// the lockfile. Nothing is modified; run `mpm update` to upgrade.
+    ///
+    /// With --verbose, also shows the first line of each new version's
+    /// changelog (Modrinth only).
+    ///
+    /// Exit codes:
+    ///   0 = all plugins are up to date, or --fail-on-outdated=false
+    ///   1 = at least one plugin is outdated
+    ///   2 = a plugin could not be checked
+    Outdated {
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+        /// Exit 1 when any plugin is outdated (on by default, so CI runs with
+        /// CI=true fail on stale plugins; pass =false to only report them)
+        #[arg(
+            long,
+            default_value_t = true,
+            action = clap::ArgAction::Set,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "true"
+        )]
+        fail_on_outdated: bool,
+    },
+    /// Upgrade outdated plugins and sync them
+    ///
+    /// Finds the plugins `mpm outdated` would report and asks before
+    /// upgrading each one. Upgrading clears the plugin's pinned version,
+    /// regenerates the lockfile, and syncs the plugins directory. Named
+    /// plugins, or all of them with --yes, are upgraded without asking.
+    ///
+    /// Exit codes:
+    ///   0 = plugins upgraded, or nothing to upgrade
+    ///   1 = lock or sync finished with warnings
+    ///   2 = a plugin could not be checked, or lock or sync failed
+    Upgrade {
+        /// Plugin names to upgrade (defaults to every outdated plugin)
+        names: Vec<String>,
+        /// Upgrade every outdated plugin without prompting
+        #[arg(long, short)]
+        yes: bool,
+        /// Update the lockfile but leave the plugins directory alone
+        #[arg(long)]
+        no_sync: bool,
     },
     /// Synchronize plugins directory with lockfile
     ///
@@ -82,6 +343,197 @@ pub enum Commands {
         /// Preview changes without modifying the plugins directory
         #[arg(long)]
         dry_run: bool,
+        /// Maximum number of plugins to download in parallel
+        ///
+        /// Defaults to `sync.concurrency` or `download_concurrency` from the
+        /// global config, or 4.
+        #[arg(long, value_name = "N")]
+        concurrency: Option<usize>,
+        /// Only install plugins in this group (plus plugins in no group)
+        ///
+        /// Plugins from other groups are removed from the plugins directory.
+        #[arg(long, value_name = "NAME")]
+        group: Option<String>,
+        /// Only sync these plugins (comma-separated or repeated)
+        ///
+        /// Nothing is removed from the plugins directory, and the lockfile is
+        /// left as it is.
+        #[arg(
+            long,
+            value_name = "NAME",
+            value_delimiter = ',',
+            conflicts_with = "exclude"
+        )]
+        include: Vec<String>,
+        /// Sync every plugin except these (comma-separated or repeated)
+        ///
+        /// The excluded plugins' files are left as they are.
+        #[arg(long, value_name = "NAME", value_delimiter = ',')]
+        exclude: Vec<String>,
+        /// Leave JARs that aren't in the lockfile in the plugins directory
+        ///
+        /// Also set by `sync.keep_unmanaged` in the global config. `mpm doctor`
+        /// still reports the files as unmanaged.
+        #[arg(long)]
+        keep_unmanaged: bool,
+        /// Re-download every plugin, even files whose hash already matches
+        ///
+        /// For recovering from corrupt plugin files.
+        #[arg(long)]
+        force: bool,
+        /// Install from the local JAR cache instead of downloading
+        ///
+        /// Every regular sync caches the JARs it downloads. Fails with the
+        /// list of missing files if the cache doesn't have them all.
+        #[arg(long)]
+        offline: bool,
+        /// Print a JSON report of downloaded, already synced and removed files
+        #[arg(long)]
+        json: bool,
+        /// Serve a health check endpoint on this port while syncing
+        ///
+        /// Returns 503 with {"status":"syncing"} while a sync is in progress
+        /// and 200 with {"status":"idle"} otherwise. The server shuts down
+        /// once the sync completes.
+        #[cfg(feature = "health-server")]
+        #[arg(long, value_name = "PORT")]
+        health_port: Option<u16>,
+    },
+    /// Show the file changes `sync` would make
+    ///
+    /// Compares every locked plugin with the plugins directory and lists the
+    /// files that would be added, replaced, or removed. Exits with 1 when
+    /// changes are pending, like `sync --dry-run`.
+    Diff {
+        /// Output every plugin file as JSON, including unchanged ones
+        #[arg(long)]
+        json: bool,
+    },
+    /// Render the lockfile as an install script for servers without mpm
+    ///
+    /// Every plugin is downloaded from its locked URL to its locked filename
+    /// and checked against its locked hash. Nothing is fetched while
+    /// exporting. Local plugins are skipped with a warning.
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value = "shell")]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Generate files from the lockfile for tools that don't use mpm
+    Generate {
+        #[command(subcommand)]
+        what: GenerateAction,
+    },
+    /// Restore the previous lockfile and re-sync
+    ///
+    /// Copies plugins.lock.bak (written whenever `lock` changes the lockfile)
+    /// over plugins.lock, then syncs the plugins directory to match. Only the
+    /// most recent previous lockfile is kept.
+    Rollback {
+        /// Confirm the rollback (required, to prevent accidental rollbacks in CI)
+        #[arg(long)]
+        confirm: bool,
+    },
+    /// Remove leftover sync directories and, optionally, cached data
+    ///
+    /// Deletes the `.plugins.staging` and `.plugins.backup` directories an
+    /// interrupted sync can leave in the plugins directory, and reports the
+    /// disk space freed.
+    Clean {
+        /// Also clear the API response and JAR cache (rebuilt from the network as needed)
+        #[arg(long)]
+        cache: bool,
+        /// Remove everything: also clears the cache and deletes plugins.lock.bak
+        #[arg(long)]
+        all: bool,
+    },
+    /// Drop lockfile entries for plugins that are no longer in the manifest
+    ///
+    /// Entries can go stale after `remove --no-update` or hand edits to
+    /// plugins.toml. The previous lockfile is kept as plugins.lock.bak.
+    ///
+    /// Exit codes:
+    ///   0 = nothing to prune
+    ///   1 = entries were pruned (or would be, with --dry-run)
+    Prune {
+        /// List stale entries without writing the lockfile
+        #[arg(long)]
+        dry_run: bool,
+        /// Also delete the stale plugins' JARs from the plugins directory
+        #[arg(long)]
+        purge: bool,
+    },
+    /// Search for plugins across all sources
+    ///
+    /// Queries every source in priority order (hangar, modrinth, spigot,
+    /// github), merges results with the same name, and prints each match with
+    /// the `source:id` to pass to `mpm add`.
+    Search {
+        /// Search terms
+        query: String,
+        /// Only search this source
+        #[arg(long)]
+        source: Option<String>,
+        /// Maximum number of results to request from each source
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show details about a plugin without adding it
+    ///
+    /// Prints the description, author, license, latest compatible version,
+    /// all compatible versions, download URL, and hash. Inside a project the
+    /// manifest's Minecraft version is used for compatibility filtering.
+    Info {
+        /// Plugin spec (format: source:id or id)
+        spec: String,
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List managed plugins and their sync state
+    ///
+    /// Shows each locked plugin's source, version, and filename along with
+    /// whether the file in the plugins directory is synced, missing, or has a
+    /// hash mismatch. JAR files not tracked by the lockfile are listed as
+    /// unmanaged. With --verbose, also shows when the lockfile was last updated.
+    List {
+        /// Output results as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check that every locked plugin file is present with the right hash
+    ///
+    /// A fast, offline check for cron jobs or systemd `ExecStartPre`: only the
+    /// lockfile and plugin files are read, with one line of output per plugin.
+    ///
+    /// Exit codes:
+    ///   0 = all files match the lockfile
+    ///   1 = a file is missing or has a different hash
+    ///   2 = the lockfile can't be read
+    Verify {
+        /// Print nothing; report only through the exit code
+        #[arg(long, short)]
+        quiet: bool,
+    },
+    /// Print a one-line summary of the plugins directory
+    ///
+    /// A compact alternative to `mpm doctor` for scripts, e.g.
+    /// "✓ 12 plugins synced" or "✗ 2 missing, 1 hash mismatch, 3 unmanaged".
+    ///
+    /// Exit codes:
+    ///   0 = every plugin is synced
+    ///   1 = unmanaged files only
+    ///   2 = missing or mismatched plugins, or not initialized
+    Status {
+        /// Print only the counts: synced, missing, hash mismatch, unmanaged
+        #[arg(long)]
+        count: bool,
     },
     /// Check plugin manager health
     ///
@@ -99,6 +551,52 @@ pub enum Commands {
         /// status, summary counts, and detailed check results.
         #[arg(long)]
         json: bool,
+        /// Attempt to fix the issues found, then check again
+        ///
+        /// Generates a missing lockfile, re-downloads missing or mismatched
+        /// plugins, and removes unmanaged JARs after asking. Other issues are
+        /// listed as needing manual intervention. The exit code reflects the
+        /// state after fixing.
+        #[arg(long, conflicts_with = "json")]
+        fix: bool,
+        /// Remove unmanaged JARs without asking (with --fix)
+        #[arg(long, requires = "fix")]
+        yes: bool,
+        /// Also check that every source's API can be reached
+        ///
+        /// Requests go through the proxy from HTTPS_PROXY / HTTP_PROXY, if
+        /// set, so this verifies the proxy configuration too.
+        #[arg(long)]
+        check_network: bool,
+        /// Also check that the Modrinth and GitHub tokens are accepted
+        ///
+        /// Sends MODRINTH_TOKEN and GITHUB_TOKEN (when set) to their APIs
+        /// and shows the rate limit each grants. A rejected token is an error.
+        #[arg(long)]
+        check_auth: bool,
+        /// Only check this plugin: its lock entry, its file and hash, and
+        /// that its download URL still answers
+        #[arg(long, value_name = "NAME")]
+        plugin: Option<String>,
+    },
+    /// Check the manifest and lockfile for common mistakes
+    ///
+    /// Works offline. Finds plugins declared twice under different names,
+    /// ids that come from more than one source, empty ids, pinned versions
+    /// the lockfile doesn't match, and lockfile hashes without a known
+    /// algorithm prefix.
+    ///
+    /// Exit codes:
+    ///   0 = no issues
+    ///   1 = warnings only
+    ///   2 = errors found
+    Lint {
+        /// Correct the issues that can be fixed automatically
+        ///
+        /// Duplicate plugins are removed, keeping the entry named after the
+        /// id (or the first name alphabetically).
+        #[arg(long)]
+        fix: bool,
     },
     /// Import existing plugins from /plugins directory
     ///
@@ -109,6 +607,9 @@ pub enum Commands {
     /// their proper source and ID. Plugins not found in any source are skipped
     /// with a warning.
     ///
+    /// With --from, the plugins are read from a text file instead, one
+    /// `mpm add` spec (source:id[@version] or id[@version]) per line.
+    ///
     /// This command requires that plugins.toml does not already exist.
     ///
     /// If no version is provided, attempts to auto-detect from Paper JAR file.
@@ -117,5 +618,180 @@ pub enum Commands {
         /// Minecraft version (e.g., 1.20.2). If not provided, attempts auto-detection from Paper JAR.
         #[arg(long)]
         version: Option<String>,
+        /// Server software, stored in the new manifest and used to resolve plugins
+        ///
+        /// Proxies (bungeecord, waterfall, velocity) read bungee.yml or
+        /// velocity-plugin.json from each JAR before plugin.yml.
+        #[arg(
+            long = "type",
+            visible_alias = "platform",
+            value_name = "TYPE",
+            value_enum
+        )]
+        server_type: Option<ServerType>,
+        /// Read plugin specs from this file instead of scanning the plugins directory
+        ///
+        /// Blank lines and lines starting with '#' are ignored.
+        #[arg(long, value_name = "FILE")]
+        from: Option<String>,
+        /// Only look plugins up in this source (modrinth, hangar, spigot, github, ...)
+        ///
+        /// Faster than searching every source, and picks the same project
+        /// every time when a name exists on several platforms.
+        #[arg(long, value_name = "NAME")]
+        source: Option<String>,
+        /// Look up every plugin and show what would be imported, without writing any files
+        ///
+        /// Exits with 1 if some plugins would be skipped.
+        #[arg(long)]
+        dry_run: bool,
+        /// Replace an existing plugins.toml instead of refusing to import
+        ///
+        /// The old lockfile is kept as plugins.lock.bak. Asks for
+        /// confirmation unless --yes is passed.
+        #[arg(long)]
+        overwrite: bool,
+        /// Overwrite without asking (with --overwrite)
+        #[arg(long, requires = "overwrite")]
+        yes: bool,
+    },
+    /// Create plugins.toml and plugins.lock from another plugin setup
+    ///
+    /// With --from plugman, reads plugin names (and optional versions) from a
+    /// PlugMan-style YAML file and searches every source for each one.
+    ///
+    /// With --from plaintext, reads one download URL per line. Modrinth,
+    /// Hangar and GitHub URLs are added from those sources; any other URL is
+    /// added as a `url` plugin.
+    ///
+    /// Plugins that can't be found are skipped with a warning. This command
+    /// requires that plugins.toml does not already exist.
+    Migrate {
+        /// Format of the file to migrate
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        from: MigrateFormat,
+        /// File to migrate
+        file: String,
+        /// Minecraft version (e.g., 1.20.2). If not provided, attempts auto-detection from Paper JAR.
+        #[arg(long)]
+        version: Option<String>,
+        /// Server software, stored in the new manifest and used to resolve plugins
+        #[arg(
+            long = "type",
+            visible_alias = "platform",
+            value_name = "TYPE",
+            value_enum
+        )]
+        server_type: Option<ServerType>,
+        /// Print the manifest that would be written, without writing any files
+        ///
+        /// Exits with 1 if some plugins would be skipped.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Read or change the global config (~/.config/mpm/config.toml)
+    ///
+    /// Keys are dotted paths such as `lock.concurrency`. Values are checked
+    /// against the key's type before the file is written.
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage the plugins of several servers, e.g. a proxy and its backends
+    ///
+    /// Servers are registered by name in mpm-workspace.toml, and commands run
+    /// in each server's directory. Every server keeps its own plugins.toml
+    /// and plugins.lock.
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+    /// Print a shell completion script
+    ///
+    /// Covers every command and flag. For bash, add
+    /// `eval "$(mpm completions bash)"` to ~/.bashrc.
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print a key's effective value
+    Get {
+        /// Dotted key, e.g. lock.concurrency
+        key: String,
+    },
+    /// Set a key in the global config file
+    Set {
+        /// Dotted key, e.g. lock.concurrency
+        key: String,
+        /// New value, e.g. 8, true, 1.21.4 or '["modrinth", "hangar"]'
+        value: String,
+    },
+    /// Print every key with its effective value, including defaults
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum GenerateAction {
+    /// Write checksums of the installed plugins in `sha256sum` format
+    ///
+    /// Lists `<hex>  <filename>` for each locked plugin whose file is in the
+    /// plugins directory and matches its locked hash, so `sha256sum -c
+    /// SHA256SUMS` can be run there without mpm. Missing or changed files
+    /// are skipped with a warning.
+    Checksums {
+        /// Hash algorithm to write
+        #[arg(long, value_enum, default_value = "sha256")]
+        format: ChecksumFormat,
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceAction {
+    /// Register a server directory under a name
+    Add {
+        /// Name to refer to the server by, e.g. lobby
+        name: String,
+        /// Server directory, absolute or relative to the workspace
+        path: String,
+    },
+    /// List the registered servers
+    List,
+    /// Run `mpm lock` in each server
+    Lock {
+        /// Servers to lock
+        #[arg(value_name = "NAME", required_unless_present = "all")]
+        servers: Vec<String>,
+        /// Lock every server
+        #[arg(long, conflicts_with = "servers")]
+        all: bool,
+    },
+    /// Run `mpm sync` in each server, one at a time unless --parallel is given
+    Sync {
+        /// Servers to sync
+        #[arg(value_name = "NAME", required_unless_present = "all")]
+        servers: Vec<String>,
+        /// Sync every server
+        #[arg(long, conflicts_with = "servers")]
+        all: bool,
+        /// Sync the servers at the same time
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// Run `mpm doctor` in every server and summarize the results
+    ///
+    /// Exits with the worst exit code of any server.
+    Doctor {
+        /// Output every server's doctor report as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
@@ -1,38 +1,154 @@
 // Add command for adding a plugin to the manifest
 
 use crate::commands::lock;
+use crate::commands::report::Report;
+use crate::config;
+use crate::error::MpmError;
 use crate::manifest::{Manifest, PluginSpec};
-use crate::sources::REGISTRY;
+use crate::sources::{REGISTRY, ResolveOptions, ResolvedVersion, modrinth};
 use crate::ui;
 use futures::future::join_all;
 use log::debug;
-use std::time::Duration;
 use tokio::time::timeout;
 
-pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> anyhow::Result<()> {
-    // Parse spec format:
-    // - source:id or source:id@version (e.g., modrinth:fabric-api)
-    // - id or id@version (searches through all sources in priority order)
-    let (source, id_version) = if let Some(colon_pos) = spec.find(':') {
-        let source = &spec[..colon_pos];
-        let id_version = &spec[colon_pos + 1..];
-        (Some(source), id_version)
-    } else {
+/// Split a plugin spec into (source, id, version)
+///
+/// Accepted formats:
+/// - source:id or source:id@version (e.g., modrinth:fabric-api)
+/// - id or id@version (searches through all sources in priority order)
+pub fn parse_spec(spec: &str) -> (Option<&str>, &str, Option<String>) {
+    let (source, id_version) = match spec.split_once(':') {
+        Some((source, id_version)) => (Some(source), id_version),
         // No colon found, will search through all sources
-        (None, spec.as_str())
+        None => (None, spec),
     };
 
-    let (id, version) = if let Some(at_pos) = id_version.find('@') {
-        let id = &id_version[..at_pos];
-        let version = Some(id_version[at_pos + 1..].to_string());
-        (id, version)
-    } else {
-        (id_version, None)
-    };
+    match id_version.split_once('@') {
+        Some((id, version)) => (source, id, Some(version.to_string())),
+        None => (source, id_version, None),
+    }
+}
 
+/// Add plugins to the manifest, then lock once
+///
+/// Each spec is added on its own; one that fails doesn't stop the rest, and
+/// the plugins that were added are still saved before the failures are reported.
+/// A dry run resolves every spec the same way but saves nothing.
+pub async fn add(
+    specs: &[String],
+    no_update: bool,
+    skip_compatibility: bool,
+    upgrade: bool,
+    json: bool,
+    note: Option<String>,
+    dry_run: bool,
+) -> anyhow::Result<()> {
     // Load existing manifest
-    let mut manifest = Manifest::load()
-        .map_err(|_| anyhow::anyhow!("Manifest not found. Run 'mpm init' first."))?;
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+
+    if dry_run {
+        ui::status("[DRY RUN]", "Previewing plugins to add...");
+    }
+
+    let mut added = Vec::new();
+    let mut failed = Vec::new();
+    for spec in specs {
+        match add_one(
+            &mut manifest,
+            spec,
+            skip_compatibility,
+            upgrade,
+            note.clone(),
+            dry_run,
+        )
+        .await
+        {
+            Ok(name) => added.push(name),
+            Err(e) => {
+                if specs.len() > 1 {
+                    ui::error(&format!("{}: {:#}", spec, e));
+                }
+                failed.push((spec.as_str(), e));
+            }
+        }
+    }
+
+    if !added.is_empty() && !dry_run {
+        manifest.save()?;
+
+        // Automatically lock after adding unless --no-update is specified
+        if !no_update {
+            lock::lock(false).await?;
+        }
+    }
+
+    let summary = format!(
+        "{} {} plugin(s), {} failed",
+        if dry_run { "Would add" } else { "Added" },
+        added.len(),
+        failed.len()
+    );
+    if specs.len() > 1 {
+        if failed.is_empty() {
+            ui::success(&summary);
+        } else {
+            ui::warning(&summary);
+        }
+    }
+
+    // A single spec fails with its own error, keeping its exit code and JSON
+    if specs.len() == 1
+        && let Some((_, e)) = failed.pop()
+    {
+        return Err(e);
+    }
+    if !failed.is_empty() {
+        let names: Vec<&str> = failed.iter().map(|(spec, _)| *spec).collect();
+        anyhow::bail!(
+            "Failed to add {} of {} plugin(s): {}",
+            failed.len(),
+            specs.len(),
+            names.join(", ")
+        );
+    }
+
+    if json {
+        let message = match added.as_slice() {
+            [(_, message)] => message.clone(),
+            _ => summary,
+        };
+        let names = added.into_iter().map(|(name, _)| name).collect();
+        Report::ok(message, names).print()?;
+    }
+    Ok(())
+}
+
+/// Resolve one spec and put it in the manifest, returning its name and the
+/// message printed for it
+async fn add_one(
+    manifest: &mut Manifest,
+    spec: &str,
+    skip_compatibility: bool,
+    upgrade: bool,
+    note: Option<String>,
+    dry_run: bool,
+) -> anyhow::Result<(String, String)> {
+    let (source, id, version) = parse_spec(spec);
+
+    // Refuse plugins the admin has explicitly prohibited
+    check_deny_list(source, id)?;
+
+    // Replacing an existing entry must be asked for explicitly
+    let upgrade_from = match manifest.plugins.get(id) {
+        Some(_) if !upgrade => anyhow::bail!(
+            "Plugin '{}' is already in the manifest. Use --upgrade to change its version.",
+            id
+        ),
+        Some(existing) => Some(existing.version.clone()),
+        None => None,
+    };
+
+    let options = resolve_options(manifest, id);
 
     let minecraft_version = if skip_compatibility {
         None
@@ -50,7 +166,7 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
         let spinner = ui::spinner(&format!("Searching for {}...", id));
 
         let sources = REGISTRY.get_priority_order();
-        let timeout_duration = Duration::from_secs(180); // 3 minutes
+        let timeout_duration = config::global().source_timeout();
 
         // Create futures for all sources with timeout
         let futures: Vec<_> = sources
@@ -59,6 +175,7 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
                 let source_name = source_impl.name();
                 let id = id.to_string();
                 let version_clone = version.clone();
+                let options = options.clone();
                 let minecraft_version_clone: Option<String> =
                     minecraft_version.map(|s| s.to_string());
 
@@ -67,16 +184,17 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
                     let minecraft_version_ref: Option<&str> = minecraft_version_clone.as_deref();
                     let result = timeout(
                         timeout_duration,
-                        source_impl.resolve_version(
+                        source_impl.resolve_version_with(
                             &id,
                             version_clone.as_deref(),
                             minecraft_version_ref,
+                            &options,
                         ),
                     )
                     .await;
 
                     match result {
-                        Ok(Ok(_)) => Ok((source_name, id)),
+                        Ok(Ok(resolved)) => Ok((source_name, id, resolved)),
                         Ok(Err(e)) => {
                             debug!("Source '{}' failed for plugin '{}': {}", source_name, id, e);
                             Err((source_name, e))
@@ -85,7 +203,10 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
                             debug!("Source '{}' timed out for plugin '{}'", source_name, id);
                             Err((
                                 source_name,
-                                anyhow::anyhow!("Search timed out after 3 minutes"),
+                                anyhow::anyhow!(
+                                    "Search timed out after {}s",
+                                    timeout_duration.as_secs()
+                                ),
                             ))
                         }
                     }
@@ -100,17 +221,18 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
         let mut errors = Vec::new();
         for result in results {
             match result {
-                Ok((source_name, plugin_id)) => {
+                Ok((source_name, plugin_id, resolved)) => {
                     debug!("Found plugin '{}' in source '{}'", plugin_id, source_name);
                     ui::clear_bar(&spinner);
-                    return add_plugin_to_manifest(
-                        &mut manifest,
+                    return Ok(insert_plugin(
+                        manifest,
                         source_name,
                         &plugin_id,
                         version,
-                        no_update,
-                    )
-                    .await;
+                        upgrade_from,
+                        note,
+                        dry_run.then_some(&resolved),
+                    ));
                 }
                 Err((source_name, err)) => {
                     errors.push((source_name, err));
@@ -120,61 +242,344 @@ pub async fn add(spec: String, no_update: bool, skip_compatibility: bool) -> any
 
         // If we get here, plugin wasn't found in any source
         ui::finish_spinner_error(&spinner, &format!("{} not found", id));
-        let error_msg = if let Some((last_source, last_err)) = errors.first() {
-            format!(
+        let not_found = anyhow::Error::new(MpmError::PluginNotFound {
+            source: None,
+            id: id.to_string(),
+        });
+        return Err(match errors.first() {
+            Some((last_source, last_err)) => not_found.context(format!(
                 "Plugin '{}' not found in any source. Last attempted source '{}': {}",
                 id, last_source, last_err
-            )
-        } else {
-            format!("Plugin '{}' not found in any source.", id)
-        };
-        anyhow::bail!(error_msg);
+            )),
+            None => not_found,
+        });
     };
 
     // Source was explicitly specified, validate and add
     source_impl.validate_plugin_id(id)?;
 
     // Check compatibility with Minecraft version
-    let _resolved = source_impl
-        .resolve_version(id, version.as_deref(), minecraft_version)
+    let resolved = match source_impl
+        .resolve_version_with(id, version.as_deref(), minecraft_version, &options)
         .await
-        .map_err(|e| {
-            anyhow::anyhow!(
-                "Failed to resolve plugin '{}' from source '{}': {}",
-                id,
-                source_name,
-                e
-            )
-        })?;
-
-    add_plugin_to_manifest(&mut manifest, source_name, id, version, no_update).await
+    {
+        Ok(resolved) => resolved,
+        Err(e) if source_name == "modrinth" && is_not_found(&e) => {
+            return Err(with_suggestions(e, id).await);
+        }
+        Err(e) => anyhow::bail!(
+            "Failed to resolve plugin '{}' from source '{}': {}",
+            id,
+            source_name,
+            e
+        ),
+    };
+
+    Ok(insert_plugin(
+        manifest,
+        source_name,
+        id,
+        version,
+        upgrade_from,
+        note,
+        dry_run.then_some(&resolved),
+    ))
+}
+
+fn is_not_found(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<MpmError>(),
+        Some(MpmError::PluginNotFound { .. })
+    )
+}
+
+/// Name Modrinth projects with similar slugs in a not-found error
+///
+/// The search is best effort: if it fails or finds nothing, the original
+/// error is returned unchanged.
+async fn with_suggestions(e: anyhow::Error, id: &str) -> anyhow::Error {
+    match modrinth::ModrinthSource::suggest(id).await {
+        Ok(suggestions) if !suggestions.is_empty() => e.context(format!(
+            "Plugin '{}' not found on Modrinth. Did you mean: {}?",
+            id,
+            suggestions.join(", ")
+        )),
+        Ok(_) => e,
+        Err(search_error) => {
+            debug!("Failed to search Modrinth for '{}': {}", id, search_error);
+            e
+        }
+    }
+}
+
+/// Print the versions of one plugin compatible with the manifest's Minecraft
+/// version, newest first, without changing the manifest
+#[allow(clippy::print_stdout)]
+pub async fn list_versions(
+    specs: &[String],
+    skip_compatibility: bool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let [spec] = specs else {
+        anyhow::bail!("--list-versions takes a single plugin");
+    };
+    let manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let (source, id, version) = parse_spec(spec);
+    if version.is_some() {
+        anyhow::bail!("--list-versions lists every version, so leave out '@<version>'");
+    }
+
+    let options = resolve_options(&manifest, id);
+    let minecraft_version = if skip_compatibility {
+        None
+    } else {
+        Some(manifest.minecraft.version.as_str())
+    };
+
+    let sources = match source {
+        Some(source) => {
+            let source_impl = REGISTRY.get_or_error(source)?;
+            source_impl.validate_plugin_id(id)?;
+            vec![source_impl]
+        }
+        None => REGISTRY.get_priority_order(),
+    };
+
+    let spinner = ui::spinner(&format!("Listing versions of {}...", id));
+    let timeout_duration = config::global().source_timeout();
+    let futures: Vec<_> = sources
+        .into_iter()
+        .map(|source_impl| {
+            let options = &options;
+            async move {
+                let source_name = source_impl.name();
+                debug!("Listing versions of '{}' from source '{}'", id, source_name);
+                match timeout(
+                    timeout_duration,
+                    source_impl.list_versions(id, minecraft_version, options),
+                )
+                .await
+                {
+                    Ok(result) => result
+                        .map(|versions| (source_name, versions))
+                        .map_err(|e| (source_name, e)),
+                    Err(_) => Err((
+                        source_name,
+                        anyhow::anyhow!("Search timed out after {}s", timeout_duration.as_secs()),
+                    )),
+                }
+            }
+        })
+        .collect();
+
+    // Results come back in priority order; take the first hit
+    let mut errors = Vec::new();
+    let mut found = None;
+    for result in join_all(futures).await {
+        match result {
+            Ok(listing) => {
+                found = Some(listing);
+                break;
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    let Some((source_name, versions)) = found else {
+        ui::finish_spinner_error(&spinner, &format!("{} not found", id));
+        return Err(match (source, errors.into_iter().next()) {
+            (Some(_), Some((_, e))) => e,
+            (None, Some((first_source, first_err))) => {
+                anyhow::Error::new(MpmError::PluginNotFound {
+                    source: None,
+                    id: id.to_string(),
+                })
+                .context(format!(
+                    "Plugin '{}' not found in any source. First attempted source '{}': {}",
+                    id, first_source, first_err
+                ))
+            }
+            (_, None) => anyhow::Error::new(MpmError::PluginNotFound {
+                source: source.map(str::to_string),
+                id: id.to_string(),
+            }),
+        });
+    };
+    ui::clear_bar(&spinner);
+
+    if json {
+        let output = serde_json::json!({
+            "source": source_name,
+            "id": id,
+            "minecraft_version": minecraft_version,
+            "versions": versions,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if versions.is_empty() {
+        ui::warning(&match minecraft_version {
+            Some(mc_version) => format!(
+                "{}:{} has no versions compatible with Minecraft {}",
+                source_name, id, mc_version
+            ),
+            None => format!("{}:{} has no versions", source_name, id),
+        });
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 3]> = versions
+        .iter()
+        .map(|v| {
+            [
+                v.version.clone(),
+                published_date(&v.published_at),
+                v.mc_versions.join(", "),
+            ]
+        })
+        .collect();
+    ui::table(["VERSION", "PUBLISHED", "MINECRAFT"], &rows);
+    ui::dim(&format!(
+        "Add one with 'mpm add {}:{}@<version>'",
+        source_name, id
+    ));
+    Ok(())
 }
 
-async fn add_plugin_to_manifest(
+/// Loaders and platform to check along with the Minecraft version,
+/// keeping any override a plugin already in the manifest has
+fn resolve_options(manifest: &Manifest, id: &str) -> ResolveOptions {
+    match manifest.plugins.get(id) {
+        Some(existing) => manifest.resolve_options(existing),
+        None => ResolveOptions {
+            loaders: manifest.default_loaders(),
+            platform: manifest.default_platform(),
+            prerelease: config::global().include_prereleases,
+            ..Default::default()
+        },
+    }
+}
+
+/// The date part of a source's publication time, which is either an
+/// RFC 3339 timestamp or Unix seconds (Spigot)
+fn published_date(published_at: &str) -> String {
+    if let Ok(secs) = published_at.parse::<i64>()
+        && let Some(time) = chrono::DateTime::from_timestamp(secs, 0)
+    {
+        return time.format("%Y-%m-%d").to_string();
+    }
+    published_at
+        .split_once('T')
+        .map_or(published_at, |(date, _)| date)
+        .to_string()
+}
+
+/// Put a resolved plugin in the manifest, returning its name and message
+///
+/// `preview` is the resolved version in a dry run, which is described
+/// instead of reported as added.
+fn insert_plugin(
     manifest: &mut Manifest,
     source: &str,
     id: &str,
     version: Option<String>,
-    no_update: bool,
-) -> anyhow::Result<()> {
+    upgrade_from: Option<Option<String>>,
+    note: Option<String>,
+    preview: Option<&ResolvedVersion>,
+) -> (String, String) {
     // Add plugin to manifest (compatibility check passed)
     let plugin_name = id.to_string();
+    // Keep per-plugin overrides, and any note, across upgrades
+    let existing = manifest.plugins.get(&plugin_name);
+    let loaders = existing.and_then(|p| p.loaders.clone());
+    let platform = existing.and_then(|p| p.platform.clone());
+    let prerelease = existing.and_then(|p| p.prerelease);
+    let asset_pattern = existing.and_then(|p| p.asset_pattern.clone());
+    let channel = existing.and_then(|p| p.channel.clone());
+    let unzip = existing.and_then(|p| p.unzip.clone());
+    let disabled = existing.and_then(|p| p.disabled);
+    let notes = note.or_else(|| existing.and_then(|p| p.notes.clone()));
     manifest.plugins.insert(
         plugin_name.clone(),
         PluginSpec {
             source: source.to_string(),
             id: id.to_string(),
-            version,
+            version: version.clone(),
+            loaders,
+            platform,
+            notes,
+            prerelease,
+            asset_pattern,
+            channel,
+            unzip,
+            disabled,
         },
     );
 
-    manifest.save()?;
-    ui::success(&format!("Added {} from {}", plugin_name, source));
+    if let Some(resolved) = preview {
+        let message = match &upgrade_from {
+            Some(_) => format!("Would upgrade {} from {}", plugin_name, source),
+            None => format!("Would add {} from {}", plugin_name, source),
+        };
+        ui::action(&message);
+        ui::dim(&format!(
+            "  version {}, file {}",
+            resolved.version, resolved.filename
+        ));
+        return (plugin_name, message);
+    }
 
-    // Automatically lock after adding unless --no-update is specified
-    if !no_update {
-        lock::lock(false).await?;
+    let message = match &upgrade_from {
+        Some(_) => format!("Upgraded {} from {}", plugin_name, source),
+        None => format!("Added {} from {}", plugin_name, source),
+    };
+    ui::success(&message);
+    if let Some(previous) = upgrade_from {
+        ui::dim(&format!(
+            "  {}: {} → {}",
+            plugin_name,
+            previous.as_deref().unwrap_or("latest"),
+            version.as_deref().unwrap_or("latest")
+        ));
     }
+    (plugin_name, message)
+}
+
+/// Fail if the plugin matches an entry in the configured deny-list
+///
+/// Entries are either `id` (blocks the ID from any source) or `source:id`.
+/// When no source was given on the command line, source-qualified entries
+/// also block the ID since the search may resolve to that source.
+fn check_deny_list(source: Option<&str>, id: &str) -> anyhow::Result<()> {
+    let Some(path) = config::global().deny_list_path() else {
+        return Ok(());
+    };
 
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read deny-list '{}': {}", path.display(), e))?;
+
+    if is_denied(&text, source, id) {
+        anyhow::bail!(
+            "Plugin '{}' is on the deny-list. Contact your admin if this is an error.",
+            id
+        );
+    }
+
+    debug!("Plugin '{}' not found in deny-list {}", id, path.display());
     Ok(())
 }
+
+fn is_denied(deny_list: &str, source: Option<&str>, id: &str) -> bool {
+    deny_list
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .any(|entry| match entry.split_once(':') {
+            Some((denied_source, denied_id)) => {
+                denied_id.eq_ignore_ascii_case(id)
+                    && source.is_none_or(|s| denied_source.eq_ignore_ascii_case(s))
+            }
+            None => entry.eq_ignore_ascii_case(id),
+        })
+}
@@ -0,0 +1,80 @@
+// Clean command for removing leftover sync directories and cached data
+
+use crate::commands::sync;
+use crate::config;
+use crate::ui;
+use indicatif::HumanBytes;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn clean(cache: bool, all: bool) -> anyhow::Result<()> {
+    let plugins_dir = config::plugins_dir();
+
+    // Sizes are measured before anything is deleted
+    let mut targets: Vec<PathBuf> = vec![
+        sync::staging_dir(&plugins_dir),
+        sync::backup_dir(&plugins_dir),
+    ];
+    if (cache || all)
+        && let Some(cache_dir) = config::cache_dir()
+    {
+        targets.push(cache_dir);
+    }
+    // Without the backup `mpm rollback` has nothing to restore, so ask first
+    let lockfile_backup = PathBuf::from(config::lockfile_backup_path());
+    if all
+        || (lockfile_backup.exists()
+            && ui::confirm(&format!(
+                "Also remove {}? `mpm rollback` won't be possible afterwards.",
+                lockfile_backup.display()
+            )))
+    {
+        targets.push(lockfile_backup);
+    }
+    let removed: Vec<(PathBuf, u64)> = targets
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| {
+            let size = disk_usage(&path);
+            (path, size)
+        })
+        .collect();
+
+    sync::cleanup_temp_dirs(&plugins_dir)?;
+    for (path, _) in &removed {
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    if removed.is_empty() {
+        ui::dim("Nothing to clean");
+        return Ok(());
+    }
+    for (path, size) in &removed {
+        ui::action(&format!(
+            "Removed {} ({})",
+            path.display(),
+            HumanBytes(*size)
+        ));
+    }
+    let freed: u64 = removed.iter().map(|(_, size)| size).sum();
+    ui::success(&format!("Freed {}", HumanBytes(freed)));
+
+    Ok(())
+}
+
+/// Total size of a file, or of every file under a directory
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| disk_usage(&e.path())).sum())
+        .unwrap_or(0)
+}
@@ -0,0 +1,15 @@
+// Completions command for generating shell completion scripts
+
+use crate::cli::Cli;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+/// Write the completion script for `shell` to stdout
+pub fn completions(shell: Shell) -> anyhow::Result<()> {
+    // Generate into memory first; clap_complete panics if stdout closes early
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "mpm", &mut script);
+    std::io::stdout().write_all(&script)?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+// Config command for reading and writing the global config file
+
+use crate::config::{self, GlobalConfig};
+use crate::ui;
+
+/// Every key `mpm config` can read or write, as dotted paths
+const KEYS: &[&str] = &[
+    "default_mc_version",
+    "http_timeout_secs",
+    "download_concurrency",
+    "github_token",
+    "hangar_api_key",
+    "source_priority",
+    "include_prereleases",
+    "http.max_retries",
+    "cache.enabled",
+    "cache.ttl_secs",
+    "security.deny_list",
+    "sync.concurrency",
+    "sync.keep_unmanaged",
+    "sync.post_command",
+    "lock.concurrency",
+    "lock.warn_stale_days",
+];
+
+/// Keys whose values are hidden by `mpm config list`
+const SECRET_KEYS: &[&str] = &["github_token", "hangar_api_key"];
+
+/// Print the effective value of one key
+#[allow(clippy::print_stdout)]
+pub fn get(key: &str) -> anyhow::Result<()> {
+    check_key(key)?;
+    match effective_value(config::global(), key)? {
+        Some(toml::Value::String(value)) => println!("{}", value),
+        Some(value) => println!("{}", value),
+        None => ui::dim(&format!("{} is not set", key)),
+    }
+    Ok(())
+}
+
+/// Set one key in the global config file, checking the value's type first
+pub fn set(key: &str, value: &str) -> anyhow::Result<()> {
+    check_key(key)?;
+    let path = config::global_config_path()
+        .ok_or_else(|| anyhow::anyhow!("Could not find a directory for the global config"))?;
+
+    let mut table: toml::Table = if path.exists() {
+        let text = std::fs::read_to_string(&path)?;
+        toml::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid global config '{}': {}", path.display(), e))?
+    } else {
+        toml::Table::new()
+    };
+
+    // Values that look like TOML (numbers, booleans, arrays) are tried as
+    // such first, so `8` becomes an integer but `1.21` can still be a version
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = toml::from_str::<toml::Table>(&format!("value = {}", value))
+        && let Some(parsed) = parsed.get("value")
+    {
+        candidates.push(parsed.clone());
+    }
+    candidates.push(toml::Value::String(value.to_string()));
+
+    let mut error = None;
+    for candidate in candidates {
+        set_path(&mut table, key, candidate);
+        let text = toml::to_string_pretty(&table)?;
+        match GlobalConfig::parse(&text) {
+            Ok(_) => {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&path, text)?;
+                ui::success(&format!("Set {} = {}", key, value));
+                return Ok(());
+            }
+            Err(e) => error = error.or(Some(e)),
+        }
+    }
+
+    let reason = error.map_or_else(String::new, |e| match e.downcast_ref::<toml::de::Error>() {
+        Some(e) => e.message().to_string(),
+        None => e
+            .to_string()
+            .trim_start_matches(&format!("{}: ", key))
+            .to_string(),
+    });
+    anyhow::bail!("Invalid value '{}' for {}: {}", value, key, reason)
+}
+
+/// Print every key with its effective value
+#[allow(clippy::print_stdout)]
+pub fn list() -> anyhow::Result<()> {
+    let global = config::global();
+    for key in KEYS {
+        let value = match effective_value(global, key)? {
+            Some(_) if SECRET_KEYS.contains(key) => "(hidden)".to_string(),
+            Some(value) => value.to_string(),
+            None => "(not set)".to_string(),
+        };
+        println!("{} = {}", key, value);
+    }
+    Ok(())
+}
+
+fn check_key(key: &str) -> anyhow::Result<()> {
+    if !KEYS.contains(&key) {
+        anyhow::bail!(
+            "Unknown config key '{}'. Available keys: {}",
+            key,
+            KEYS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The value a key has in effect, including built-in defaults
+fn effective_value(global: &GlobalConfig, key: &str) -> anyhow::Result<Option<toml::Value>> {
+    let value = toml::Value::try_from(global)?;
+    let configured = key
+        .split('.')
+        .try_fold(&value, |value, part| value.get(part))
+        .cloned();
+    Ok(configured.or_else(|| match key {
+        "default_mc_version" => Some(global.default_minecraft_version().into()),
+        "sync.concurrency" => Some((global.sync_concurrency() as i64).into()),
+        "lock.concurrency" => Some((global.lock_concurrency() as i64).into()),
+        "lock.warn_stale_days" => Some((global.lock_warn_stale_days() as i64).into()),
+        _ => None,
+    }))
+}
+
+/// Set a dotted key in a TOML table, creating intermediate tables
+fn set_path(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let (parents, last) = match key.rsplit_once('.') {
+        Some((parents, last)) => (parents.split('.').collect(), last),
+        None => (Vec::new(), key),
+    };
+    let mut table = table;
+    for part in parents {
+        let entry = table
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().expect("just made a table");
+    }
+    table.insert(last.to_string(), value);
+}
@@ -0,0 +1,180 @@
+// Diff command for previewing the file changes a sync would make
+
+use crate::commands::sync::verify_plugin_hash;
+use crate::config;
+use crate::jar;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::ui;
+use console::style;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DiffAction {
+    Add,
+    Replace,
+    Remove,
+    Ok,
+}
+
+/// Name and version read from a JAR's plugin.yml
+type PluginYml = (String, Option<String>);
+
+#[derive(Debug, Serialize)]
+struct DiffEntry {
+    action: DiffAction,
+    filename: String,
+    plugin_name: Option<String>,
+    old_version: Option<String>,
+    new_version: Option<String>,
+}
+
+pub fn diff(json: bool) -> anyhow::Result<i32> {
+    // Exit codes (mirroring `sync --dry-run`):
+    // 0 = no changes
+    // 1 = changes pending
+    // 2 = errors present
+
+    let lockfile = Lockfile::load_existing()?;
+
+    let entries = collect_entries(&config::plugins_dir(), &lockfile);
+    let has_changes = entries.iter().any(|e| e.action != DiffAction::Ok);
+
+    if json {
+        print_json(&entries)?;
+    } else if has_changes {
+        print_diff(&entries);
+    } else {
+        ui::success("Plugins directory is in sync with the lockfile");
+    }
+
+    Ok(if has_changes { 1 } else { 0 })
+}
+
+fn collect_entries(plugins_dir: &str, lockfile: &Lockfile) -> Vec<DiffEntry> {
+    let plugins_path = Path::new(plugins_dir);
+    let managed_files: HashSet<&str> = lockfile.plugin.iter().map(|p| p.file.as_str()).collect();
+
+    // JARs the lockfile doesn't know about, with the plugin.yml name when readable
+    let mut unmanaged: Vec<(String, Option<PluginYml>)> = Vec::new();
+    if let Ok(dir_entries) = fs::read_dir(plugins_path) {
+        for entry in dir_entries.flatten() {
+            let path = entry.path();
+            if path.is_file()
+                && let Some(filename) = path.file_name().and_then(|n| n.to_str())
+                && filename.ends_with(".jar")
+                && !managed_files.contains(filename)
+            {
+                let plugin_yml = jar::read_plugin_yml_from_jar(&path).ok();
+                unmanaged.push((filename.to_string(), plugin_yml));
+            }
+        }
+    }
+    unmanaged.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut entries = Vec::new();
+    for plugin in &lockfile.plugin {
+        let file_path = plugins_path.join(&plugin.file);
+        if file_path.exists() {
+            let action = if is_synced(&file_path, plugin) {
+                DiffAction::Ok
+            } else {
+                DiffAction::Replace
+            };
+            let old_version = match action {
+                DiffAction::Replace => jar_version(&file_path),
+                _ => Some(plugin.version.clone()),
+            };
+            entries.push(entry(action, plugin, old_version));
+            continue;
+        }
+
+        // A new filename for a plugin that is already installed under an old one
+        // (e.g. worldedit-7.3.jar -> worldedit-7.4.jar) counts as a replacement
+        let previous = unmanaged.iter().position(|(_, yml)| {
+            yml.as_ref()
+                .is_some_and(|(name, _)| name.eq_ignore_ascii_case(&plugin.name))
+        });
+        match previous {
+            Some(index) => {
+                let (_, yml) = unmanaged.remove(index);
+                let old_version = yml.and_then(|(_, version)| version);
+                entries.push(entry(DiffAction::Replace, plugin, old_version));
+            }
+            None => entries.push(entry(DiffAction::Add, plugin, None)),
+        }
+    }
+
+    entries.extend(unmanaged.into_iter().map(|(filename, _)| DiffEntry {
+        action: DiffAction::Remove,
+        filename,
+        plugin_name: None,
+        old_version: None,
+        new_version: None,
+    }));
+
+    entries
+}
+
+fn entry(action: DiffAction, plugin: &LockedPlugin, old_version: Option<String>) -> DiffEntry {
+    DiffEntry {
+        action,
+        filename: plugin.file.clone(),
+        plugin_name: Some(plugin.name.clone()),
+        old_version,
+        new_version: Some(plugin.version.clone()),
+    }
+}
+
+fn is_synced(file_path: &Path, plugin: &LockedPlugin) -> bool {
+    plugin.parse_hash().is_ok_and(|(algorithm, _)| {
+        verify_plugin_hash(file_path, algorithm).is_ok_and(|hash| hash == plugin.hash)
+    })
+}
+
+fn jar_version(file_path: &Path) -> Option<String> {
+    jar::read_plugin_yml_from_jar(file_path)
+        .ok()
+        .and_then(|(_, version)| version)
+}
+
+#[allow(clippy::print_stdout)]
+fn print_json(entries: &[DiffEntry]) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(entries)?);
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+fn print_diff(entries: &[DiffEntry]) {
+    for entry in entries {
+        match entry.action {
+            DiffAction::Add => println!(
+                "{} {} {}",
+                style("+").green(),
+                entry.filename,
+                style("(new)").dim()
+            ),
+            DiffAction::Replace => println!(
+                "{} {} {}",
+                style("~").yellow(),
+                entry.filename,
+                style(format!(
+                    "({} → {})",
+                    entry.old_version.as_deref().unwrap_or("unknown"),
+                    entry.new_version.as_deref().unwrap_or("unknown")
+                ))
+                .dim()
+            ),
+            DiffAction::Remove => println!(
+                "{} {} {}",
+                style("-").red(),
+                entry.filename,
+                style("(unmanaged, would be removed)").dim()
+            ),
+            DiffAction::Ok => {}
+        }
+    }
+}
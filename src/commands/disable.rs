@@ -0,0 +1,44 @@
+// Disable and enable commands for leaving plugins out without removing them
+
+use crate::commands::pin::{save_and_relock, select_targets};
+use crate::config;
+use crate::error::MpmError;
+use crate::manifest::Manifest;
+use crate::ui;
+
+/// Mark each plugin disabled, so lock and sync leave it out
+pub async fn disable(names: Vec<String>, no_update: bool) -> anyhow::Result<()> {
+    set_disabled(names, true, no_update).await
+}
+
+/// Clear the disabled mark, so the plugins are locked and synced again
+pub async fn enable(names: Vec<String>, no_update: bool) -> anyhow::Result<()> {
+    set_disabled(names, false, no_update).await
+}
+
+async fn set_disabled(names: Vec<String>, disabled: bool, no_update: bool) -> anyhow::Result<()> {
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let targets = select_targets(&manifest, names, false)?;
+
+    let mut changed = 0;
+    for name in &targets {
+        let Some(entry) = manifest.plugin_mut(config::env(), name) else {
+            continue;
+        };
+        if entry.is_disabled() == disabled {
+            let state = if disabled { "disabled" } else { "enabled" };
+            ui::dim(&format!("{} is already {}", name, state));
+            continue;
+        }
+        // Enabling drops the field rather than writing `disabled = false`
+        entry.disabled = disabled.then_some(true);
+        if disabled {
+            ui::success(&format!("Disabled {}", name));
+        } else {
+            ui::success(&format!("Enabled {}", name));
+        }
+        changed += 1;
+    }
+
+    save_and_relock(&manifest, changed, no_update).await
+}
@@ -1,14 +1,56 @@
 // Doctor module for health checking
 
-use crate::commands::sync::verify_plugin_hash;
+use crate::commands::lock;
+use crate::commands::sync::{self, SyncOptions, verify_plugin_hash};
 use crate::config;
 use crate::constants;
-use crate::lockfile::Lockfile;
+use crate::lockfile::{LockedPlugin, Lockfile};
 use crate::manifest::Manifest;
+use crate::sources::http;
+use crate::sources::{GitHubSource, LocalSource};
 use crate::ui;
+use log::debug;
+use reqwest::StatusCode;
 use serde::Serialize;
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Remaining fraction of the GitHub rate limit below which doctor adds a note
+const GITHUB_RATE_LIMIT_NOTE_THRESHOLD: f64 = 0.2;
+
+/// Source APIs probed by `--check-network`
+const NETWORK_ENDPOINTS: &[(&str, &str)] = &[
+    ("modrinth", "https://api.modrinth.com/v2/"),
+    (
+        "hangar",
+        "https://hangar.papermc.io/api/v1/projects?limit=1",
+    ),
+    ("spigot", "https://api.spiget.org/v2/"),
+    ("github", "https://api.github.com/"),
+];
+
+/// Endpoints `--check-auth` sends each source's token to, with the variable
+/// the token is read from
+const AUTH_ENDPOINTS: &[(&str, &str, &str)] = &[
+    (
+        "modrinth",
+        http::MODRINTH_TOKEN_ENV,
+        "https://api.modrinth.com/v2/user",
+    ),
+    (
+        "github",
+        http::GITHUB_TOKEN_ENV,
+        "https://api.github.com/rate_limit",
+    ),
+];
+
+/// Header both Modrinth and GitHub use for the requests allowed per window
+const RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+
+/// How long each `--check-network` probe may take
+const NETWORK_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Serialize)]
 struct Issue {
@@ -31,6 +73,8 @@ struct LockfileInfo {
     present: bool,
     valid: bool,
     path: String,
+    /// When `mpm lock` last changed the lockfile, if recorded
+    locked_at: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,11 +95,84 @@ struct DoctorOutput {
     manifest: ManifestInfo,
     lockfile: LockfileInfo,
     plugins: PluginsInfo,
+    /// Only present with `--check-network`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network: Option<NetworkInfo>,
+    /// Only present with `--check-auth`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth: Option<Vec<AuthInfo>>,
     issues: Vec<Issue>,
 }
 
+#[derive(Debug, Serialize)]
+struct NetworkInfo {
+    /// Proxy in use, with any password hidden
+    proxy: Option<String>,
+    endpoints: Vec<EndpointInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct EndpointInfo {
+    source: String,
+    url: String,
+    reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AuthInfo {
+    source: String,
+    /// Environment variable the token is read from
+    token_env: String,
+    token_set: bool,
+    /// Whether the API accepted the token; null without a token or if it couldn't be checked
+    accepted: Option<bool>,
+    /// Requests allowed per rate-limit window, as reported by the API
+    rate_limit: Option<u64>,
+}
+
+/// Outcome of trying to fix one issue with `--fix`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixResult {
+    Fixed,
+    Failed,
+    Skipped,
+}
+
 #[allow(clippy::print_stdout)]
-pub fn check_health(json: bool) -> anyhow::Result<i32> {
+pub async fn check_health(
+    json: bool,
+    fix: bool,
+    yes: bool,
+    check_network: bool,
+    check_auth: bool,
+    plugin: Option<String>,
+) -> anyhow::Result<i32> {
+    let mut output = run_checks(check_network, check_auth, plugin.as_deref()).await;
+
+    if fix && !output.issues.is_empty() {
+        apply_fixes(&output, yes).await;
+        println!();
+        output = run_checks(check_network, check_auth, plugin.as_deref()).await;
+    }
+
+    if json {
+        // Output JSON (use println! for structured output)
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        // Output human-readable format
+        output_human_readable(&output);
+        if fix {
+            output_manual_issues(&output.issues);
+        }
+    }
+
+    Ok(output.exit_code)
+}
+
+/// Run every check, or with `plugin` only the checks for that one plugin
+async fn run_checks(check_network: bool, check_auth: bool, plugin: Option<&str>) -> DoctorOutput {
     let manifest_path = config::manifest_path();
     let lockfile_path = config::lockfile_path();
     let plugins_dir = config::plugins_dir();
@@ -69,7 +186,10 @@ pub fn check_health(json: bool) -> anyhow::Result<i32> {
 
     // Check plugins (only if lockfile is valid)
     let (plugins_info, plugins_issues) = if let Some(ref lockfile) = lockfile_opt {
-        check_plugins(&plugins_dir, lockfile)
+        match plugin {
+            Some(name) => check_single_plugin(&plugins_dir, lockfile, name).await,
+            None => check_plugins(&plugins_dir, lockfile, None),
+        }
     } else {
         // If lockfile is invalid, we can still check if the directory exists
         let dir_present = Path::new(&plugins_dir).exists();
@@ -86,6 +206,23 @@ pub fn check_health(json: bool) -> anyhow::Result<i32> {
         )
     };
     issues.extend(plugins_issues);
+    issues.extend(check_github_rate_limit().await);
+
+    let network = if check_network {
+        let (network, network_issues) = check_network_access().await;
+        issues.extend(network_issues);
+        Some(network)
+    } else {
+        None
+    };
+
+    let auth = if check_auth {
+        let (auth, auth_issues) = check_tokens().await;
+        issues.extend(auth_issues);
+        Some(auth)
+    } else {
+        None
+    };
 
     // Sort issues deterministically by code, then message
     issues.sort_by(|a, b| a.code.cmp(&b.code).then_with(|| a.message.cmp(&b.message)));
@@ -102,25 +239,127 @@ pub fn check_health(json: bool) -> anyhow::Result<i32> {
         ("ok".to_string(), 0)
     };
 
-    let output = DoctorOutput {
+    DoctorOutput {
         schema_version: constants::SCHEMA_VERSION,
-        status: status.clone(),
+        status,
         exit_code,
         manifest: manifest_info,
         lockfile: lockfile_info,
         plugins: plugins_info,
+        network,
+        auth,
         issues,
-    };
+    }
+}
 
-    if json {
-        // Output JSON (use println! for structured output)
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
-        // Output human-readable format
-        output_human_readable(&output);
+/// Whether `--fix` knows how to remediate an issue
+fn is_fixable(code: &str) -> bool {
+    matches!(
+        code,
+        "LOCKFILE_MISSING"
+            | "PLUGINS_DIR_MISSING"
+            | "PLUGIN_MISSING"
+            | "HASH_MISMATCH"
+            | "UNMANAGED_PLUGIN"
+    )
+}
+
+/// Try to fix every fixable issue in the report
+///
+/// A missing lockfile is regenerated with `lock`, unmanaged JARs are removed
+/// (after confirmation unless `yes`), and missing or mismatched plugins are
+/// re-downloaded with a sync that leaves any kept unmanaged JARs alone.
+async fn apply_fixes(output: &DoctorOutput, yes: bool) {
+    let has = |code: &str| output.issues.iter().any(|i| i.code == code);
+    let mut needs_sync =
+        has("PLUGINS_DIR_MISSING") || has("PLUGIN_MISSING") || has("HASH_MISMATCH");
+
+    if has("LOCKFILE_MISSING") {
+        if output.manifest.valid {
+            ui::action("Fixing LOCKFILE_MISSING: generating the lockfile");
+            let result = match lock::lock(false).await {
+                Ok(0) => FixResult::Fixed,
+                Ok(_) => FixResult::Failed,
+                Err(e) => {
+                    ui::error(&e.to_string());
+                    FixResult::Failed
+                }
+            };
+            log_fix("LOCKFILE_MISSING", result);
+            // The new lockfile's plugins still have to be installed
+            needs_sync = result == FixResult::Fixed;
+        } else {
+            log_fix("LOCKFILE_MISSING", FixResult::Skipped);
+        }
+    }
+
+    for issue in output
+        .issues
+        .iter()
+        .filter(|i| i.code == "UNMANAGED_PLUGIN")
+    {
+        let Some(path) = &issue.path else {
+            continue;
+        };
+        let filename = Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        let result = if yes || ui::confirm(&format!("Remove unmanaged plugin '{}'?", filename)) {
+            ui::action(&format!("Fixing UNMANAGED_PLUGIN: removing {}", filename));
+            match fs::remove_file(path) {
+                Ok(()) => FixResult::Fixed,
+                Err(e) => {
+                    ui::error(&format!("Failed to remove {}: {}", filename, e));
+                    FixResult::Failed
+                }
+            }
+        } else {
+            FixResult::Skipped
+        };
+        log_fix("UNMANAGED_PLUGIN", result);
     }
 
-    Ok(exit_code)
+    if needs_sync {
+        ui::action("Fixing missing or mismatched plugins: syncing from the lockfile");
+        let options = SyncOptions {
+            keep_unmanaged: true,
+            ..Default::default()
+        };
+        let result = match sync::sync_plugins_with_options(&options).await {
+            Ok(0) => FixResult::Fixed,
+            Ok(_) => FixResult::Failed,
+            Err(e) => {
+                ui::error(&e.to_string());
+                FixResult::Failed
+            }
+        };
+        log_fix("missing or mismatched plugins", result);
+    }
+}
+
+fn log_fix(what: &str, result: FixResult) {
+    match result {
+        FixResult::Fixed => ui::success(&format!("Fixed {}", what)),
+        FixResult::Failed => ui::warning(&format!("Could not fix {}", what)),
+        FixResult::Skipped => ui::dim(&format!("Skipped fixing {}", what)),
+    }
+}
+
+/// List the issues left after `--fix` that it can't remediate
+#[allow(clippy::print_stdout)]
+fn output_manual_issues(issues: &[Issue]) {
+    let manual: Vec<_> = issues
+        .iter()
+        .filter(|i| i.severity != "info" && !is_fixable(&i.code))
+        .collect();
+    if manual.is_empty() {
+        return;
+    }
+    println!("\nManual intervention required");
+    for issue in manual {
+        ui::warning(&format!("  {}: {}", issue.code, issue.message));
+    }
 }
 
 fn check_manifest(path: &str) -> (ManifestInfo, Vec<Issue>) {
@@ -138,8 +377,42 @@ fn check_manifest(path: &str) -> (ManifestInfo, Vec<Issue>) {
         });
     } else {
         match Manifest::load() {
-            Ok(_) => {
+            Ok(manifest) => {
                 valid = true;
+                for (group, plugin) in manifest.undeclared_group_members() {
+                    issues.push(Issue {
+                        severity: "error".to_string(),
+                        code: "GROUP_PLUGIN_UNDECLARED".to_string(),
+                        message: format!(
+                            "Group '{}' lists '{}', which is not declared in [plugins]",
+                            group, plugin
+                        ),
+                        path: Some(path.to_string()),
+                    });
+                }
+                match manifest.resolve_for_env(config::env()) {
+                    Ok(resolved) => {
+                        for (name, _) in resolved.plugins.iter().filter(|(_, s)| s.is_disabled()) {
+                            issues.push(Issue {
+                                severity: "info".to_string(),
+                                code: "PLUGIN_DISABLED".to_string(),
+                                message: format!(
+                                    "Plugin '{}' is disabled; `mpm enable {}` turns it back on",
+                                    name, name
+                                ),
+                                path: Some(path.to_string()),
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        issues.push(Issue {
+                            severity: "error".to_string(),
+                            code: "ENV_NOT_FOUND".to_string(),
+                            message: e.to_string(),
+                            path: Some(path.to_string()),
+                        });
+                    }
+                }
             }
             Err(e) => {
                 issues.push(Issue {
@@ -193,23 +466,77 @@ fn check_lockfile(path: &str) -> (LockfileInfo, Option<Lockfile>, Vec<Issue>) {
         }
     }
 
+    // The previous lockfile is a note: it's what `mpm rollback` restores
+    let backup_path = config::lockfile_backup_path();
+    if Path::new(&backup_path).exists() {
+        issues.push(Issue {
+            severity: "info".to_string(),
+            code: "LOCKFILE_BACKUP".to_string(),
+            message: format!(
+                "{}.bak holds the previous lockfile; `mpm rollback` restores it",
+                constants::LOCKFILE_FILE
+            ),
+            path: Some(backup_path),
+        });
+    }
+
     (
         LockfileInfo {
             present,
             valid,
             path: path.to_string(),
+            locked_at: lockfile_opt.as_ref().and_then(|l| l.locked_at.clone()),
         },
         lockfile_opt,
         issues,
     )
 }
 
-fn check_plugins(plugins_dir: &str, lockfile: &Lockfile) -> (PluginsInfo, Vec<Issue>) {
+/// How many locked plugins are synced, missing, or have the wrong hash, and
+/// how many JARs in the plugins directory are unmanaged, for `mpm status`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PluginCounts {
+    pub synced: usize,
+    pub missing: usize,
+    pub hash_mismatch: usize,
+    pub unmanaged: usize,
+}
+
+/// Count plugin file states with the same checks `mpm doctor` runs
+///
+/// Without a plugins directory, every locked plugin counts as missing.
+pub fn count_plugins(plugins_dir: &str, lockfile: &Lockfile) -> PluginCounts {
+    let (info, _) = check_plugins(plugins_dir, lockfile, None);
+    PluginCounts {
+        synced: info.installed,
+        missing: if info.directory_present {
+            info.missing.len()
+        } else {
+            info.expected
+        },
+        hash_mismatch: info.hash_mismatch.len(),
+        unmanaged: info.unmanaged.len(),
+    }
+}
+
+/// Check the plugin files, or with `only` just that plugin's file
+///
+/// Unmanaged JARs are only looked for when every plugin is checked.
+fn check_plugins(
+    plugins_dir: &str,
+    lockfile: &Lockfile,
+    only: Option<&str>,
+) -> (PluginsInfo, Vec<Issue>) {
     let mut issues = Vec::new();
     let plugins_path = Path::new(plugins_dir);
     let directory_present = plugins_path.exists();
 
-    let expected = lockfile.plugin.len();
+    let checked: Vec<&LockedPlugin> = lockfile
+        .plugin
+        .iter()
+        .filter(|p| only.is_none_or(|name| p.name == name))
+        .collect();
+    let expected = checked.len();
     let mut installed = 0;
     let mut missing = Vec::new();
     let mut hash_mismatch = Vec::new();
@@ -228,7 +555,7 @@ fn check_plugins(plugins_dir: &str, lockfile: &Lockfile) -> (PluginsInfo, Vec<Is
             lockfile.plugin.iter().map(|p| p.file.clone()).collect();
 
         // Check each plugin in lockfile
-        for plugin in &lockfile.plugin {
+        for plugin in &checked {
             let file_path = plugins_path.join(&plugin.file);
 
             if !file_path.exists() {
@@ -284,7 +611,9 @@ fn check_plugins(plugins_dir: &str, lockfile: &Lockfile) -> (PluginsInfo, Vec<Is
         }
 
         // Check for unmanaged files (sorted for determinism)
-        if let Ok(entries) = fs::read_dir(plugins_path) {
+        if only.is_none()
+            && let Ok(entries) = fs::read_dir(plugins_path)
+        {
             let mut unmanaged_files: Vec<String> = Vec::new();
             for entry in entries.flatten() {
                 let path = entry.path();
@@ -310,6 +639,20 @@ fn check_plugins(plugins_dir: &str, lockfile: &Lockfile) -> (PluginsInfo, Vec<Is
         }
     }
 
+    // Local plugins are copied from the project tree, so the source file must exist too
+    for plugin in checked.iter().filter(|p| p.source == "local") {
+        if let Some(source_path) = LocalSource::path_from_url(&plugin.url)
+            && !source_path.exists()
+        {
+            issues.push(Issue {
+                severity: "error".to_string(),
+                code: "LOCAL_SOURCE_MISSING".to_string(),
+                message: format!("Source file for local plugin '{}' not found", plugin.name),
+                path: Some(source_path.to_string_lossy().to_string()),
+            });
+        }
+    }
+
     (
         PluginsInfo {
             directory_present,
@@ -323,6 +666,219 @@ fn check_plugins(plugins_dir: &str, lockfile: &Lockfile) -> (PluginsInfo, Vec<Is
     )
 }
 
+/// Check one locked plugin: its file, its hash, and that its URL still answers
+async fn check_single_plugin(
+    plugins_dir: &str,
+    lockfile: &Lockfile,
+    name: &str,
+) -> (PluginsInfo, Vec<Issue>) {
+    let (info, mut issues) = check_plugins(plugins_dir, lockfile, Some(name));
+
+    let Some(plugin) = lockfile.plugin.iter().find(|p| p.name == name) else {
+        issues.push(Issue {
+            severity: "error".to_string(),
+            code: "PLUGIN_NOT_LOCKED".to_string(),
+            message: format!("Plugin '{}' not found in the lockfile", name),
+            path: Some(config::lockfile_path()),
+        });
+        return (info, issues);
+    };
+
+    // Local plugins are checked through their source file instead
+    if plugin.source != "local"
+        && let Err(e) = check_url(&plugin.source, &plugin.url).await
+    {
+        issues.push(Issue {
+            severity: "warning".to_string(),
+            code: "PLUGIN_URL_UNREACHABLE".to_string(),
+            message: format!("Download URL for '{}' is unreachable: {}", plugin.name, e),
+            path: Some(plugin.url.clone()),
+        });
+    }
+
+    (info, issues)
+}
+
+/// Send a HEAD request to a download URL, failing on any non-success status
+async fn check_url(source: &str, url: &str) -> Result<(), String> {
+    let request = http::client_for_source(source).head(url).send();
+    let response = timeout(NETWORK_CHECK_TIMEOUT, request)
+        .await
+        .map_err(|_| "timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+/// Note when unauthenticated GitHub requests are close to the hourly limit
+///
+/// Only checked when the manifest has GitHub plugins and no token is set.
+/// Network failures are ignored so doctor still works offline.
+async fn check_github_rate_limit() -> Vec<Issue> {
+    let uses_github = Manifest::load()
+        .map(|m| m.plugins.values().any(|spec| spec.source == "github"))
+        .unwrap_or(false);
+    if !uses_github || http::github_token().is_some() {
+        return Vec::new();
+    }
+
+    let rate = match timeout(Duration::from_secs(5), GitHubSource::rate_limit()).await {
+        Ok(Ok(rate)) => rate,
+        Ok(Err(e)) => {
+            debug!("Failed to check GitHub rate limit: {}", e);
+            return Vec::new();
+        }
+        Err(_) => {
+            debug!("Timed out checking GitHub rate limit");
+            return Vec::new();
+        }
+    };
+
+    if (rate.remaining as f64) >= (rate.limit as f64) * GITHUB_RATE_LIMIT_NOTE_THRESHOLD {
+        return Vec::new();
+    }
+
+    vec![Issue {
+        severity: "info".to_string(),
+        code: "GITHUB_RATE_LIMIT_LOW".to_string(),
+        message: format!(
+            "Only {} of {} unauthenticated GitHub API requests remain this hour. \
+            Set {} to raise the limit to 5000 requests per hour.",
+            rate.remaining,
+            rate.limit,
+            http::GITHUB_TOKEN_ENV
+        ),
+        path: None,
+    }]
+}
+
+/// Probe every source API through the shared client (and so any proxy)
+async fn check_network_access() -> (NetworkInfo, Vec<Issue>) {
+    let proxy = http::proxy_description();
+    let probes = NETWORK_ENDPOINTS.iter().map(|(source, url)| async move {
+        let result = timeout(NETWORK_CHECK_TIMEOUT, http::client().get(*url).send())
+            .await
+            .map_err(|_| "timed out".to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+        EndpointInfo {
+            source: source.to_string(),
+            url: url.to_string(),
+            reachable: result.is_ok(),
+            error: result.err(),
+        }
+    });
+    let endpoints = futures::future::join_all(probes).await;
+
+    let via = proxy
+        .as_deref()
+        .map(|p| format!(" through proxy {}", p))
+        .unwrap_or_default();
+    let issues = endpoints
+        .iter()
+        .filter(|e| !e.reachable)
+        .map(|e| Issue {
+            severity: "error".to_string(),
+            code: "NETWORK_UNREACHABLE".to_string(),
+            message: format!(
+                "Cannot reach {} API{}: {}",
+                e.source,
+                via,
+                e.error.as_deref().unwrap_or("unknown error")
+            ),
+            path: Some(e.url.clone()),
+        })
+        .collect();
+
+    (NetworkInfo { proxy, endpoints }, issues)
+}
+
+/// Send each configured token to its source's API to see that it is accepted
+///
+/// Sources without a token are listed but not contacted.
+async fn check_tokens() -> (Vec<AuthInfo>, Vec<Issue>) {
+    let probes = AUTH_ENDPOINTS
+        .iter()
+        .map(|(source, token_env, url)| async move {
+            let token_set = match *source {
+                "github" => http::github_token().is_some(),
+                _ => http::modrinth_token().is_some(),
+            };
+            let mut info = AuthInfo {
+                source: source.to_string(),
+                token_env: token_env.to_string(),
+                token_set,
+                accepted: None,
+                rate_limit: None,
+            };
+            if !token_set {
+                return (info, None);
+            }
+            // A token that can't be sent as a header was dropped with a warning
+            if !http::is_authenticated(source) {
+                info.accepted = Some(false);
+                let issue = Issue {
+                    severity: "error".to_string(),
+                    code: "AUTH_TOKEN_REJECTED".to_string(),
+                    message: format!(
+                        "{} contains characters that can't be sent in an HTTP header",
+                        token_env
+                    ),
+                    path: None,
+                };
+                return (info, Some(issue));
+            }
+
+            let request = http::client_for_source(source).get(*url).send();
+            let issue = match timeout(NETWORK_CHECK_TIMEOUT, request).await {
+                Ok(Ok(response)) if response.status().is_success() => {
+                    info.accepted = Some(true);
+                    info.rate_limit = response
+                        .headers()
+                        .get(RATELIMIT_LIMIT)
+                        .and_then(|v| v.to_str().ok()?.trim().parse().ok());
+                    debug!(
+                        "{} accepted {} (rate limit {:?})",
+                        source, token_env, info.rate_limit
+                    );
+                    None
+                }
+                Ok(Ok(response)) if response.status() == StatusCode::UNAUTHORIZED => {
+                    info.accepted = Some(false);
+                    Some(Issue {
+                        severity: "error".to_string(),
+                        code: "AUTH_TOKEN_REJECTED".to_string(),
+                        message: format!("{} rejected the token in {}", source, token_env),
+                        path: Some(url.to_string()),
+                    })
+                }
+                result => {
+                    let reason = match result {
+                        Ok(Ok(response)) => format!("HTTP {}", response.status()),
+                        Ok(Err(e)) => e.to_string(),
+                        Err(_) => "timed out".to_string(),
+                    };
+                    Some(Issue {
+                        severity: "warning".to_string(),
+                        code: "AUTH_CHECK_FAILED".to_string(),
+                        message: format!(
+                            "Could not check {} with {}: {}",
+                            token_env, source, reason
+                        ),
+                        path: Some(url.to_string()),
+                    })
+                }
+            };
+            (info, issue)
+        });
+
+    let (auth, issues): (Vec<AuthInfo>, Vec<Option<Issue>>) =
+        futures::future::join_all(probes).await.into_iter().unzip();
+    (auth, issues.into_iter().flatten().collect())
+}
+
 #[allow(clippy::print_stdout)]
 fn output_human_readable(output: &DoctorOutput) {
     // 1. Manifest section
@@ -370,11 +926,61 @@ fn output_human_readable(output: &DoctorOutput) {
                 ui::warning(&format!("  Unmanaged: {}", filename));
             }
         }
+        // Only reported by `--plugin`
+        for issue in &output.issues {
+            match issue.code.as_str() {
+                "PLUGIN_NOT_LOCKED" => ui::error(&format!("  {}", issue.message)),
+                "PLUGIN_URL_UNREACHABLE" => ui::warning(&format!("  {}", issue.message)),
+                _ => {}
+            }
+        }
     } else {
         ui::error("  Directory not found");
     }
 
-    // 4. Summary
+    // 4. Network section (with --check-network)
+    if let Some(network) = &output.network {
+        match &network.proxy {
+            Some(proxy) => println!("\nNetwork (proxy {})", proxy),
+            None => println!("\nNetwork (no proxy)"),
+        }
+        for endpoint in &network.endpoints {
+            match &endpoint.error {
+                None => ui::success(&format!("  {} reachable", endpoint.source)),
+                Some(e) => ui::error(&format!("  {} unreachable: {}", endpoint.source, e)),
+            }
+        }
+    }
+
+    // 5. Authentication section (with --check-auth)
+    if let Some(auth) = &output.auth {
+        println!("\nAuthentication");
+        for info in auth {
+            let limit = info
+                .rate_limit
+                .map(|limit| format!(" (rate limit {})", limit))
+                .unwrap_or_default();
+            match info.accepted {
+                _ if !info.token_set => ui::dim(&format!(
+                    "  {}: {} not set, requests are anonymous",
+                    info.source, info.token_env
+                )),
+                Some(true) => ui::success(&format!(
+                    "  {}: {} accepted{}",
+                    info.source, info.token_env, limit
+                )),
+                Some(false) => {
+                    ui::error(&format!("  {}: {} rejected", info.source, info.token_env))
+                }
+                None => ui::warning(&format!(
+                    "  {}: {} could not be checked",
+                    info.source, info.token_env
+                )),
+            }
+        }
+    }
+
+    // 6. Summary
     println!("\nSummary");
     let error_count = output
         .issues
@@ -397,6 +1003,19 @@ fn output_human_readable(output: &DoctorOutput) {
         ui::success("  No issues");
     }
 
+    // Notes are informational and don't affect the exit code
+    let notes: Vec<_> = output
+        .issues
+        .iter()
+        .filter(|i| i.severity == "info")
+        .collect();
+    if !notes.is_empty() {
+        println!("\nNotes");
+        for note in notes {
+            ui::dim(&format!("  {}", note.message));
+        }
+    }
+
     // Status line
     let status_label = match output.status.as_str() {
         "error" => "errors",
@@ -0,0 +1,194 @@
+// Export command for rendering the lockfile as a standalone install script
+
+use crate::constants;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::ui;
+use serde::Serialize;
+use std::fs;
+
+/// Formats `mpm export` can render the lockfile as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// POSIX shell script using curl or wget
+    Shell,
+    /// Dockerfile `RUN` instruction
+    Docker,
+    /// Ansible task list using `get_url`
+    Ansible,
+}
+
+#[derive(Debug, Serialize)]
+struct AnsibleTask {
+    name: String,
+    #[serde(rename = "ansible.builtin.get_url")]
+    get_url: AnsibleGetUrl,
+}
+
+#[derive(Debug, Serialize)]
+struct AnsibleGetUrl {
+    url: String,
+    dest: String,
+    checksum: String,
+    mode: String,
+}
+
+#[allow(clippy::print_stdout)]
+pub fn export(format: ExportFormat, output: Option<String>) -> anyhow::Result<()> {
+    let lockfile = Lockfile::load_existing()?;
+
+    // Local plugins live in the project tree, which the target server doesn't
+    // have, and zipped plugins need extracting, which the formats can't express
+    let (skipped, remote): (Vec<&LockedPlugin>, Vec<&LockedPlugin>) = lockfile
+        .plugin
+        .iter()
+        .partition(|p| p.source == "local" || p.zip_entry.is_some());
+    for plugin in &skipped {
+        if plugin.source == "local" {
+            ui::warning(&format!(
+                "Skipping local plugin '{}': copy {} to the server yourself",
+                plugin.name, plugin.file
+            ));
+        } else {
+            ui::warning(&format!(
+                "Skipping zipped plugin '{}': extract {} from {} yourself",
+                plugin.name, plugin.file, plugin.url
+            ));
+        }
+    }
+
+    let rendered = match format {
+        ExportFormat::Shell => render_shell(&remote)?,
+        ExportFormat::Docker => render_docker(&remote)?,
+        ExportFormat::Ansible => render_ansible(&remote)?,
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, rendered)?;
+            ui::success(&format!("Exported {} plugin(s) to {}", remote.len(), path));
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn header() -> String {
+    format!(
+        "# Generated by mpm export from {}. Do not edit; re-run mpm export instead.\n",
+        constants::LOCKFILE_FILE
+    )
+}
+
+/// Split a lockfile hash into the algorithm and hex digest
+fn split_hash(plugin: &LockedPlugin) -> anyhow::Result<(&str, &str)> {
+    let (algorithm, hex) = plugin.parse_hash()?;
+    match algorithm {
+        "sha256" | "sha512" => Ok((algorithm, hex)),
+        _ => anyhow::bail!(
+            "Plugin '{}' uses unsupported hash algorithm '{}'",
+            plugin.name,
+            algorithm
+        ),
+    }
+}
+
+/// Quote a string for a POSIX shell
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn render_shell(plugins: &[&LockedPlugin]) -> anyhow::Result<String> {
+    let mut script = String::from("#!/bin/sh\n");
+    script.push_str(&header());
+    script.push_str(
+        r#"set -eu
+
+PLUGINS_DIR="${PLUGINS_DIR:-plugins}"
+mkdir -p "$PLUGINS_DIR"
+
+download() {
+    if command -v curl >/dev/null 2>&1; then
+        curl -fsSL -o "$2" "$1"
+    else
+        wget -q -O "$2" "$1"
+    fi
+}
+
+verify() {
+    echo "$3  $2" | "$1sum" -c - >/dev/null || {
+        echo "Hash mismatch for $2" >&2
+        exit 1
+    }
+}
+"#,
+    );
+
+    for plugin in plugins {
+        let (algorithm, hex) = split_hash(plugin)?;
+        let dest = format!("\"$PLUGINS_DIR\"/{}", shell_quote(&plugin.file));
+        script.push_str(&format!(
+            "\n# {} {}\ndownload {} {}\nverify {} {} {}\n",
+            plugin.name,
+            plugin.version,
+            shell_quote(&plugin.url),
+            dest,
+            algorithm,
+            dest,
+            hex
+        ));
+    }
+
+    Ok(script)
+}
+
+fn render_docker(plugins: &[&LockedPlugin]) -> anyhow::Result<String> {
+    let mut dockerfile = header();
+    dockerfile.push_str("ARG PLUGINS_DIR=plugins\n");
+
+    let mut commands = vec!["mkdir -p \"$PLUGINS_DIR\"".to_string()];
+    for plugin in plugins {
+        let (algorithm, hex) = split_hash(plugin)?;
+        let dest = format!("\"$PLUGINS_DIR\"/{}", shell_quote(&plugin.file));
+        commands.push(format!(
+            "curl -fsSL -o {} {}",
+            dest,
+            shell_quote(&plugin.url)
+        ));
+        // `dest` stays outside double quotes so its single quotes are shell syntax
+        commands.push(format!(
+            "printf '%s  %s\\n' {} {} | {}sum -c -",
+            hex, dest, algorithm
+        ));
+    }
+    dockerfile.push_str(&format!("RUN {}\n", commands.join(" \\\n && ")));
+
+    Ok(dockerfile)
+}
+
+fn render_ansible(plugins: &[&LockedPlugin]) -> anyhow::Result<String> {
+    let tasks = plugins
+        .iter()
+        .map(|plugin| {
+            // get_url takes the same "algorithm:hex" checksum format as the lockfile
+            split_hash(plugin)?;
+            Ok(AnsibleTask {
+                name: format!("Download {} {}", plugin.name, plugin.version),
+                get_url: AnsibleGetUrl {
+                    url: plugin.url.clone(),
+                    dest: format!("{{{{ plugins_dir | default('plugins') }}}}/{}", plugin.file),
+                    checksum: plugin.hash.clone(),
+                    mode: "0644".to_string(),
+                },
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut playbook = header();
+    if tasks.is_empty() {
+        playbook.push_str("[]\n");
+    } else {
+        playbook.push_str(&serde_yaml::to_string(&tasks)?);
+    }
+    Ok(playbook)
+}
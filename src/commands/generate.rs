@@ -0,0 +1,79 @@
+// Generate command for writing files derived from the lockfile
+
+use crate::commands::sync::verify_plugin_hash;
+use crate::config;
+use crate::lockfile::Lockfile;
+use crate::ui;
+use std::fs;
+use std::path::Path;
+
+/// Hash algorithms `mpm generate checksums` can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ChecksumFormat {
+    /// `SHA256SUMS`, for `sha256sum -c`
+    Sha256,
+    /// `SHA512SUMS`, for `sha512sum -c`
+    Sha512,
+}
+
+impl ChecksumFormat {
+    fn algorithm(self) -> &'static str {
+        match self {
+            ChecksumFormat::Sha256 => "sha256",
+            ChecksumFormat::Sha512 => "sha512",
+        }
+    }
+}
+
+/// Write a checksum file in `sha256sum` format for the installed plugins
+///
+/// Only files matching their locked hash are listed, so the result vouches
+/// for the lockfile's contents rather than whatever is on disk. A plugin
+/// locked with another algorithm is re-hashed once it has been checked.
+#[allow(clippy::print_stdout)]
+pub fn checksums(format: ChecksumFormat, output: Option<String>) -> anyhow::Result<()> {
+    let lockfile = Lockfile::load_existing()?;
+    let plugins_dir = config::plugins_dir();
+    let algorithm = format.algorithm();
+
+    let mut lines = String::new();
+    let mut count = 0;
+    for plugin in &lockfile.plugin {
+        let file_path = Path::new(&plugins_dir).join(&plugin.file);
+        if !file_path.is_file() {
+            ui::warning(&format!(
+                "Skipping {}: {} is not in the plugins directory. Run 'mpm sync' first.",
+                plugin.name, plugin.file
+            ));
+            continue;
+        }
+
+        let (locked_algorithm, locked_hex) = plugin.parse_hash()?;
+        if verify_plugin_hash(&file_path, locked_algorithm)? != plugin.hash {
+            ui::warning(&format!(
+                "Skipping {}: {} does not match its locked hash",
+                plugin.name, plugin.file
+            ));
+            continue;
+        }
+
+        let hex = if locked_algorithm == algorithm {
+            locked_hex.to_string()
+        } else {
+            let hash = verify_plugin_hash(&file_path, algorithm)?;
+            hash[algorithm.len() + 1..].to_string()
+        };
+        lines.push_str(&format!("{}  {}\n", hex, plugin.file));
+        count += 1;
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(&path, lines)?;
+            ui::success(&format!("Wrote {} checksum(s) to {}", count, path));
+        }
+        None => print!("{}", lines),
+    }
+
+    Ok(())
+}
@@ -1,63 +1,73 @@
 // Import module for importing existing plugins from /plugins directory
 
+use crate::commands::add;
 use crate::config;
 use crate::constants;
+use crate::jar::read_plugin_descriptor;
 use crate::lockfile::{LockedPlugin, Lockfile};
-use crate::manifest::{Manifest, MinecraftSpec, PluginSpec};
-use crate::sources::REGISTRY;
+use crate::manifest::{Manifest, MinecraftSpec, PluginSpec, ServerType};
+use crate::sources::hash::{self, HashAlgorithm};
+use crate::sources::{REGISTRY, ResolveOptions, modrinth};
+use crate::ui;
 use futures::future::join_all;
 use log::{debug, info, warn};
-use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
 use tokio::time::timeout;
 
 /// Plugin information scanned from the plugins directory
-/// Tuple contains: (name, filename, version_option, hash)
+/// Tuple contains: (name, filename, version_option, sha512_hash)
 type ScannedPlugin = (String, String, Option<String>, String);
 
-#[derive(Debug, Deserialize, Serialize)]
-struct PluginYml {
-    name: Option<String>,
+pub async fn import_plugins(
     version: Option<String>,
-}
+    server_type: Option<ServerType>,
+    from: Option<String>,
+    source: Option<&str>,
+    dry_run: bool,
+    overwrite: bool,
+    yes: bool,
+) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = every plugin was imported (or would be, in a dry run)
+    // 1 = some plugins were skipped in a dry run
+
+    if let Some(source) = source {
+        REGISTRY.get_or_error(source)?;
+    }
 
-pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
-    // Check if plugins.toml already exists
-    if Manifest::load().is_ok() {
+    // An existing plugins.toml is only replaced when asked for
+    if overwrite && Path::new(&config::manifest_path()).exists() {
+        ui::warning(&format!(
+            "This will delete your existing {}. Your lockfile will be regenerated from the plugins directory.",
+            constants::MANIFEST_FILE
+        ));
+        if !dry_run && !yes && !ui::confirm(&format!("Replace {}?", constants::MANIFEST_FILE)) {
+            anyhow::bail!(
+                "Not overwriting {}. Pass --yes to confirm without a prompt.",
+                constants::MANIFEST_FILE
+            );
+        }
+    } else if Manifest::load().is_ok() {
         anyhow::bail!(
-            "{} already exists. Remove it first before importing.",
+            "{} already exists. Remove it first, or pass --overwrite to replace it.",
             constants::MANIFEST_FILE
         );
     }
 
-    // Determine which version to use
-    let final_version = if let Some(v) = version {
-        // User provided version explicitly, use it
-        v
-    } else {
-        // Try to detect from Paper JAR
-        match detect_minecraft_version_from_paper_jar() {
-            Some(detected_version) => {
-                info!(
-                    "Auto-detected Minecraft version {} from Paper JAR",
-                    detected_version
-                );
-                detected_version
-            }
-            None => {
-                warn!(
-                    "Could not detect Minecraft version from Paper JAR, using default: {}",
-                    constants::DEFAULT_MC_VERSION
-                );
-                constants::DEFAULT_MC_VERSION.to_string()
-            }
-        }
-    };
+    let final_version = choose_minecraft_version(version);
+
+    if dry_run {
+        ui::status("[DRY RUN]", "Previewing import...");
+    }
+
+    if let Some(list_path) = from {
+        return import_from_list(&list_path, final_version, server_type, source, dry_run).await;
+    }
 
     let plugins_dir = config::plugins_dir();
     let plugins_path = Path::new(&plugins_dir);
@@ -68,7 +78,7 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
     }
 
     // Scan plugins directory for JAR files
-    let plugins = scan_plugins_dir(&plugins_dir)?;
+    let plugins = scan_plugins_dir(&plugins_dir, server_type)?;
 
     debug!(
         "Scanned plugins directory: found {} plugin(s)",
@@ -77,40 +87,76 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
 
     if plugins.is_empty() {
         info!("No JAR files found in plugins directory");
+        if dry_run {
+            print_preview(0, &[], 0);
+            return Ok(0);
+        }
         // Create empty manifest and lockfile
-        let manifest = Manifest {
-            minecraft: MinecraftSpec {
-                version: final_version.clone(),
-            },
-            plugins: BTreeMap::new(),
-        };
-        manifest.save()?;
-
-        let lockfile = Lockfile::new();
-        lockfile.save()?;
+        save_import(final_version, server_type, BTreeMap::new(), Vec::new())?;
 
         info!(
             "Created empty {} and {}",
             constants::MANIFEST_FILE,
             constants::LOCKFILE_FILE
         );
-        return Ok(());
+        return Ok(0);
     }
 
     // Search for sources for each plugin
     let minecraft_version = Some(final_version.as_str());
+    let options = resolve_options(server_type);
     let mut manifest_plugins = BTreeMap::new();
     let mut lockfile_plugins = Vec::new();
 
-    let mut skipped_plugins = Vec::new();
-    for (name, filename, version_option, _hash) in &plugins {
-        debug!(
-            "Searching for plugin: name={}, filename={}, version={:?}",
-            name, filename, version_option
-        );
+    // Search for every plugin at once, with a spinner each that finishes as
+    // its result arrives; results are then taken in scan order
+    let multi = ui::multi_progress();
+    let semaphore = Arc::new(Semaphore::new(config::global().lock_concurrency()));
+    let handles: Vec<_> = plugins
+        .iter()
+        .map(|(name, filename, version_option, hash)| {
+            debug!(
+                "Searching for plugin: name={}, filename={}, version={:?}",
+                name, filename, version_option
+            );
+            let name = name.clone();
+            let version_option = version_option.clone();
+            let sha512 = hash.strip_prefix("sha512:").map(str::to_string);
+            let minecraft_version = minecraft_version.map(str::to_string);
+            let options = options.clone();
+            let source = source.map(str::to_string);
+            let semaphore = Arc::clone(&semaphore);
+            let multi = multi.clone();
+
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok();
+                let spinner = ui::multi_spinner(&multi, &format!("Searching for {}...", name));
+                // Try to find the plugin in sources using search functionality
+                let found = find_plugin_source(
+                    &name,
+                    version_option.as_deref(),
+                    minecraft_version.as_deref(),
+                    &options,
+                    source.as_deref(),
+                    sha512.as_deref(),
+                )
+                .await;
+                match &found {
+                    Some((source, plugin_id, _)) => ui::finish_spinner_success(
+                        &spinner,
+                        &format!("{} → {}:{}", name, source, plugin_id),
+                    ),
+                    None => ui::finish_spinner_error(&spinner, &format!("{} (skipped)", name)),
+                }
+                found
+            })
+        })
+        .collect();
 
-        // Try to find the plugin in sources using search functionality
-        match find_plugin_source(name, version_option.as_deref(), minecraft_version).await {
+    let mut skipped_plugins = Vec::new();
+    for ((name, filename, version_option, _), found) in plugins.iter().zip(join_all(handles).await)
+    {
+        match found? {
             Some((source, plugin_id, resolved)) => {
                 debug!(
                     "Plugin found in source: name={}, source={}, plugin_id={}",
@@ -123,6 +169,14 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
                         source: source.clone(),
                         id: plugin_id.clone(),
                         version: version_option.clone(),
+                        loaders: None,
+                        platform: None,
+                        notes: None,
+                        prerelease: None,
+                        asset_pattern: None,
+                        channel: None,
+                        unzip: None,
+                        disabled: None,
                     },
                 );
 
@@ -132,9 +186,13 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
                     name: name.clone(),
                     source,
                     version: resolved.version.clone(),
-                    file: filename.clone(),      // Keep local filename
-                    url: resolved.url.clone(),   // Use resolved URL
+                    file: filename.clone(),    // Keep local filename
+                    url: resolved.url.clone(), // Use resolved URL
+                    urls: (!resolved.urls.is_empty()).then(|| resolved.urls.clone()),
                     hash: resolved.hash.clone(), // Use resolved hash
+                    size_bytes: resolved.size_bytes,
+                    upstream_id: resolved.canonical_id.clone(),
+                    zip_entry: resolved.zip_entry.clone(),
                 });
             }
             None => {
@@ -153,27 +211,18 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
         }
     }
 
-    let imported_count = manifest_plugins.len();
-
-    let manifest = Manifest {
-        minecraft: MinecraftSpec {
-            version: final_version.clone(),
-        },
-        plugins: manifest_plugins,
-    };
-
-    // Create lockfile
-    let mut lockfile = Lockfile::new();
-    for plugin in lockfile_plugins {
-        lockfile.add_plugin(plugin);
+    if dry_run {
+        print_preview(plugins.len(), &lockfile_plugins, skipped_plugins.len());
+        return Ok(i32::from(!skipped_plugins.is_empty()));
     }
 
-    // Sort plugins by name
-    lockfile.sort_by_name();
-
-    // Save both files
-    manifest.save()?;
-    lockfile.save()?;
+    let imported_count = manifest_plugins.len();
+    let manifest = save_import(
+        final_version,
+        server_type,
+        manifest_plugins,
+        lockfile_plugins,
+    )?;
 
     debug!(
         "Import complete: imported={}, skipped={}",
@@ -194,26 +243,307 @@ pub async fn import_plugins(version: Option<String>) -> anyhow::Result<()> {
         }
     }
 
-    Ok(())
+    Ok(0)
+}
+
+/// The Minecraft version for a new manifest: the one given, else the
+/// detected one, else the configured default
+pub fn choose_minecraft_version(version: Option<String>) -> String {
+    if let Some(v) = version {
+        // User provided version explicitly, use it
+        return v;
+    }
+
+    // Try to detect from the Paper JAR or server.properties
+    match detect_minecraft_version() {
+        Some((detected_version, detected_from)) => {
+            info!(
+                "Auto-detected Minecraft version {} from {}",
+                detected_version, detected_from
+            );
+            detected_version
+        }
+        None => {
+            warn!(
+                "Could not detect Minecraft version from Paper JAR or server.properties, using default: {}",
+                config::global().default_minecraft_version()
+            );
+            config::global().default_minecraft_version().to_string()
+        }
+    }
+}
+
+/// Import the plugins listed in a text file, one `mpm add` spec per line
+///
+/// Blank lines and lines starting with `#` are ignored. Plugins with an
+/// unknown source or that can't be resolved are skipped with a warning.
+async fn import_from_list(
+    list_path: &str,
+    final_version: String,
+    server_type: Option<ServerType>,
+    only_source: Option<&str>,
+    dry_run: bool,
+) -> anyhow::Result<i32> {
+    let text = fs::read_to_string(list_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read plugin list '{}': {}", list_path, e))?;
+
+    let minecraft_version = Some(final_version.as_str());
+    let options = resolve_options(server_type);
+    let mut manifest_plugins = BTreeMap::new();
+    let mut lockfile_plugins = Vec::new();
+    let mut total = 0;
+    let mut skipped = 0;
+
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        total += 1;
+
+        let (source, id, version) = add::parse_spec(line);
+        // Named after the id, so `id` and `source:id` are the same plugin
+        if manifest_plugins.contains_key(id) {
+            warn!(
+                "{}:{}: plugin '{}' is already listed, skipping '{}'",
+                list_path,
+                line_number + 1,
+                id,
+                line
+            );
+            skipped += 1;
+            continue;
+        }
+        let found = match source {
+            Some(source_name) => {
+                let Some(source_impl) = REGISTRY.get(source_name) else {
+                    warn!(
+                        "{}:{}: unknown source '{}', skipping '{}'",
+                        list_path,
+                        line_number + 1,
+                        source_name,
+                        line
+                    );
+                    skipped += 1;
+                    continue;
+                };
+                let resolved = match source_impl.validate_plugin_id(id) {
+                    Ok(()) => {
+                        source_impl
+                            .resolve_version_with(
+                                id,
+                                version.as_deref(),
+                                minecraft_version,
+                                &options,
+                            )
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                match resolved {
+                    Ok(resolved) => Some((source_name.to_string(), id.to_string(), resolved)),
+                    Err(e) => {
+                        debug!("Failed to resolve '{}': {}", line, e);
+                        None
+                    }
+                }
+            }
+            None => {
+                find_plugin_source(
+                    id,
+                    version.as_deref(),
+                    minecraft_version,
+                    &options,
+                    only_source,
+                    None,
+                )
+                .await
+            }
+        };
+
+        let Some((source, plugin_id, resolved)) = found else {
+            warn!(
+                "{}:{}: plugin '{}' not found, skipping",
+                list_path,
+                line_number + 1,
+                line
+            );
+            skipped += 1;
+            continue;
+        };
+
+        // Named after the id, like `mpm add`
+        let name = id.to_string();
+        manifest_plugins.insert(
+            name.clone(),
+            PluginSpec {
+                source: source.clone(),
+                id: plugin_id,
+                version,
+                loaders: None,
+                platform: None,
+                notes: None,
+                prerelease: None,
+                asset_pattern: None,
+                channel: None,
+                unzip: None,
+                disabled: None,
+            },
+        );
+        lockfile_plugins.push(LockedPlugin {
+            name,
+            source,
+            version: resolved.version,
+            file: resolved.filename,
+            url: resolved.url,
+            urls: (!resolved.urls.is_empty()).then_some(resolved.urls),
+            hash: resolved.hash,
+            size_bytes: resolved.size_bytes,
+            upstream_id: resolved.canonical_id,
+            zip_entry: resolved.zip_entry,
+        });
+    }
+
+    if dry_run {
+        print_preview(total, &lockfile_plugins, skipped);
+        return Ok(i32::from(skipped > 0));
+    }
+
+    let imported_count = manifest_plugins.len();
+    save_import(
+        final_version,
+        server_type,
+        manifest_plugins,
+        lockfile_plugins,
+    )?;
+
+    info!("Imported {} plugin(s) from {}", imported_count, list_path);
+    if skipped > 0 {
+        info!("Skipped {} line(s)", skipped);
+    }
+    Ok(0)
+}
+
+/// Show what a dry-run import would write to the manifest and lockfile
+fn print_preview(total: usize, locked: &[LockedPlugin], skipped: usize) {
+    for plugin in locked {
+        ui::action(&format!(
+            "Would import {} from {} ({})",
+            plugin.name, plugin.source, plugin.version
+        ));
+    }
+    ui::dim(&format!(
+        "Found {} plugin(s): {} matched, {} would be skipped. Nothing was written to {} or {}",
+        total,
+        locked.len(),
+        skipped,
+        constants::MANIFEST_FILE,
+        constants::LOCKFILE_FILE
+    ));
+}
+
+/// A fresh manifest holding just the imported plugins
+pub fn new_manifest(
+    minecraft_version: String,
+    server_type: Option<ServerType>,
+    plugins: BTreeMap<String, PluginSpec>,
+) -> Manifest {
+    Manifest {
+        minecraft: MinecraftSpec {
+            version: minecraft_version,
+            server_type,
+            plugins_dir: None,
+            post_sync_command: None,
+        },
+        plugins,
+        groups: None,
+        env: None,
+    }
+}
+
+/// Write the manifest and lockfile for an import
+pub fn save_import(
+    minecraft_version: String,
+    server_type: Option<ServerType>,
+    plugins: BTreeMap<String, PluginSpec>,
+    locked: Vec<LockedPlugin>,
+) -> anyhow::Result<Manifest> {
+    let manifest = new_manifest(minecraft_version, server_type, plugins);
+
+    let mut lockfile = Lockfile::new();
+    for plugin in locked {
+        lockfile.add_plugin(plugin);
+    }
+    lockfile.sort_by_name();
+
+    manifest.save()?;
+    // A lockfile being replaced is kept as plugins.lock.bak
+    lockfile.save_with_backup()?;
+    Ok(manifest)
+}
+
+/// Filters for resolving imported plugins, from the server type like `mpm lock`
+pub fn resolve_options(server_type: Option<ServerType>) -> ResolveOptions {
+    ResolveOptions {
+        loaders: server_type
+            .map_or(constants::DEFAULT_LOADERS, ServerType::loaders)
+            .iter()
+            .map(|l| l.to_string())
+            .collect(),
+        platform: server_type
+            .and_then(ServerType::hangar_platform)
+            .map(str::to_string),
+        prerelease: config::global().include_prereleases,
+        ..Default::default()
+    }
 }
 
 /// Search for a plugin across all sources in priority order
 /// Returns Some((source_name, plugin_id, resolved_version)) if found, None otherwise
-async fn find_plugin_source(
+///
+/// With `only_source`, just that source is searched. With the file's SHA-512
+/// hash, Modrinth is asked for the exact file first; the name search only runs
+/// if it doesn't know it.
+pub async fn find_plugin_source(
     plugin_name: &str,
     version: Option<&str>,
     minecraft_version: Option<&str>,
+    options: &ResolveOptions,
+    only_source: Option<&str>,
+    sha512: Option<&str>,
 ) -> Option<(String, String, crate::sources::ResolvedVersion)> {
-    let sources = REGISTRY.get_priority_order();
-    let timeout_duration = Duration::from_secs(180); // 3 minutes
+    let sources = match only_source {
+        Some(name) => REGISTRY.get(name).into_iter().collect(),
+        None => REGISTRY.get_priority_order(),
+    };
+    let timeout_duration = config::global().source_timeout();
+
+    if let Some(sha512) = sha512
+        && sources.iter().any(|s| s.name() == "modrinth")
+    {
+        match timeout(timeout_duration, modrinth::lookup_file(sha512)).await {
+            Ok(Ok(Some((slug, resolved)))) => {
+                debug!(
+                    "Plugin identified by hash: plugin={}, source=modrinth, plugin_id={}",
+                    plugin_name, slug
+                );
+                return Some(("modrinth".to_string(), slug, resolved));
+            }
+            Ok(Ok(None)) => debug!("Modrinth does not know the file of '{}'", plugin_name),
+            Ok(Err(e)) => debug!("Hash lookup failed for '{}': {}", plugin_name, e),
+            Err(_) => debug!("Hash lookup timed out for '{}'", plugin_name),
+        }
+    }
 
     // Helper function to create a search future
+    #[allow(clippy::too_many_arguments)]
     async fn search_source(
         source_impl: std::sync::Arc<dyn crate::sources::PluginSource>,
         source_name: &'static str,
         search_id: String,
         version: Option<String>,
         minecraft_version: Option<String>,
+        options: ResolveOptions,
         timeout_duration: Duration,
         priority: usize,
     ) -> Result<(String, String, crate::sources::ResolvedVersion, usize), (String, String, usize)>
@@ -225,8 +555,12 @@ async fn find_plugin_source(
 
         // First try with the exact version from plugin.yml if provided
         let minecraft_version_ref: Option<&str> = minecraft_version.as_deref();
-        let resolved_future =
-            source_impl.resolve_version(&search_id, version.as_deref(), minecraft_version_ref);
+        let resolved_future = source_impl.resolve_version_with(
+            &search_id,
+            version.as_deref(),
+            minecraft_version_ref,
+            &options,
+        );
 
         let result = timeout(timeout_duration, resolved_future).await;
 
@@ -246,8 +580,12 @@ async fn find_plugin_source(
                         search_id, source_name
                     );
                     let minecraft_version_ref: Option<&str> = minecraft_version.as_deref();
-                    let retry_future =
-                        source_impl.resolve_version(&search_id, None, minecraft_version_ref);
+                    let retry_future = source_impl.resolve_version_with(
+                        &search_id,
+                        None,
+                        minecraft_version_ref,
+                        &options,
+                    );
                     let retry_result = timeout(timeout_duration, retry_future).await;
                     match retry_result {
                         Ok(Ok(resolved)) => Ok((
@@ -297,6 +635,7 @@ async fn find_plugin_source(
             search_id,
             version_clone,
             minecraft_version_clone,
+            options.clone(),
             timeout_duration_clone,
             priority,
         ));
@@ -318,6 +657,7 @@ async fn find_plugin_source(
                     search_id_lower,
                     version_clone_lower,
                     minecraft_version_clone_lower,
+                    options.clone(),
                     timeout_duration_clone_lower,
                     priority_lower,
                 ));
@@ -357,7 +697,10 @@ async fn find_plugin_source(
     None
 }
 
-fn scan_plugins_dir(plugins_dir: &str) -> anyhow::Result<Vec<ScannedPlugin>> {
+fn scan_plugins_dir(
+    plugins_dir: &str,
+    server_type: Option<ServerType>,
+) -> anyhow::Result<Vec<ScannedPlugin>> {
     let plugins_path = Path::new(plugins_dir);
     let mut plugins = Vec::new();
 
@@ -378,7 +721,7 @@ fn scan_plugins_dir(plugins_dir: &str) -> anyhow::Result<Vec<ScannedPlugin>> {
                 .to_string();
 
             // Try to read plugin.yml from JAR
-            let (name, version) = match read_plugin_yml_from_jar(&path) {
+            let (name, version) = match read_plugin_descriptor(&path, server_type) {
                 Ok((n, v)) => (n, v),
                 Err(e) => {
                     warn!("Could not read plugin.yml from {}: {}", filename, e);
@@ -391,9 +734,9 @@ fn scan_plugins_dir(plugins_dir: &str) -> anyhow::Result<Vec<ScannedPlugin>> {
                 }
             };
 
-            // Compute SHA-256 hash
-            let hash = match compute_sha256(&path) {
-                Ok(h) => h,
+            // SHA-512, since that is what Modrinth can look files up by
+            let hash = match fs::read(&path) {
+                Ok(data) => hash::compute_hash(&data, HashAlgorithm::Sha512),
                 Err(e) => {
                     warn!("Could not compute hash for {}: {}", filename, e);
                     continue; // Skip this plugin if hash computation fails
@@ -407,48 +750,70 @@ fn scan_plugins_dir(plugins_dir: &str) -> anyhow::Result<Vec<ScannedPlugin>> {
     Ok(plugins)
 }
 
-fn read_plugin_yml_from_jar(jar_path: &Path) -> anyhow::Result<(String, Option<String>)> {
-    use std::io::Read;
+/// Detect the Minecraft version of the server in the configuration directory
+/// Tries the Paper JAR first, then `server.properties`, and returns the version
+/// along with where it was found
+pub fn detect_minecraft_version() -> Option<(String, &'static str)> {
+    if let Some(version) = detect_minecraft_version_from_paper_jar() {
+        return Some((version, "Paper JAR"));
+    }
+    detect_minecraft_version_from_server_properties(&config::config_dir())
+        .map(|version| (version, constants::SERVER_PROPERTIES_FILE))
+}
 
-    // Open JAR file as ZIP archive
-    let file = fs::File::open(jar_path)?;
-    let mut archive = zip::ZipArchive::new(file)?;
-
-    // Look for plugin.yml in the root of the JAR
-    // Try plugin.yml first, then bungee.yml for BungeeCord plugins
-    let yml_name = {
-        let _test = archive.by_name("plugin.yml");
-        if _test.is_ok() {
-            "plugin.yml"
-        } else {
-            "bungee.yml"
+/// Detect Minecraft version from `server.properties` in `dir`
+/// Vanilla servers don't record the version there, but some forks write a
+/// `minecraft-version` key, and some setups name the world after the version
+/// (`level-name`). Returns None if neither holds a version
+pub fn detect_minecraft_version_from_server_properties(dir: &str) -> Option<String> {
+    let path = Path::new(dir).join(constants::SERVER_PROPERTIES_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            debug!("Failed to read {:?}: {}", path, e);
+            return None;
         }
     };
-    let mut plugin_yml = archive.by_name(yml_name)?;
 
-    // Read the contents
-    let mut contents = String::new();
-    plugin_yml.read_to_string(&mut contents)?;
-
-    // Parse YAML
-    let plugin_data: PluginYml = serde_yaml::from_str(&contents)
-        .map_err(|e| anyhow::anyhow!("Failed to parse plugin.yml: {}", e))?;
+    let mut minecraft_version = None;
+    let mut level_name = None;
+    for line in contents.lines() {
+        let line = line.trim();
 
-    let name = plugin_data
-        .name
-        .ok_or_else(|| anyhow::anyhow!("plugin.yml missing 'name' field"))?;
+        // Skip comments and blank lines
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
 
-    let version = plugin_data.version;
+        let Some((key, value)) = line.split_once(['=', ':']) else {
+            continue;
+        };
+        match key.trim() {
+            "minecraft-version" => minecraft_version = Some(value.trim().to_string()),
+            "level-name" => level_name = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
 
-    Ok((name, version))
+    // An explicit minecraft-version wins over a world name that looks like one
+    let version = [minecraft_version, level_name]
+        .into_iter()
+        .flatten()
+        .find(|v| looks_like_minecraft_version(v));
+    match &version {
+        Some(version) => debug!("Found version {} in {:?}", version, path),
+        None => debug!("No Minecraft version found in {:?}", path),
+    }
+    version
 }
 
-fn compute_sha256(file_path: &Path) -> anyhow::Result<String> {
-    let data = fs::read(file_path)?;
-    let mut hasher = Sha256::new();
-    hasher.update(&data);
-    let hash_hex = hex::encode(hasher.finalize());
-    Ok(format!("sha256:{}", hash_hex))
+/// Whether a string is a dotted release number such as `1.20.6`
+fn looks_like_minecraft_version(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('.').collect();
+    parts.len() >= 2
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
 }
 
 /// Detect Minecraft version from Paper JAR file in the configuration directory
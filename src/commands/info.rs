@@ -0,0 +1,207 @@
+// Info command for displaying plugin metadata without modifying the manifest
+
+use crate::config;
+use crate::error::MpmError;
+use crate::manifest::Manifest;
+use crate::sources::{PluginInfo, REGISTRY, modrinth};
+use crate::ui;
+use console::style;
+use futures::future::join_all;
+use log::debug;
+use tokio::time::timeout;
+
+pub async fn info(spec: String, json: bool) -> anyhow::Result<()> {
+    // Parse spec format:
+    // - source:id (e.g., modrinth:fabric-api)
+    // - id (searches through all sources in priority order)
+    let (source, id) = match spec.split_once(':') {
+        Some((source, id)) => (Some(source), id),
+        None => (None, spec.as_str()),
+    };
+
+    // Filter by the project's Minecraft version when run inside one
+    let manifest = Manifest::load().ok();
+    let minecraft_version = manifest.as_ref().map(|m| m.minecraft.version.as_str());
+    let notes = manifest
+        .as_ref()
+        .and_then(|m| manifest_notes(m, source, id));
+
+    let explicit_source = source.map(|s| REGISTRY.get_or_error(s)).transpose()?;
+    if let Some(source_impl) = explicit_source {
+        source_impl.validate_plugin_id(id)?;
+    }
+
+    let spinner = ui::spinner(&format!("Fetching {}...", id));
+
+    let info = if let Some(source_impl) = explicit_source {
+        match source_impl.info(id, minecraft_version).await {
+            Ok(info) => info,
+            Err(e) => {
+                ui::finish_spinner_error(&spinner, &format!("{}: {}", id, e));
+                return Err(e);
+            }
+        }
+    } else {
+        let timeout_duration = config::global().source_timeout();
+
+        let futures: Vec<_> = REGISTRY
+            .get_priority_order()
+            .into_iter()
+            .map(|source_impl| async move {
+                let source_name = source_impl.name();
+                debug!("Fetching info for '{}' from source '{}'", id, source_name);
+                match timeout(timeout_duration, source_impl.info(id, minecraft_version)).await {
+                    Ok(result) => result.map_err(|e| (source_name, e)),
+                    Err(_) => Err((
+                        source_name,
+                        anyhow::anyhow!("Search timed out after {}s", timeout_duration.as_secs()),
+                    )),
+                }
+            })
+            .collect();
+
+        // Results come back in priority order; take the first hit
+        let mut errors = Vec::new();
+        let mut found = None;
+        for result in join_all(futures).await {
+            match result {
+                Ok(info) => {
+                    found = Some(info);
+                    break;
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        match found {
+            Some(info) => info,
+            None => {
+                ui::finish_spinner_error(&spinner, &format!("{} not found", id));
+                let not_found = anyhow::Error::new(MpmError::PluginNotFound {
+                    source: None,
+                    id: id.to_string(),
+                });
+                return Err(match errors.first() {
+                    Some((first_source, first_err)) => not_found.context(format!(
+                        "Plugin '{}' not found in any source. First attempted source '{}': {}",
+                        id, first_source, first_err
+                    )),
+                    None => not_found,
+                });
+            }
+        }
+    };
+
+    // A missing changelog shouldn't stop the rest of the info from showing
+    let changelog = if info.source == "modrinth" {
+        modrinth::changelog(&info.id, &info.latest.version)
+            .await
+            .unwrap_or_else(|e| {
+                debug!("Failed to fetch changelog for '{}': {}", info.id, e);
+                None
+            })
+    } else {
+        None
+    };
+
+    ui::clear_bar(&spinner);
+
+    if json {
+        print_json(&info, notes.as_deref(), changelog.as_deref())?;
+    } else {
+        print_info(
+            &info,
+            minecraft_version,
+            notes.as_deref(),
+            changelog.as_deref(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Notes on the matching manifest entry, looked up by name or by source and id
+fn manifest_notes(manifest: &Manifest, source: Option<&str>, id: &str) -> Option<String> {
+    let resolved = manifest.resolve_for_env(config::env()).ok()?;
+    let spec = resolved.plugins.get(id).or_else(|| {
+        resolved
+            .plugins
+            .values()
+            .find(|p| p.id == id && source.is_none_or(|s| s == p.source))
+    })?;
+    spec.notes.clone()
+}
+
+#[allow(clippy::print_stdout)]
+fn print_json(
+    info: &PluginInfo,
+    notes: Option<&str>,
+    changelog: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut value = serde_json::to_value(info)?;
+    if let Some(object) = value.as_object_mut() {
+        if let Some(notes) = notes {
+            object.insert("notes".to_string(), notes.into());
+        }
+        if let Some(changelog) = changelog {
+            object.insert("changelog".to_string(), changelog.into());
+        }
+    }
+    println!("{}", serde_json::to_string_pretty(&value)?);
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+fn print_info(
+    info: &PluginInfo,
+    minecraft_version: Option<&str>,
+    notes: Option<&str>,
+    changelog: Option<&str>,
+) {
+    let title = info.name.as_deref().unwrap_or(&info.id);
+    println!(
+        "{} {}",
+        style(title).bold(),
+        style(format!("({}:{})", info.source, info.id)).dim()
+    );
+    if let Some(description) = &info.description {
+        println!("  {}", description);
+    }
+    println!();
+
+    let field = |label: &str, value: &str| {
+        let label = format!("{}:", label);
+        println!("  {} {}", style(format!("{:<16}", label)).cyan(), value);
+    };
+
+    field("Author", info.author.as_deref().unwrap_or("unknown"));
+    field("License", info.license.as_deref().unwrap_or("unknown"));
+
+    let latest = match minecraft_version {
+        Some(mc_version) => format!(
+            "{} (compatible with Minecraft {})",
+            info.latest.version, mc_version
+        ),
+        None => info.latest.version.clone(),
+    };
+    field("Latest version", &latest);
+    if !info.compatible_versions.is_empty() {
+        field("Versions", &info.compatible_versions.join(", "));
+    }
+    field("Download URL", &info.latest.url);
+    field("Hash", &info.latest.hash);
+    if let Some(notes) = notes {
+        field("Notes", notes);
+    }
+
+    if let Some(changelog) = changelog {
+        println!();
+        println!(
+            "  {}",
+            style(format!("Changelog ({}):", info.latest.version)).cyan()
+        );
+        for line in modrinth::strip_markdown(changelog).lines() {
+            println!("    {}", line);
+        }
+    }
+}
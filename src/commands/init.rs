@@ -1,49 +1,87 @@
 // Init command for initializing a new plugin manifest
 
-use crate::commands::import::detect_minecraft_version_from_paper_jar;
+use crate::commands::import::detect_minecraft_version;
+use crate::commands::{add, lock};
+use crate::config;
 use crate::constants;
-use crate::manifest::{Manifest, MinecraftSpec};
+use crate::manifest::{Manifest, MinecraftSpec, PluginSpec, ServerType};
 use crate::ui;
+use std::collections::BTreeMap;
+use std::path::Path;
 
-pub fn init(version: Option<String>) -> anyhow::Result<()> {
-    // Check if manifest already exists
-    if Manifest::load().is_ok() {
-        ui::dim("Manifest detected. Skipping initialization.");
-        return Ok(());
-    }
+pub fn init(
+    version: Option<String>,
+    server_type: Option<ServerType>,
+    force: bool,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let manifest_path = config::manifest_path();
+    let existing = if Path::new(&manifest_path).exists() {
+        if !force {
+            ui::dim("Manifest detected. Skipping initialization.");
+            return Ok(());
+        }
+        ui::warning(&format!(
+            "Overwriting existing {}. Lockfile is preserved.",
+            constants::MANIFEST_FILE
+        ));
+        if !yes && !ui::confirm(&format!("Overwrite {}?", constants::MANIFEST_FILE)) {
+            anyhow::bail!(
+                "Not overwriting {}. Pass --yes to confirm without a prompt.",
+                constants::MANIFEST_FILE
+            );
+        }
+        Some(salvage_manifest(&manifest_path))
+    } else {
+        None
+    };
 
-    // Determine which version to use
+    // Determine which version to use; a reinitialized manifest keeps its own
     let final_version = if let Some(v) = version {
         // User provided version explicitly, use it
         v
+    } else if let Some(v) = existing.as_ref().and_then(|m| m.version.clone()) {
+        v
     } else {
-        // Try to detect from Paper JAR
-        match detect_minecraft_version_from_paper_jar() {
-            Some(detected_version) => {
+        // Try to detect from the Paper JAR or server.properties
+        match detect_minecraft_version() {
+            Some((detected_version, detected_from)) => {
                 ui::success(&format!(
-                    "Auto-detected Minecraft version {} from Paper JAR",
-                    detected_version
+                    "Auto-detected Minecraft version {} from {}",
+                    detected_version, detected_from
                 ));
                 detected_version
             }
             None => {
                 ui::warning(&format!(
-                    "Could not detect Minecraft version from Paper JAR, using default: {}",
-                    constants::DEFAULT_MC_VERSION
+                    "Could not detect Minecraft version from Paper JAR or server.properties, using default: {}",
+                    config::global().default_minecraft_version()
                 ));
-                constants::DEFAULT_MC_VERSION.to_string()
+                config::global().default_minecraft_version().to_string()
             }
         }
     };
 
+    let existing = existing.unwrap_or_default();
     let manifest = Manifest {
         minecraft: MinecraftSpec {
             version: final_version.clone(),
+            server_type: server_type.or(existing.server_type),
+            plugins_dir: existing.plugins_dir,
+            post_sync_command: existing.post_sync_command,
         },
-        plugins: Default::default(),
+        plugins: existing.plugins,
+        groups: existing.groups,
+        env: existing.env,
     };
 
     manifest.save()?;
+    if !manifest.plugins.is_empty() {
+        ui::dim(&format!(
+            "Kept {} plugin(s) from the previous manifest",
+            manifest.plugins.len()
+        ));
+    }
     ui::success(&format!(
         "Initialized {} with Minecraft version {}",
         constants::MANIFEST_FILE,
@@ -51,3 +89,87 @@ pub fn init(version: Option<String>) -> anyhow::Result<()> {
     ));
     Ok(())
 }
+
+/// Add the `--plugins` specs to the new manifest, then lock once
+///
+/// Specs that fail are reported together after the rest have been added,
+/// so the manifest and lockfile hold every plugin that could be resolved.
+pub async fn add_plugins(specs: &[String]) -> anyhow::Result<()> {
+    let result = add::add(specs, true, false, false, false, None, false).await;
+    if Manifest::load().is_ok_and(|m| !m.plugins.is_empty()) {
+        lock::lock(false).await?;
+    }
+    result
+}
+
+/// What `init --force` carries over from the manifest it replaces
+#[derive(Default)]
+struct Salvaged {
+    version: Option<String>,
+    server_type: Option<ServerType>,
+    plugins_dir: Option<String>,
+    post_sync_command: Option<String>,
+    plugins: BTreeMap<String, PluginSpec>,
+    groups: Option<BTreeMap<String, Vec<String>>>,
+    env: Option<BTreeMap<String, crate::manifest::EnvSpec>>,
+}
+
+/// Read what can be kept from an existing manifest
+///
+/// A manifest in the current format is kept whole. Otherwise each entry under
+/// `[plugins]` that still parses as a plugin is kept, and the rest are dropped
+/// with a warning.
+fn salvage_manifest(path: &str) -> Salvaged {
+    if let Ok(manifest) = Manifest::load() {
+        return Salvaged {
+            version: Some(manifest.minecraft.version),
+            server_type: manifest.minecraft.server_type,
+            plugins_dir: manifest.minecraft.plugins_dir,
+            post_sync_command: manifest.minecraft.post_sync_command,
+            plugins: manifest.plugins,
+            groups: manifest.groups,
+            env: manifest.env,
+        };
+    }
+
+    let table = match std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|text| Ok(toml::from_str::<toml::Table>(&text)?))
+    {
+        Ok(table) => table,
+        Err(e) => {
+            ui::warning(&format!(
+                "Could not read plugins from the existing {}: {}",
+                constants::MANIFEST_FILE,
+                e
+            ));
+            return Salvaged::default();
+        }
+    };
+
+    let version = table
+        .get("minecraft")
+        .and_then(|m| m.get("version"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let mut plugins = BTreeMap::new();
+    for (name, value) in table
+        .get("plugins")
+        .and_then(|p| p.as_table())
+        .into_iter()
+        .flatten()
+    {
+        match value.clone().try_into::<PluginSpec>() {
+            Ok(spec) => {
+                plugins.insert(name.clone(), spec);
+            }
+            Err(e) => ui::warning(&format!("Dropping plugin '{}': {}", name, e)),
+        }
+    }
+
+    Salvaged {
+        version,
+        plugins,
+        ..Default::default()
+    }
+}
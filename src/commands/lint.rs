@@ -0,0 +1,238 @@
+// Lint command for finding mistakes in the manifest and lockfile without the network
+
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::Lockfile;
+use crate::manifest::{Manifest, PluginSpec};
+use crate::sources::version_matcher;
+use crate::ui;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug)]
+struct Issue {
+    severity: &'static str,
+    code: &'static str,
+    message: String,
+}
+
+impl Issue {
+    fn error(code: &'static str, message: String) -> Self {
+        Self {
+            severity: "error",
+            code,
+            message,
+        }
+    }
+
+    fn warning(code: &'static str, message: String) -> Self {
+        Self {
+            severity: "warning",
+            code,
+            message,
+        }
+    }
+}
+
+pub fn lint(fix: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = no issues
+    // 1 = warnings only
+    // 2 = errors found
+
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let mut lockfile = load_lockfile()?;
+
+    let mut issues = check(&manifest, lockfile.as_ref())?;
+    if fix {
+        let duplicates = duplicate_names(&manifest.resolve_for_env(config::env())?.plugins);
+        if !duplicates.is_empty() {
+            for (kept, removed) in &duplicates {
+                remove_duplicate(&mut manifest, lockfile.as_mut(), kept, removed);
+                ui::action(&format!(
+                    "Fixing DUPLICATE_PLUGIN: removed {} (same plugin as {})",
+                    removed, kept
+                ));
+            }
+            manifest.save()?;
+            if let Some(lockfile) = &lockfile {
+                lockfile.save_with_backup()?;
+            }
+            issues = check(&manifest, lockfile.as_ref())?;
+        }
+    }
+
+    for issue in &issues {
+        let line = format!("{}: {}", issue.code, issue.message);
+        match issue.severity {
+            "error" => ui::error(&line),
+            _ => ui::warning(&line),
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == "error").count();
+    let warnings = issues.len() - errors;
+    if issues.is_empty() {
+        ui::success("No issues found");
+        return Ok(0);
+    }
+    ui::dim(&format!("{} error(s), {} warning(s)", errors, warnings));
+    Ok(if errors > 0 { 2 } else { 1 })
+}
+
+/// Load the lockfile if there is one; its checks are skipped otherwise
+fn load_lockfile() -> anyhow::Result<Option<Lockfile>> {
+    if !Path::new(&config::lockfile_path()).exists() {
+        return Ok(None);
+    }
+    Lockfile::load().map(Some)
+}
+
+fn check(manifest: &Manifest, lockfile: Option<&Lockfile>) -> anyhow::Result<Vec<Issue>> {
+    let resolved = manifest.resolve_for_env(config::env())?;
+    let plugins = &resolved.plugins;
+    let mut issues = Vec::new();
+
+    for (name, spec) in plugins {
+        if spec.id.trim().is_empty() {
+            issues.push(Issue::error(
+                "EMPTY_ID",
+                format!("Plugin '{}' has an empty id", name),
+            ));
+        }
+    }
+
+    for (kept, removed) in duplicate_names(plugins) {
+        issues.push(Issue::error(
+            "DUPLICATE_PLUGIN",
+            format!(
+                "Plugins '{}' and '{}' are both {}:{}",
+                kept, removed, plugins[&kept].source, plugins[&kept].id
+            ),
+        ));
+    }
+
+    // The same id from different sources, even in different case, is usually
+    // one plugin added twice, and both copies would be installed
+    let mut sources_by_id: BTreeMap<String, BTreeMap<&str, &str>> = BTreeMap::new();
+    for (name, spec) in plugins.iter().filter(|(_, s)| !s.id.trim().is_empty()) {
+        sources_by_id
+            .entry(spec.id.to_lowercase())
+            .or_default()
+            .entry(spec.source.as_str())
+            .or_insert(name.as_str());
+    }
+    for (id, sources) in sources_by_id.iter().filter(|(_, s)| s.len() > 1) {
+        let listed: Vec<String> = sources
+            .iter()
+            .map(|(source, name)| format!("{} ({})", name, source))
+            .collect();
+        issues.push(Issue::error(
+            "SOURCE_CONFLICT",
+            format!(
+                "Id '{}' comes from more than one source: {}",
+                id,
+                listed.join(", ")
+            ),
+        ));
+    }
+
+    // Within one source, ids differing only in case name the same project
+    for (first, second) in resolved.same_id_plugins() {
+        let (a, b) = (&plugins[first], &plugins[second]);
+        if a.source == b.source && a.id != b.id {
+            issues.push(Issue::error(
+                "CASE_CONFLICT",
+                format!(
+                    "Plugins '{}' and '{}' are {}:{} and {}:{}, which differ only in case",
+                    first, second, a.source, a.id, b.source, b.id
+                ),
+            ));
+        }
+    }
+
+    if let Some(lockfile) = lockfile {
+        for locked in &lockfile.plugin {
+            if let Some(pin) = plugins.get(&locked.name).and_then(|s| s.version.as_deref())
+                && !pin_matches(pin, &locked.version)
+            {
+                issues.push(Issue::warning(
+                    "PIN_MISMATCH",
+                    format!(
+                        "Plugin '{}' is pinned to {} but locked at {}; run `mpm lock`",
+                        locked.name, pin, locked.version
+                    ),
+                ));
+            }
+            if !locked.hash.starts_with("sha256:") && !locked.hash.starts_with("sha512:") {
+                issues.push(Issue::error(
+                    "INVALID_HASH",
+                    format!(
+                        "Lockfile entry '{}' has hash '{}', expected sha256: or sha512:",
+                        locked.name, locked.hash
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues.sort_by(|a, b| a.code.cmp(b.code).then_with(|| a.message.cmp(&b.message)));
+    Ok(issues)
+}
+
+/// Whether a locked version satisfies a pin, which may be a constraint like `^5.4`
+fn pin_matches(pin: &str, version: &str) -> bool {
+    version_matcher::parse_constraint(pin)
+        .map(|constraint| constraint.matches(version))
+        .unwrap_or(pin == version)
+}
+
+/// Names that point at the same source and id as another plugin, as
+/// (kept, duplicate) pairs
+///
+/// The name matching the id is kept, else the first name alphabetically.
+fn duplicate_names(plugins: &BTreeMap<String, PluginSpec>) -> Vec<(String, String)> {
+    let mut by_target: BTreeMap<(&str, &str), Vec<&String>> = BTreeMap::new();
+    for (name, spec) in plugins.iter().filter(|(_, s)| !s.id.trim().is_empty()) {
+        by_target
+            .entry((spec.source.as_str(), spec.id.as_str()))
+            .or_default()
+            .push(name);
+    }
+
+    let mut duplicates = Vec::new();
+    for ((_, id), names) in by_target.into_iter().filter(|(_, n)| n.len() > 1) {
+        let kept = names
+            .iter()
+            .find(|name| name.as_str() == id)
+            .unwrap_or(&names[0]);
+        for name in names.iter().filter(|name| *name != kept) {
+            duplicates.push((kept.to_string(), name.to_string()));
+        }
+    }
+    duplicates
+}
+
+/// Drop a duplicate plugin, pointing its group memberships at the kept name
+fn remove_duplicate(
+    manifest: &mut Manifest,
+    lockfile: Option<&mut Lockfile>,
+    kept: &str,
+    removed: &str,
+) {
+    manifest.plugins.remove(removed);
+    for env in manifest.env.iter_mut().flat_map(|e| e.values_mut()) {
+        env.plugins.remove(removed);
+    }
+    for members in manifest.groups.iter_mut().flat_map(|g| g.values_mut()) {
+        if members.iter().any(|m| m == removed) {
+            members.retain(|m| m != removed);
+            if !members.iter().any(|m| m == kept) {
+                members.push(kept.to_string());
+            }
+        }
+    }
+    if let Some(lockfile) = lockfile {
+        lockfile.plugin.retain(|p| p.name != removed);
+    }
+}
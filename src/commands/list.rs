@@ -0,0 +1,210 @@
+// List command for displaying managed plugins and their sync state
+
+use crate::commands::sync::verify_plugin_hash;
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::manifest::Manifest;
+use crate::ui;
+use console::style;
+use indicatif::HumanBytes;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PluginStatus {
+    Synced,
+    Missing,
+    HashMismatch,
+    Unmanaged,
+}
+
+impl PluginStatus {
+    fn label(&self) -> String {
+        match self {
+            PluginStatus::Synced => format!("{} synced", style("✓").green()),
+            PluginStatus::Missing => format!("{} missing", style("✗").red()),
+            PluginStatus::HashMismatch => format!("{} hash mismatch", style("!").red()),
+            PluginStatus::Unmanaged => format!("{} unmanaged", style("?").yellow()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PluginEntry {
+    name: String,
+    source: Option<String>,
+    version: Option<String>,
+    file: String,
+    /// Locked size, or the size on disk when the lockfile doesn't record one
+    size_bytes: Option<u64>,
+    status: PluginStatus,
+    notes: Option<String>,
+}
+
+/// Longest note shown in the table; the JSON output has the full text
+const NOTE_WIDTH: usize = 40;
+
+#[allow(clippy::print_stdout)]
+pub fn list(json: bool, verbose: bool) -> anyhow::Result<()> {
+    let manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let lockfile = Lockfile::load_existing()?;
+
+    let plugins_dir = config::plugins_dir_from_manifest(&manifest);
+    let mut entries = collect_entries(&plugins_dir, &lockfile);
+
+    let resolved = manifest.resolve_for_env(config::env())?;
+    for entry in &mut entries {
+        entry.notes = resolved
+            .plugins
+            .get(&entry.name)
+            .and_then(|p| p.notes.clone());
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No plugins managed");
+    } else {
+        print_table(&entries);
+    }
+    if verbose {
+        ui::dim(&format!(
+            "Lockfile last updated: {}",
+            lockfile.locked_at.as_deref().unwrap_or("unknown")
+        ));
+    }
+    Ok(())
+}
+
+fn collect_entries(plugins_dir: &str, lockfile: &Lockfile) -> Vec<PluginEntry> {
+    let plugins_path = Path::new(plugins_dir);
+
+    let mut entries: Vec<PluginEntry> = lockfile
+        .plugin
+        .iter()
+        .map(|plugin| PluginEntry {
+            name: plugin.name.clone(),
+            source: Some(plugin.source.clone()),
+            version: Some(plugin.version.clone()),
+            file: plugin.file.clone(),
+            size_bytes: plugin
+                .size_bytes
+                .or_else(|| file_size(&plugins_path.join(&plugin.file))),
+            status: plugin_status(plugins_path, plugin),
+            notes: None,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Unmanaged files go last, sorted for determinism
+    let managed_files: HashSet<&str> = lockfile.plugin.iter().map(|p| p.file.as_str()).collect();
+    let mut unmanaged_files: Vec<String> = Vec::new();
+    if let Ok(dir_entries) = fs::read_dir(plugins_path) {
+        for entry in dir_entries.flatten() {
+            let path = entry.path();
+            if path.is_file()
+                && let Some(filename) = path.file_name().and_then(|n| n.to_str())
+                && filename.ends_with(".jar")
+                && !managed_files.contains(filename)
+            {
+                unmanaged_files.push(filename.to_string());
+            }
+        }
+    }
+    unmanaged_files.sort();
+
+    entries.extend(unmanaged_files.into_iter().map(|filename| {
+        PluginEntry {
+            name: filename
+                .strip_suffix(".jar")
+                .unwrap_or(&filename)
+                .to_string(),
+            source: None,
+            version: None,
+            size_bytes: file_size(&plugins_path.join(&filename)),
+            file: filename,
+            status: PluginStatus::Unmanaged,
+            notes: None,
+        }
+    }));
+
+    entries
+}
+
+/// First line of a note, cut to `NOTE_WIDTH` characters
+fn truncate_note(note: &str) -> String {
+    let line = note.lines().next().unwrap_or("");
+    if line.chars().count() > NOTE_WIDTH || line.len() < note.trim_end().len() {
+        let cut: String = line.chars().take(NOTE_WIDTH - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+fn file_size(path: &Path) -> Option<u64> {
+    fs::metadata(path).ok().map(|m| m.len())
+}
+
+fn plugin_status(plugins_path: &Path, plugin: &LockedPlugin) -> PluginStatus {
+    let file_path = plugins_path.join(&plugin.file);
+    if !file_path.exists() {
+        return PluginStatus::Missing;
+    }
+
+    match plugin.parse_hash() {
+        Ok((algorithm, _)) => match verify_plugin_hash(&file_path, algorithm) {
+            Ok(computed_hash) if computed_hash == plugin.hash => PluginStatus::Synced,
+            _ => PluginStatus::HashMismatch,
+        },
+        Err(_) => PluginStatus::HashMismatch,
+    }
+}
+
+fn print_table(entries: &[PluginEntry]) {
+    let rows: Vec<[String; 6]> = entries
+        .iter()
+        .map(|e| {
+            [
+                e.name.clone(),
+                e.source.clone().unwrap_or_else(|| "-".to_string()),
+                e.version.clone().unwrap_or_else(|| "-".to_string()),
+                e.file.clone(),
+                e.size_bytes
+                    .map_or_else(|| "-".to_string(), |b| HumanBytes(b).to_string()),
+                e.status.label(),
+            ]
+        })
+        .collect();
+
+    let headers = ["NAME", "SOURCE", "VERSION", "FILE", "SIZE", "STATUS"];
+    if entries.iter().any(|e| e.notes.is_some()) {
+        // Notes go last since they vary the most in length
+        let rows: Vec<[String; 7]> = rows
+            .into_iter()
+            .zip(entries)
+            .map(|([name, source, version, file, size, status], e)| {
+                let note = e.notes.as_deref().map_or_else(String::new, truncate_note);
+                [name, source, version, file, size, status, note]
+            })
+            .collect();
+        let [name, source, version, file, size, status] = headers;
+        ui::table([name, source, version, file, size, status, "NOTES"], &rows);
+    } else {
+        ui::table(headers, &rows);
+    }
+
+    let total: u64 = entries.iter().filter_map(|e| e.size_bytes).sum();
+    ui::dim(&format!(
+        "Total: {} in {} file(s)",
+        HumanBytes(total),
+        entries.len()
+    ));
+}
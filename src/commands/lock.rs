@@ -1,28 +1,139 @@
 // Lock command for generating or updating the lockfile
 
+use crate::commands::report::Report;
+use crate::config;
+use crate::error::MpmError;
 use crate::lockfile::{LockedPlugin, Lockfile};
-use crate::manifest::Manifest;
-use crate::sources::REGISTRY;
+use crate::manifest::{Manifest, PluginSpec, ResolvedManifest};
+use crate::sources::{REGISTRY, ResolutionDetails, ResolveOptions, version_matcher};
 use crate::ui;
+use futures::future::join_all;
+use log::debug;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use toml;
 
+/// Options controlling a lock run
+#[derive(Debug, Default)]
+pub struct LockOptions {
+    /// Preview changes without writing the lockfile
+    pub dry_run: bool,
+    /// Maximum plugins resolved at once (falls back to `lock.concurrency` in the global config)
+    pub concurrency: Option<usize>,
+    /// Only resolve plugins in this group (plus ungrouped plugins)
+    pub group: Option<String>,
+    /// Only re-resolve these plugins, keeping every other lock entry as it is
+    pub update: Vec<String>,
+    /// Print a JSON report instead of progress output
+    pub json: bool,
+    /// Only report whether the lockfile is up to date (implies `dry_run`)
+    pub check: bool,
+    /// Print how each plugin's version was chosen
+    pub verbose: bool,
+    /// Fail instead of adding plugins that are not in the existing lockfile
+    pub fail_on_new: bool,
+    /// Fail instead of dropping plugins from the existing lockfile
+    pub fail_on_removed: bool,
+    /// Write a `sha256sum` checksum file to this path after locking
+    pub checksums_path: Option<String>,
+    /// Write a CycloneDX SBOM to this path after locking
+    #[cfg(feature = "sbom")]
+    pub sbom_path: Option<String>,
+}
+
 pub async fn lock(dry_run: bool) -> anyhow::Result<i32> {
+    #[allow(clippy::needless_update)] // Remaining fields are feature-gated
+    lock_with_options(&LockOptions {
+        dry_run,
+        ..Default::default()
+    })
+    .await
+}
+
+pub async fn lock_with_options(options: &LockOptions) -> anyhow::Result<i32> {
+    let dry_run = options.dry_run || options.check;
+
+    // A check prints nothing but its summary
+    if options.check {
+        ui::set_buffered(true);
+    }
+
+    let concurrency = options
+        .concurrency
+        .unwrap_or_else(|| config::global().lock_concurrency());
+    if concurrency == 0 {
+        anyhow::bail!("Lock concurrency must be at least 1");
+    }
+    debug!("Resolving with concurrency {}", concurrency);
+
     // Load manifest
     let manifest = Manifest::load()
-        .map_err(|_| anyhow::anyhow!("Manifest not found. Run 'mpm init' first."))?;
+        .map_err(|_| MpmError::ManifestNotFound)?
+        .resolve_for_env(config::env())?;
 
     if dry_run {
         ui::status("[DRY RUN]", "Previewing lock changes...");
     }
 
-    let mut lockfile = Lockfile::new();
+    let mut plugins = manifest.plugins_for_group(options.group.as_deref())?;
+
+    // Disabled plugins are left out of the lockfile, so sync removes their files
+    let disabled: Vec<String> = manifest
+        .plugins
+        .iter()
+        .filter(|(_, spec)| spec.is_disabled())
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in &disabled {
+        if plugins.remove(name).is_some() {
+            ui::warning(&format!("Skipping disabled plugin '{}'", name));
+        }
+    }
+
+    // Two entries for one project would lock and install it twice
+    for (first, second) in manifest.same_id_plugins() {
+        if plugins.contains_key(first) && plugins.contains_key(second) {
+            ui::warning(&format!(
+                "Plugins '{}' and '{}' appear to resolve to the same plugin. This may cause conflicts.",
+                first, second
+            ));
+        }
+    }
+
+    // --update narrows the run to the named plugins, which must be in the manifest
+    if !options.update.is_empty() {
+        for name in &options.update {
+            if !manifest.plugins.contains_key(name) {
+                anyhow::bail!(
+                    "Plugin '{}' not found in manifest. Add it with 'mpm add' first.",
+                    name
+                );
+            }
+        }
+        plugins.retain(|name, _| options.update.contains(name));
+    }
+
     let minecraft_version = Some(manifest.minecraft.version.as_str());
 
+    // Updated plugins are looked up by the source's own ID, which still
+    // works if the manifest's slug has since been renamed
+    let mut upstream_ids = HashMap::new();
+    if !options.update.is_empty() {
+        for locked in Lockfile::load_existing()?.plugin {
+            if let Some(spec) = plugins.get(&locked.name)
+                && spec.source == locked.source
+                && let Some(upstream_id) = locked.upstream_id
+            {
+                upstream_ids.insert(locked.name, upstream_id);
+            }
+        }
+    }
+
     // Check if there are any GitHub plugins and warn once about version compatibility
-    let has_github_plugins = manifest
-        .plugins
-        .values()
-        .any(|spec| spec.source == "github");
+    let has_github_plugins = plugins.values().any(|spec| spec.source == "github");
     if has_github_plugins && minecraft_version.is_some() {
         ui::warning(
             "GitHub source does not support Minecraft version filtering. \
@@ -30,84 +141,415 @@ pub async fn lock(dry_run: bool) -> anyhow::Result<i32> {
         );
     }
 
-    // For each plugin, resolve version
-    for (name, plugin_spec) in manifest.plugins.iter() {
-        let spinner = ui::spinner(&format!("Resolving {}...", name));
-
-        // Get the source implementation
-        let source = match REGISTRY.get_or_error(&plugin_spec.source) {
-            Ok(s) => s,
-            Err(e) => {
-                ui::finish_spinner_error(&spinner, &format!("{}: {}", name, e));
-                return Err(e);
+    // Resolve every plugin, at most `concurrency` at a time
+    let multi = ui::multi_progress();
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let verbose = options.verbose;
+    let handles: Vec<_> = plugins
+        .iter()
+        .map(|(name, plugin_spec)| {
+            let name = name.clone();
+            let mut plugin_spec = plugin_spec.clone();
+            let options = manifest.resolve_options(&plugin_spec);
+            if let Some(upstream_id) = upstream_ids.get(&name) {
+                debug!("Resolving {} by its upstream ID '{}'", name, upstream_id);
+                plugin_spec.id = upstream_id.clone();
             }
-        };
+            let minecraft_version = minecraft_version.map(str::to_string);
+            let semaphore = Arc::clone(&semaphore);
+            let multi = multi.clone();
 
-        // Validate plugin ID format
-        if let Err(e) = source.validate_plugin_id(&plugin_spec.id) {
-            ui::finish_spinner_error(&spinner, &format!("{}: {}", name, e));
-            return Err(e);
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                let spinner = ui::multi_spinner(&multi, &format!("Resolving {}...", name));
+                match resolve_plugin_with_details(
+                    &name,
+                    &plugin_spec,
+                    minecraft_version.as_deref(),
+                    &options,
+                )
+                .await
+                {
+                    Ok((locked, details)) => {
+                        ui::finish_spinner_resolved(&spinner, &name, &locked.version);
+                        if verbose {
+                            print_details(&locked, &details, minecraft_version.as_deref());
+                        }
+                        Ok(locked)
+                    }
+                    Err(e) => {
+                        ui::finish_spinner_error(&spinner, &format!("{}: {}", name, e));
+                        Err(anyhow::anyhow!("{}: {}", name, e))
+                    }
+                }
+            })
+        })
+        .collect();
+
+    // Report every failure at once rather than stopping at the first
+    let mut resolved = Vec::new();
+    let mut failures = Vec::new();
+    for result in join_all(handles).await {
+        match result {
+            Ok(Ok(locked)) => resolved.push(locked),
+            Ok(Err(e)) => failures.push(e.to_string()),
+            Err(e) => failures.push(format!("resolve task failed: {}", e)),
         }
+    }
+    if !failures.is_empty() {
+        anyhow::bail!(
+            "Failed to resolve {} plugin(s):\n  {}",
+            failures.len(),
+            failures.join("\n  ")
+        );
+    }
 
-        // Resolve version using the trait
-        let resolved = match source
-            .resolve_version(
-                &plugin_spec.id,
-                plugin_spec.version.as_deref(),
-                minecraft_version,
-            )
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                ui::finish_spinner_error(&spinner, &format!("{}: {}", name, e));
-                return Err(e);
-            }
-        };
+    // Plugins outside the group or update list keep their existing lock entries
+    let existing = Lockfile::load().ok();
+    let lockfile = lock_resolved(&manifest, existing.as_ref(), resolved);
 
-        lockfile.add_plugin(LockedPlugin {
-            name: name.clone(),
-            source: plugin_spec.source.clone(),
-            version: resolved.version.clone(),
-            file: resolved.filename.clone(),
-            url: resolved.url.clone(),
-            hash: resolved.hash.clone(),
-        });
+    guard_plugin_set(existing.as_ref(), &lockfile, options)?;
 
-        ui::finish_spinner_resolved(&spinner, name, &resolved.version);
+    if options.check {
+        ui::take_buffered();
+        ui::set_buffered(false);
+        return check_lockfile(existing.as_ref(), &lockfile);
     }
 
-    // Sort plugins by name
-    lockfile.sort_by_name();
-
     // Exit codes:
     // 0 = healthy, no issues
     // 1 = warnings only (changes detected in dry-run)
     // 2 = errors present
-    if dry_run {
-        ui::dim(&format!("Would lock {} plugin(s)", lockfile.plugin.len()));
+    let (exit_code, message) = if dry_run {
+        // Only the updated plugins can change, so show just their changes
+        if !options.update.is_empty() {
+            for name in changed_plugins(existing.as_ref(), &lockfile) {
+                let old = existing
+                    .as_ref()
+                    .and_then(|l| l.plugin.iter().find(|p| p.name == name));
+                let new = lockfile.plugin.iter().find(|p| p.name == name);
+                if let (Some(old), Some(new)) = (old, new) {
+                    ui::action(&format!(
+                        "Would update {}: {} → {}",
+                        name, old.version, new.version
+                    ));
+                } else if let Some(new) = new {
+                    ui::action(&format!("Would add {} {}", name, new.version));
+                }
+            }
+        }
+        let message = format!("Would lock {} plugin(s)", lockfile.plugin.len());
+        ui::dim(&message);
 
         // Check if lockfile would change by comparing with existing lockfile
-        let exit_code = match Lockfile::load() {
-            Ok(existing_lockfile) => {
+        let exit_code = match &existing {
+            Some(existing_lockfile) => {
                 // Compare lockfiles by serializing them
                 let new_content = toml::to_string_pretty(&lockfile)?;
-                let existing_content = toml::to_string_pretty(&existing_lockfile)?;
+                let existing_content = toml::to_string_pretty(existing_lockfile)?;
                 if new_content == existing_content {
                     0 // No changes needed
                 } else {
                     1 // Changes detected
                 }
             }
-            Err(_) => {
+            None => {
                 // No existing lockfile, so it would be created (change)
                 1
             }
         };
-        Ok(exit_code)
+        (exit_code, message)
     } else {
-        lockfile.save()?;
-        ui::success(&format!("Locked {} plugin(s)", lockfile.plugin.len()));
-        Ok(0) // Success
+        lockfile.save_with_backup()?;
+        let message = format!("Locked {} plugin(s)", lockfile.plugin.len());
+        ui::success(&message);
+
+        // SBOM is derived from the lockfile and written alongside it
+        #[cfg(feature = "sbom")]
+        if let Some(path) = &options.sbom_path {
+            crate::sbom::Bom::from_lockfile(&manifest, &lockfile).save(path)?;
+            ui::success(&format!("Wrote SBOM to {}", path));
+        }
+
+        if let Some(path) = &options.checksums_path {
+            save_checksums(&lockfile, path)?;
+        }
+
+        (0, message) // Success
+    };
+
+    if options.json {
+        let affected = changed_plugins(existing.as_ref(), &lockfile);
+        let mut report = Report::ok(message, affected);
+        report.plugins = Some(lockfile.plugin);
+        report.print()?;
     }
+
+    Ok(exit_code)
+}
+
+/// Write each locked plugin's hash in `sha256sum` format (`<hex>  <file>`)
+///
+/// The hashes come straight from the lockfile, so nothing is downloaded.
+/// Plugins locked with SHA-512 can't be converted without their files and
+/// are written to a `.sha512` file beside `path` instead, which is removed
+/// when no plugin needs it.
+fn save_checksums(lockfile: &Lockfile, path: &str) -> anyhow::Result<()> {
+    let sha512_path = Path::new(path).with_extension("sha512");
+    let mut sha256 = String::new();
+    let mut sha512 = String::new();
+    for plugin in &lockfile.plugin {
+        let (algorithm, hex) = plugin.parse_hash()?;
+        let lines = match algorithm {
+            "sha256" => &mut sha256,
+            "sha512" => &mut sha512,
+            other => anyhow::bail!(
+                "Can't write a checksum for {}: unsupported hash algorithm '{}'",
+                plugin.name,
+                other
+            ),
+        };
+        lines.push_str(&format!("{}  {}\n", hex, plugin.file));
+    }
+
+    fs::write(path, sha256)?;
+    ui::success(&format!("Wrote checksums to {}", path));
+    if !sha512.is_empty() {
+        fs::write(&sha512_path, sha512)?;
+        ui::success(&format!(
+            "Wrote SHA-512 checksums to {}",
+            sha512_path.display()
+        ));
+    } else if sha512_path.is_file() {
+        fs::remove_file(&sha512_path)?;
+    }
+    Ok(())
+}
+
+/// Compare a freshly resolved lockfile with the one on disk
+///
+/// Returns 0 if they match and 1, listing each changed plugin, if not.
+fn check_lockfile(existing: Option<&Lockfile>, lockfile: &Lockfile) -> anyhow::Result<i32> {
+    let Some(existing) = existing else {
+        ui::error("plugins.lock is missing. Run 'mpm lock' to create it.");
+        return Ok(1);
+    };
+    if toml::to_string_pretty(lockfile)? == toml::to_string_pretty(existing)? {
+        ui::success("plugins.lock is up to date");
+        return Ok(0);
+    }
+
+    ui::error("plugins.lock is out of date. Run 'mpm lock' to update it.");
+    for name in changed_plugins(Some(existing), lockfile) {
+        let old = existing.plugin.iter().find(|p| p.name == name);
+        let new = lockfile.plugin.iter().find(|p| p.name == name);
+        let change = match (old, new) {
+            (None, _) => "added".to_string(),
+            (_, None) => "removed".to_string(),
+            (Some(old), Some(new)) if old.version != new.version => {
+                format!("{} → {}", old.version, new.version)
+            }
+            (Some(old), Some(new)) if old.upstream_id != new.upstream_id => {
+                "upstream ID changed".to_string()
+            }
+            (Some(_), Some(_)) => "file or hash changed".to_string(),
+        };
+        ui::error(&format!("{}: {}", name, change));
+    }
+    Ok(1)
+}
+
+/// Refuse a lock that adds or drops plugins when asked to
+///
+/// Used by deployment pipelines so a new plugin needs its own reviewed commit.
+fn guard_plugin_set(
+    existing: Option<&Lockfile>,
+    lockfile: &Lockfile,
+    options: &LockOptions,
+) -> anyhow::Result<()> {
+    let previous = existing.map_or(&[][..], |l| l.plugin.as_slice());
+    let mut problems = Vec::new();
+    if options.fail_on_new {
+        let added: Vec<&str> = lockfile
+            .plugin
+            .iter()
+            .filter(|p| !previous.iter().any(|old| old.name == p.name))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !added.is_empty() {
+            problems.push(format!("would add {}", added.join(", ")));
+        }
+    }
+    if options.fail_on_removed {
+        let removed: Vec<&str> = previous
+            .iter()
+            .filter(|old| !lockfile.plugin.iter().any(|p| p.name == old.name))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !removed.is_empty() {
+            problems.push(format!("would remove {}", removed.join(", ")));
+        }
+    }
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "Lockfile not written: {}. Lock without --fail-on-new/--fail-on-removed to accept the change.",
+            problems.join("; ")
+        );
+    }
+    Ok(())
+}
+
+/// Names of plugins added, changed or dropped relative to the previous lockfile
+fn changed_plugins(existing: Option<&Lockfile>, lockfile: &Lockfile) -> Vec<String> {
+    let previous = existing.map_or(&[][..], |l| l.plugin.as_slice());
+    let mut names: Vec<String> = lockfile
+        .plugin
+        .iter()
+        .filter(|p| !previous.contains(p))
+        .chain(
+            previous
+                .iter()
+                .filter(|p| !lockfile.plugin.iter().any(|l| l.name == p.name)),
+        )
+        .map(|p| p.name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Print how a plugin's version was chosen, as one block so parallel
+/// resolutions don't interleave
+fn print_details(
+    locked: &LockedPlugin,
+    details: &ResolutionDetails,
+    minecraft_version: Option<&str>,
+) {
+    let mut lines = vec![format!("{} ({})", locked.name, locked.source)];
+    if let Some(api_url) = &details.api_url {
+        lines.push(format!("  API: {}", api_url));
+    }
+    if let Some(total) = details.versions_total {
+        let compatible = match (details.versions_compatible, minecraft_version) {
+            (Some(compatible), Some(mc)) => {
+                format!(", {} compatible with Minecraft {}", compatible, mc)
+            }
+            _ => String::new(),
+        };
+        lines.push(format!("  Versions: {} returned{}", total, compatible));
+    }
+    match &details.published_at {
+        Some(published) => lines.push(format!(
+            "  Selected: {} (published {})",
+            locked.version, published
+        )),
+        None => lines.push(format!("  Selected: {}", locked.version)),
+    }
+    lines.push(format!("  File: {}", locked.file));
+    let (algorithm, hex) = locked.hash.split_once(':').unwrap_or(("", &locked.hash));
+    lines.push(format!(
+        "  Hash: {}:{}…",
+        algorithm,
+        &hex[..hex.len().min(16)]
+    ));
+    ui::dim(&lines.join("\n"));
+}
+
+/// Build the lockfile that replaces `existing` once `resolved` are resolved
+///
+/// Every other enabled manifest plugin keeps its existing lock entry, so a
+/// partial run (or a plugin that failed to resolve) leaves it as it was.
+/// Disabled plugins and plugins no longer in the manifest are dropped.
+pub fn lock_resolved(
+    manifest: &ResolvedManifest,
+    existing: Option<&Lockfile>,
+    resolved: Vec<LockedPlugin>,
+) -> Lockfile {
+    let mut lockfile = Lockfile::new();
+    let is_enabled = |name: &str| manifest.plugins.get(name).is_some_and(|s| !s.is_disabled());
+    for locked in existing.iter().flat_map(|existing| &existing.plugin) {
+        if is_enabled(&locked.name) && !resolved.iter().any(|p| p.name == locked.name) {
+            lockfile.add_plugin(locked.clone());
+        }
+    }
+    for locked in resolved {
+        if is_enabled(&locked.name) {
+            lockfile.add_plugin(locked);
+        }
+    }
+    lockfile.sort_by_name();
+    set_locked_at(&mut lockfile, existing);
+    lockfile
+}
+
+/// Stamp `locked_at` on a lockfile about to replace `existing`
+///
+/// Only a change to the locked plugins moves the timestamp, so an unchanged
+/// lock leaves the file (and `--check`) alone.
+fn set_locked_at(lockfile: &mut Lockfile, existing: Option<&Lockfile>) {
+    lockfile.locked_at = match existing {
+        Some(existing) if existing.plugin == lockfile.plugin => existing.locked_at.clone(),
+        _ => Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+    };
+}
+
+/// Resolve a single manifest entry into its lockfile entry
+pub async fn resolve_plugin(
+    name: &str,
+    plugin_spec: &PluginSpec,
+    minecraft_version: Option<&str>,
+    options: &ResolveOptions,
+) -> anyhow::Result<LockedPlugin> {
+    resolve_plugin_with_details(name, plugin_spec, minecraft_version, options)
+        .await
+        .map(|(locked, _)| locked)
+}
+
+/// Resolve a manifest entry, also returning how its version was chosen
+async fn resolve_plugin_with_details(
+    name: &str,
+    plugin_spec: &PluginSpec,
+    minecraft_version: Option<&str>,
+    options: &ResolveOptions,
+) -> anyhow::Result<(LockedPlugin, ResolutionDetails)> {
+    // Get the source implementation
+    let source = REGISTRY.get_or_error(&plugin_spec.source)?;
+
+    // Validate plugin ID format
+    source.validate_plugin_id(&plugin_spec.id)?;
+
+    // Catch malformed version constraints before touching the network
+    if let Some(version) = &plugin_spec.version {
+        version_matcher::parse_constraint(version).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid version constraint '{}' for plugin '{}': {}",
+                version,
+                name,
+                e
+            )
+        })?;
+    }
+
+    // Resolve version using the trait
+    let resolved = source
+        .resolve_version_with(
+            &plugin_spec.id,
+            plugin_spec.version.as_deref(),
+            minecraft_version,
+            options,
+        )
+        .await?;
+
+    let locked = LockedPlugin {
+        name: name.to_string(),
+        source: plugin_spec.source.clone(),
+        version: resolved.version,
+        file: resolved.filename,
+        url: resolved.url,
+        urls: (!resolved.urls.is_empty()).then_some(resolved.urls),
+        hash: resolved.hash,
+        size_bytes: resolved.size_bytes,
+        upstream_id: resolved.canonical_id,
+        zip_entry: resolved.zip_entry,
+    };
+    Ok((locked, resolved.details))
 }
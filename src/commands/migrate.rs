@@ -0,0 +1,382 @@
+// Migrate command for converting other plugin setups into a manifest
+
+use crate::commands::import;
+use crate::constants;
+use crate::lockfile::LockedPlugin;
+use crate::manifest::{Manifest, PluginSpec, ServerType};
+use crate::sources::{REGISTRY, ResolveOptions, ResolvedVersion};
+use crate::ui;
+use log::{debug, info, warn};
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Formats `mpm migrate --from` can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MigrateFormat {
+    /// PlugMan-style YAML listing plugin names under `plugins`
+    Plugman,
+    /// One download URL per line
+    Plaintext,
+}
+
+/// A plugin found in the file being migrated
+#[derive(Debug, PartialEq)]
+struct Entry {
+    /// How the plugin appeared in the file, for messages
+    label: String,
+    /// Source to resolve from, or `None` to search every source
+    source: Option<&'static str>,
+    id: String,
+    version: Option<String>,
+}
+
+pub async fn migrate(
+    format: MigrateFormat,
+    path: String,
+    version: Option<String>,
+    server_type: Option<ServerType>,
+    dry_run: bool,
+) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = every plugin was migrated (or would be, in a dry run)
+    // 1 = some plugins were skipped in a dry run
+
+    if Manifest::load().is_ok() {
+        anyhow::bail!(
+            "{} already exists. Remove it first before migrating.",
+            constants::MANIFEST_FILE
+        );
+    }
+
+    let text = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path, e))?;
+    let entries = match format {
+        MigrateFormat::Plugman => parse_plugman(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid PlugMan file '{}': {}", path, e))?,
+        MigrateFormat::Plaintext => parse_plaintext(&text),
+    };
+    debug!("Found {} plugin(s) in {}", entries.len(), path);
+
+    let final_version = import::choose_minecraft_version(version);
+    if dry_run {
+        ui::status("[DRY RUN]", "Previewing migration...");
+    }
+
+    let options = import::resolve_options(server_type);
+    let mut manifest_plugins = BTreeMap::new();
+    let mut lockfile_plugins = Vec::new();
+    let mut skipped = 0;
+
+    for entry in &entries {
+        let spinner = ui::spinner(&format!("Resolving {}...", entry.label));
+        let Some((source, id, version, resolved)) =
+            resolve_entry(entry, &final_version, &options).await
+        else {
+            ui::finish_spinner_error(&spinner, &format!("{}: not found", entry.label));
+            warn!("Plugin '{}' not found in any source, skipping", entry.label);
+            skipped += 1;
+            continue;
+        };
+        ui::finish_spinner_resolved(&spinner, &id, &resolved.version);
+
+        // Named after the id, like `mpm add`
+        let name = id.clone();
+        manifest_plugins.insert(
+            name.clone(),
+            PluginSpec {
+                source: source.clone(),
+                id,
+                version,
+                loaders: None,
+                platform: None,
+                notes: None,
+                prerelease: None,
+                asset_pattern: None,
+                channel: None,
+                unzip: None,
+                disabled: None,
+            },
+        );
+        lockfile_plugins.push(LockedPlugin {
+            name,
+            source,
+            version: resolved.version,
+            file: resolved.filename,
+            url: resolved.url,
+            urls: (!resolved.urls.is_empty()).then_some(resolved.urls),
+            hash: resolved.hash,
+            size_bytes: resolved.size_bytes,
+            upstream_id: resolved.canonical_id,
+            zip_entry: resolved.zip_entry,
+        });
+    }
+
+    if dry_run {
+        let manifest = import::new_manifest(final_version, server_type, manifest_plugins);
+        print_manifest(&manifest)?;
+        ui::dim(&format!(
+            "Found {} plugin(s): {} matched, {} would be skipped. Nothing was written to {} or {}",
+            entries.len(),
+            lockfile_plugins.len(),
+            skipped,
+            constants::MANIFEST_FILE,
+            constants::LOCKFILE_FILE
+        ));
+        return Ok(i32::from(skipped > 0));
+    }
+
+    let migrated = manifest_plugins.len();
+    import::save_import(
+        final_version,
+        server_type,
+        manifest_plugins,
+        lockfile_plugins,
+    )?;
+
+    info!("Migrated {} plugin(s) from {}", migrated, path);
+    if skipped > 0 {
+        info!("Skipped {} plugin(s) not found in any source", skipped);
+    }
+    Ok(0)
+}
+
+/// Resolve an entry to (source, id, pinned version, resolved version)
+///
+/// A version that can't be resolved falls back to the latest compatible one,
+/// which is then left unpinned.
+async fn resolve_entry(
+    entry: &Entry,
+    minecraft_version: &str,
+    options: &ResolveOptions,
+) -> Option<(String, String, Option<String>, ResolvedVersion)> {
+    let Some(source_name) = entry.source else {
+        let (source, id, resolved) = import::find_plugin_source(
+            &entry.id,
+            entry.version.as_deref(),
+            Some(minecraft_version),
+            options,
+            None,
+            None,
+        )
+        .await?;
+        let version = entry.version.clone().filter(|v| *v == resolved.version);
+        return Some((source, id, version, resolved));
+    };
+
+    let source = REGISTRY.get(source_name)?;
+    if let Err(e) = source.validate_plugin_id(&entry.id) {
+        debug!("Invalid id for '{}': {}", entry.label, e);
+        return None;
+    }
+
+    let mut version = entry.version.clone();
+    loop {
+        match source
+            .resolve_version_with(
+                &entry.id,
+                version.as_deref(),
+                Some(minecraft_version),
+                options,
+            )
+            .await
+        {
+            Ok(resolved) => {
+                return Some((source_name.to_string(), entry.id.clone(), version, resolved));
+            }
+            Err(e) if version.is_some() => {
+                debug!(
+                    "Version {:?} of '{}' not found, trying the latest: {}",
+                    version, entry.label, e
+                );
+                version = None;
+            }
+            Err(e) => {
+                debug!("Failed to resolve '{}': {}", entry.label, e);
+                return None;
+            }
+        }
+    }
+}
+
+/// Plugins listed under `plugins` in a PlugMan-style YAML file
+///
+/// `plugins` may be a list of names or a map of names to a version (or to a
+/// table with a `version` key). A top-level list is read the same way.
+fn parse_plugman(text: &str) -> anyhow::Result<Vec<Entry>> {
+    let document: serde_yaml::Value = serde_yaml::from_str(text)?;
+    let plugins = match document.get("plugins") {
+        Some(plugins) => plugins,
+        None if document.is_sequence() => &document,
+        None => anyhow::bail!("expected a 'plugins' list or map"),
+    };
+
+    let entry = |name: &str, version: Option<&serde_yaml::Value>| {
+        let version = version.and_then(|v| match v {
+            serde_yaml::Value::String(s) => Some(s.clone()),
+            serde_yaml::Value::Number(n) => Some(n.to_string()),
+            serde_yaml::Value::Mapping(_) => v.get("version").and_then(|v| match v {
+                serde_yaml::Value::String(s) => Some(s.clone()),
+                serde_yaml::Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            }),
+            _ => None,
+        });
+        Entry {
+            label: name.to_string(),
+            source: None,
+            id: name.to_string(),
+            version,
+        }
+    };
+
+    match plugins {
+        serde_yaml::Value::Sequence(names) => names
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .map(|name| entry(name, None))
+                    .ok_or_else(|| anyhow::anyhow!("plugin names must be strings"))
+            })
+            .collect(),
+        serde_yaml::Value::Mapping(plugins) => plugins
+            .iter()
+            .map(|(name, version)| {
+                name.as_str()
+                    .map(|name| entry(name, Some(version)))
+                    .ok_or_else(|| anyhow::anyhow!("plugin names must be strings"))
+            })
+            .collect(),
+        _ => anyhow::bail!("'plugins' must be a list or map"),
+    }
+}
+
+/// Download URLs, one per line; blank lines and `#` comments are ignored
+fn parse_plaintext(text: &str) -> Vec<Entry> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (source, id, version) = source_from_url(line);
+            Entry {
+                label: line.to_string(),
+                source: Some(source),
+                id,
+                version,
+            }
+        })
+        .collect()
+}
+
+/// Work out the source, id and version a download URL belongs to
+///
+/// Modrinth, Hangar and GitHub URLs map to their sources; anything else is
+/// kept as a `url` plugin.
+fn source_from_url(url: &str) -> (&'static str, String, Option<String>) {
+    let fallback = ("url", url.to_string(), None);
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return fallback;
+    };
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let host = parsed.host_str().unwrap_or_default();
+
+    match (host, segments.as_slice()) {
+        // cdn.modrinth.com/data/<project id>/versions/<version id>/<file>
+        ("cdn.modrinth.com", ["data", project, ..])
+        // api.modrinth.com/v2/project/<slug>/...
+        | ("api.modrinth.com", ["v2", "project", project, ..])
+        // modrinth.com/plugin/<slug>
+        | ("modrinth.com", ["plugin" | "mod", project, ..]) => {
+            ("modrinth", project.to_string(), None)
+        }
+        // hangar.papermc.io/api/v1/projects/[<author>/]<slug>/versions/<version>/...
+        ("hangar.papermc.io", ["api", "v1", "projects", rest @ ..]) => match rest {
+            [slug, "versions", version, ..] => {
+                ("hangar", slug.to_string(), Some(version.to_string()))
+            }
+            [author, slug, "versions", version, ..] => (
+                "hangar",
+                format!("{}/{}", author, slug),
+                Some(version.to_string()),
+            ),
+            [author, slug, ..] => ("hangar", format!("{}/{}", author, slug), None),
+            _ => fallback,
+        },
+        // hangar.papermc.io/<author>/<slug>[/versions/<version>]
+        ("hangar.papermc.io", [author, slug, rest @ ..]) => {
+            let version = match rest {
+                ["versions", version, ..] => Some(version.to_string()),
+                _ => None,
+            };
+            ("hangar", format!("{}/{}", author, slug), version)
+        }
+        // github.com/<owner>/<repo>/releases/download/<tag>/<file>
+        ("github.com", [owner, repo, rest @ ..]) => {
+            let version = match rest {
+                ["releases", "download", tag, ..] => Some(tag.to_string()),
+                _ => None,
+            };
+            ("github", format!("{}/{}", owner, repo), version)
+        }
+        _ => fallback,
+    }
+}
+
+/// Print the manifest a dry run would write
+#[allow(clippy::print_stdout)]
+fn print_manifest(manifest: &Manifest) -> anyhow::Result<()> {
+    println!("{}", toml::to_string_pretty(manifest)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_url() {
+        assert_eq!(
+            source_from_url("https://cdn.modrinth.com/data/1u6JkXh5/versions/abc/worldedit.jar"),
+            ("modrinth", "1u6JkXh5".to_string(), None)
+        );
+        assert_eq!(
+            source_from_url(
+                "https://hangar.papermc.io/api/v1/projects/kennytv/Maintenance/versions/4.2.1/PAPER/download"
+            ),
+            (
+                "hangar",
+                "kennytv/Maintenance".to_string(),
+                Some("4.2.1".to_string())
+            )
+        );
+        assert_eq!(
+            source_from_url(
+                "https://github.com/EssentialsX/Essentials/releases/download/2.20.1/EssentialsX-2.20.1.jar"
+            ),
+            (
+                "github",
+                "EssentialsX/Essentials".to_string(),
+                Some("2.20.1".to_string())
+            )
+        );
+        assert_eq!(
+            source_from_url("https://example.com/plugin.jar"),
+            ("url", "https://example.com/plugin.jar".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_parse_plugman() {
+        let entries = parse_plugman("plugins:\n  WorldEdit: 7.3.0\n  Vault:\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "WorldEdit");
+        assert_eq!(entries[0].version.as_deref(), Some("7.3.0"));
+        assert_eq!(entries[1].version, None);
+
+        let entries = parse_plugman("- LuckPerms\n- Vault\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(parse_plugman("name: PlugMan\n").is_err());
+    }
+}
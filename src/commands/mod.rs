@@ -1,9 +1,31 @@
 // Commands module
 
 pub mod add;
+pub mod clean;
+pub mod completions;
+pub mod config;
+pub mod diff;
+pub mod disable;
 pub mod doctor;
+pub mod export;
+pub mod generate;
 pub mod import;
+pub mod info;
 pub mod init;
+pub mod lint;
+pub mod list;
 pub mod lock;
+pub mod migrate;
+pub mod outdated;
+pub mod pin;
+pub mod prune;
 pub mod remove;
+pub mod report;
+pub mod rollback;
+pub mod search;
+pub mod status;
 pub mod sync;
+pub mod update;
+pub mod upgrade;
+pub mod verify;
+pub mod workspace;
@@ -0,0 +1,208 @@
+// Outdated command for finding plugins with newer compatible versions
+
+use crate::config;
+use crate::constants;
+use crate::error::MpmError;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::manifest::{Manifest, ResolvedManifest};
+use crate::sources::{REGISTRY, modrinth};
+use crate::ui;
+use futures::future::join_all;
+use log::debug;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::time::timeout;
+
+#[derive(Debug, Serialize)]
+pub struct OutdatedPlugin {
+    pub name: String,
+    pub source: String,
+    pub current: String,
+    pub available: String,
+    /// Download URL of the available version
+    pub url: String,
+    /// First line of the available version's changelog (Modrinth only, with --verbose)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+}
+
+pub async fn outdated(json: bool, fail_on_outdated: bool, verbose: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = all plugins are current, or outdated ones shouldn't fail
+    // 1 = at least one plugin is outdated
+    // 2 = errors present
+
+    let manifest = Manifest::load()
+        .map_err(|_| MpmError::ManifestNotFound)?
+        .resolve_for_env(config::env())?;
+    let lockfile = Lockfile::load_existing()?;
+
+    let stale_days = config::global().lock_warn_stale_days();
+    if stale_days > 0
+        && let Some(age) = lockfile.age_days()
+        && age > stale_days as i64
+    {
+        ui::warning(&format!(
+            "{} was last updated {} days ago. Run 'mpm lock' to refresh it.",
+            constants::LOCKFILE_FILE,
+            age
+        ));
+    }
+
+    let (mut outdated, failed) = find_outdated(&manifest, lockfile.plugin).await?;
+    if verbose {
+        add_changelogs(&manifest, &mut outdated).await;
+    }
+
+    if json {
+        print_json(&outdated)?;
+    } else if outdated.is_empty() {
+        ui::success("All plugins are up to date");
+    } else if verbose {
+        print_verbose_table(&outdated);
+    } else {
+        print_table(&outdated);
+    }
+
+    Ok(if failed > 0 {
+        2
+    } else if outdated.is_empty() || !fail_on_outdated {
+        0
+    } else {
+        1
+    })
+}
+
+/// Locked plugins with a newer compatible version, sorted by name, and the
+/// number of plugins that couldn't be checked
+///
+/// Pins are ignored, so a pinned plugin is outdated when a newer version exists.
+pub async fn find_outdated(
+    manifest: &ResolvedManifest,
+    plugins: Vec<LockedPlugin>,
+) -> anyhow::Result<(Vec<OutdatedPlugin>, usize)> {
+    let minecraft_version = manifest.minecraft.version.clone();
+    let mut failed = 0;
+
+    // Spawn one check per plugin so the network calls run concurrently
+    let mut handles = Vec::new();
+    for plugin in plugins {
+        // The lockfile doesn't record the source ID, so take it from the manifest
+        let Some(spec) = manifest.plugins.get(&plugin.name) else {
+            ui::warning(&format!(
+                "Skipping {}: not found in manifest (run 'mpm lock' to refresh the lockfile)",
+                plugin.name
+            ));
+            failed += 1;
+            continue;
+        };
+
+        let source = Arc::clone(REGISTRY.get_or_error(&plugin.source)?);
+        let plugin_id = spec.id.clone();
+        let minecraft_version = minecraft_version.clone();
+        let options = manifest.resolve_options(spec);
+
+        handles.push(tokio::spawn(async move {
+            let result = timeout(
+                config::global().source_timeout(),
+                source.resolve_version_with(&plugin_id, None, Some(&minecraft_version), &options),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out")));
+            (plugin, result)
+        }));
+    }
+
+    let spinner = ui::spinner(&format!("Checking {} plugin(s)...", handles.len()));
+    let results = join_all(handles).await;
+    ui::clear_bar(&spinner);
+
+    let mut outdated = Vec::new();
+    for result in results {
+        let (plugin, resolved) = result?;
+        match resolved {
+            Ok(resolved) if resolved.version != plugin.version => {
+                outdated.push(OutdatedPlugin {
+                    name: plugin.name,
+                    source: plugin.source,
+                    current: plugin.version,
+                    available: resolved.version,
+                    url: resolved.url,
+                    changelog: None,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                ui::error(&format!("{}: {}", plugin.name, e));
+                failed += 1;
+            }
+        }
+    }
+    outdated.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok((outdated, failed))
+}
+
+/// Fill in a changelog summary for each outdated Modrinth plugin
+///
+/// Changelogs are optional, so failures are logged and skipped.
+async fn add_changelogs(manifest: &ResolvedManifest, outdated: &mut [OutdatedPlugin]) {
+    let spinner = ui::spinner("Fetching changelogs...");
+    let lookups = outdated.iter().map(|plugin| async move {
+        let spec = manifest.plugins.get(&plugin.name)?;
+        if plugin.source != "modrinth" {
+            return None;
+        }
+        match modrinth::changelog(&spec.id, &plugin.available).await {
+            Ok(changelog) => changelog.as_deref().and_then(modrinth::changelog_summary),
+            Err(e) => {
+                debug!("Failed to fetch changelog for '{}': {}", plugin.name, e);
+                None
+            }
+        }
+    });
+    let summaries = join_all(lookups).await;
+    ui::clear_bar(&spinner);
+
+    for (plugin, summary) in outdated.iter_mut().zip(summaries) {
+        plugin.changelog = summary;
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_json(outdated: &[OutdatedPlugin]) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(outdated)?);
+    Ok(())
+}
+
+fn print_table(outdated: &[OutdatedPlugin]) {
+    let rows: Vec<[String; 4]> = outdated
+        .iter()
+        .map(|p| {
+            [
+                p.name.clone(),
+                p.current.clone(),
+                p.available.clone(),
+                p.source.clone(),
+            ]
+        })
+        .collect();
+
+    ui::table(["NAME", "CURRENT", "AVAILABLE", "SOURCE"], &rows);
+}
+
+fn print_verbose_table(outdated: &[OutdatedPlugin]) {
+    let rows: Vec<[String; 5]> = outdated
+        .iter()
+        .map(|p| {
+            [
+                p.name.clone(),
+                p.current.clone(),
+                p.available.clone(),
+                p.source.clone(),
+                p.changelog.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    ui::table(["NAME", "CURRENT", "AVAILABLE", "SOURCE", "CHANGES"], &rows);
+}
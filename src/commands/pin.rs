@@ -0,0 +1,95 @@
+// Pin and unpin commands for freezing plugins at their locked versions
+
+use crate::commands::lock;
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::Lockfile;
+use crate::manifest::Manifest;
+use crate::ui;
+
+/// Copy each plugin's locked version into its manifest entry
+pub async fn pin(names: Vec<String>, all: bool, no_update: bool) -> anyhow::Result<()> {
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let lockfile = Lockfile::load_existing()?;
+    let targets = select_targets(&manifest, names, all)?;
+
+    let mut changed = 0;
+    for name in &targets {
+        let Some(locked) = lockfile.plugin.iter().find(|p| &p.name == name) else {
+            anyhow::bail!(
+                "Plugin '{}' is not in the lockfile. Run 'mpm lock' first.",
+                name
+            );
+        };
+        let Some(entry) = manifest.plugin_mut(config::env(), name) else {
+            continue;
+        };
+        if entry.version.as_deref() == Some(locked.version.as_str()) {
+            ui::dim(&format!("{} is already pinned to {}", name, locked.version));
+            continue;
+        }
+        entry.version = Some(locked.version.clone());
+        ui::success(&format!("Pinned {} to {}", name, locked.version));
+        changed += 1;
+    }
+
+    save_and_relock(&manifest, changed, no_update).await
+}
+
+/// Clear the version of each plugin so `lock` resolves the latest compatible one
+pub async fn unpin(names: Vec<String>, all: bool, no_update: bool) -> anyhow::Result<()> {
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let targets = select_targets(&manifest, names, all)?;
+
+    let mut changed = 0;
+    for name in &targets {
+        let Some(entry) = manifest.plugin_mut(config::env(), name) else {
+            continue;
+        };
+        match entry.version.take() {
+            Some(version) => {
+                ui::success(&format!("Unpinned {} (was {})", name, version));
+                changed += 1;
+            }
+            None => ui::dim(&format!("{} is not pinned", name)),
+        }
+    }
+
+    save_and_relock(&manifest, changed, no_update).await
+}
+
+/// The named plugins, or every plugin with `all`, checked against the manifest
+pub fn select_targets(
+    manifest: &Manifest,
+    names: Vec<String>,
+    all: bool,
+) -> anyhow::Result<Vec<String>> {
+    let resolved = manifest.resolve_for_env(config::env())?;
+    if all {
+        return Ok(resolved.plugins.keys().cloned().collect());
+    }
+    for name in &names {
+        if !resolved.plugins.contains_key(name) {
+            anyhow::bail!("Plugin '{}' not found in manifest", name);
+        }
+    }
+    Ok(names)
+}
+
+/// Save the manifest and refresh the lockfile, if anything changed
+pub async fn save_and_relock(
+    manifest: &Manifest,
+    changed: usize,
+    no_update: bool,
+) -> anyhow::Result<()> {
+    if changed == 0 {
+        return Ok(());
+    }
+    manifest.save()?;
+
+    // Pins change what `lock` resolves, so refresh the lockfile unless told not to
+    if !no_update {
+        lock::lock(false).await?;
+    }
+    Ok(())
+}
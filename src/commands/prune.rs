@@ -0,0 +1,78 @@
+// Prune command for removing lockfile entries that are no longer in the manifest
+
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::manifest::Manifest;
+use crate::ui;
+use std::fs;
+use std::path::Path;
+
+pub fn prune(dry_run: bool, purge: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = nothing to prune
+    // 1 = entries were pruned (or would be, in a dry run)
+
+    let manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let resolved = manifest.resolve_for_env(config::env())?;
+    let mut lockfile = Lockfile::load_existing()?;
+
+    if dry_run {
+        ui::status("[DRY RUN]", "Previewing lockfile pruning...");
+    }
+
+    let (stale, kept): (Vec<LockedPlugin>, Vec<LockedPlugin>) = lockfile
+        .plugin
+        .into_iter()
+        .partition(|p| !resolved.plugins.contains_key(&p.name));
+    lockfile.plugin = kept;
+
+    if stale.is_empty() {
+        ui::success("Lockfile has no stale entries");
+        return Ok(0);
+    }
+
+    let plugins_dir = config::plugins_dir_from_manifest(&manifest);
+    let prefix = if dry_run { "Would prune" } else { "Pruning" };
+    for plugin in &stale {
+        ui::action(&format!("{} {} ({})", prefix, plugin.name, plugin.version));
+    }
+
+    if dry_run {
+        if purge {
+            for path in stale_files(&plugins_dir, &stale) {
+                ui::action(&format!("Would delete {}", path.display()));
+            }
+        }
+        ui::dim(&format!(
+            "Would prune {} plugin(s) from the lockfile",
+            stale.len()
+        ));
+        return Ok(1);
+    }
+
+    lockfile.save_with_backup()?;
+    ui::success(&format!(
+        "Pruned {} plugin(s) from the lockfile",
+        stale.len()
+    ));
+
+    if purge {
+        for path in stale_files(&plugins_dir, &stale) {
+            fs::remove_file(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to delete '{}': {}", path.display(), e))?;
+            ui::success(&format!("Deleted {}", path.display()));
+        }
+    }
+
+    Ok(1)
+}
+
+/// Files of pruned plugins that are still in the plugins directory
+fn stale_files(plugins_dir: &str, stale: &[LockedPlugin]) -> Vec<std::path::PathBuf> {
+    stale
+        .iter()
+        .map(|p| Path::new(plugins_dir).join(&p.file))
+        .filter(|path| path.is_file())
+        .collect()
+}
@@ -1,25 +1,93 @@
 // Remove command for removing a plugin from the manifest
 
 use crate::commands::lock;
+use crate::commands::report::Report;
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::Lockfile;
 use crate::manifest::Manifest;
 use crate::ui;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-pub async fn remove(spec: String, no_update: bool) -> anyhow::Result<()> {
+/// Remove plugins from the manifest, then lock once
+///
+/// Names that aren't in the manifest are warned about, unless none of them are.
+pub async fn remove(
+    specs: &[String],
+    no_update: bool,
+    purge: bool,
+    json: bool,
+) -> anyhow::Result<()> {
     // Load existing manifest
-    let mut manifest = Manifest::load()
-        .map_err(|_| anyhow::anyhow!("Manifest not found. Run 'mpm init' first."))?;
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
 
-    // Remove plugin from manifest
-    if manifest.plugins.remove(&spec).is_some() {
-        manifest.save()?;
-        ui::success(&format!("Removed {}", spec));
-
-        // Automatically lock after removing unless --no-update is specified
-        if !no_update {
-            lock::lock(false).await?;
+    let (removed, missing): (Vec<&String>, Vec<&String>) = specs
+        .iter()
+        .partition(|spec| manifest.plugins.contains_key(spec.as_str()));
+    if removed.is_empty() {
+        let names: Vec<&str> = missing.iter().map(|s| s.as_str()).collect();
+        match names.as_slice() {
+            [name] => anyhow::bail!("Plugin '{}' not found in manifest", name),
+            _ => anyhow::bail!("No plugins found in manifest: {}", names.join(", ")),
         }
+    }
+    for spec in &missing {
+        ui::warning(&format!("Plugin '{}' not found in manifest", spec));
+    }
+
+    // Find the files to purge while the lockfile still records them
+    let purge_files = if purge {
+        plugin_files(&removed)
     } else {
-        anyhow::bail!("Plugin '{}' not found in manifest", spec);
+        Vec::new()
+    };
+
+    for spec in &removed {
+        manifest.plugins.remove(spec.as_str());
+        // Drop it from any groups too, so they don't reference a missing plugin
+        for members in manifest.groups.iter_mut().flat_map(|g| g.values_mut()) {
+            members.retain(|member| member != *spec);
+        }
+        ui::success(&format!("Removed {}", spec));
+    }
+    manifest.save()?;
+
+    // Automatically lock after removing unless --no-update is specified
+    if !no_update {
+        lock::lock(false).await?;
+    }
+
+    for file_path in purge_files.iter().filter(|path| path.exists()) {
+        fs::remove_file(file_path)
+            .map_err(|e| anyhow::anyhow!("Failed to delete '{}': {}", file_path.display(), e))?;
+        ui::success(&format!("Deleted {}", file_path.display()));
+    }
+
+    if json {
+        let names: Vec<String> = removed.into_iter().cloned().collect();
+        Report::ok(format!("Removed {}", names.join(", ")), names).print()?;
     }
     Ok(())
 }
+
+/// JARs of removed plugins in the plugins directory, from the lockfile
+fn plugin_files(names: &[&String]) -> Vec<PathBuf> {
+    let Ok(lockfile) = Lockfile::load() else {
+        ui::warning("No lockfile found; nothing to purge");
+        return Vec::new();
+    };
+
+    let plugins_dir = config::plugins_dir();
+    let mut files = Vec::new();
+    for name in names {
+        match lockfile.plugin.iter().find(|p| &p.name == *name) {
+            Some(plugin) => files.push(Path::new(&plugins_dir).join(&plugin.file)),
+            None => ui::warning(&format!(
+                "{} is not in the lockfile; nothing to purge",
+                name
+            )),
+        }
+    }
+    files
+}
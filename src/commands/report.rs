@@ -0,0 +1,72 @@
+// JSON reports printed by add, remove, lock and sync with --json
+
+use crate::lockfile::LockedPlugin;
+use crate::ui::{self, BufferedMessage};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Ok,
+    Error,
+}
+
+/// Single JSON object describing what a command did
+///
+/// Progress output is suppressed while `--json` is set; the messages the
+/// command would have printed are included under `messages`.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub status: ReportStatus,
+    pub message: String,
+    pub plugins_affected: Vec<String>,
+    /// Every locked plugin (`lock` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<Vec<LockedPlugin>>,
+    /// Files downloaded, or that would be with --dry-run (`sync` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloaded: Option<Vec<String>>,
+    /// Files whose hash already matched (`sync` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub already_synced: Option<Vec<String>>,
+    /// Unmanaged files removed, or that would be with --dry-run (`sync` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Vec<String>>,
+    pub messages: Vec<BufferedMessage>,
+}
+
+impl Report {
+    pub fn ok(message: impl Into<String>, plugins_affected: Vec<String>) -> Self {
+        Self {
+            status: ReportStatus::Ok,
+            message: message.into(),
+            plugins_affected,
+            plugins: None,
+            downloaded: None,
+            already_synced: None,
+            removed: None,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            status: ReportStatus::Error,
+            ..Self::ok(message, Vec::new())
+        }
+    }
+
+    /// Print the report on stdout along with the buffered messages
+    #[allow(clippy::print_stdout)]
+    pub fn print(mut self) -> anyhow::Result<()> {
+        self.messages = ui::take_buffered();
+        println!("{}", serde_json::to_string_pretty(&self)?);
+        Ok(())
+    }
+}
+
+/// Print a failed command's error as a report
+pub fn print_error(e: &anyhow::Error) {
+    // Serializing a report can't fail; nothing else is left to print anyway
+    let _ = Report::error(e.to_string()).print();
+}
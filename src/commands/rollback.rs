@@ -0,0 +1,30 @@
+// Rollback command for restoring the previous lockfile
+
+use crate::commands::sync::{self, SyncOptions};
+use crate::config;
+use crate::ui;
+use std::fs;
+use std::path::Path;
+
+pub async fn rollback(confirm: bool) -> anyhow::Result<i32> {
+    let backup_path = config::lockfile_backup_path();
+    if !Path::new(&backup_path).exists() {
+        anyhow::bail!(
+            "No previous lockfile to roll back to ({} not found). A backup is written each time 'mpm lock' changes the lockfile.",
+            backup_path
+        );
+    }
+
+    if !confirm {
+        anyhow::bail!(
+            "Rollback replaces {} with {} and re-syncs plugins. Re-run with --confirm to proceed.",
+            config::lockfile_path(),
+            backup_path
+        );
+    }
+
+    fs::copy(&backup_path, config::lockfile_path())?;
+    ui::success("Restored previous lockfile");
+
+    sync::sync_plugins_with_options(&SyncOptions::default()).await
+}
@@ -0,0 +1,130 @@
+// Search command for finding plugins across sources
+
+use crate::sources::search::merge_search_results;
+use crate::sources::{PluginSource, REGISTRY, SearchResult};
+use crate::ui;
+use console::style;
+use futures::future::join_all;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Per-source timeout so one slow API doesn't stall the whole search
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub async fn search(
+    query: &str,
+    source: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> anyhow::Result<()> {
+    if query.trim().is_empty() {
+        anyhow::bail!("Search query cannot be empty");
+    }
+    if limit == 0 {
+        anyhow::bail!("--limit must be at least 1");
+    }
+
+    let sources: Vec<Arc<dyn PluginSource>> = match source {
+        Some(name) => vec![Arc::clone(REGISTRY.get_or_error(name)?)],
+        None => REGISTRY
+            .get_priority_order()
+            .into_iter()
+            .map(Arc::clone)
+            .collect(),
+    };
+
+    let spinner = ui::spinner(&format!("Searching for '{}'...", query));
+    let searches = sources.iter().map(|source| async move {
+        let result = match timeout(SEARCH_TIMEOUT, source.search(query, limit)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("timed out")),
+        };
+        (source.name(), result)
+    });
+    let outcomes = join_all(searches).await;
+    ui::clear_bar(&spinner);
+
+    // Results are collected in source priority order, which merging relies on
+    let mut results: Vec<SearchResult> = Vec::new();
+    let mut failures = Vec::new();
+    for (source_name, outcome) in outcomes {
+        match outcome {
+            Ok(found) => results.extend(found),
+            Err(e) => failures.push((source_name, e)),
+        }
+    }
+
+    // Only fail outright if no source could be searched
+    if failures.len() == sources.len()
+        && let Some((source_name, e)) = failures.pop()
+    {
+        if sources.len() == 1 {
+            anyhow::bail!("Search failed for source '{}': {}", source_name, e);
+        }
+        anyhow::bail!(
+            "Search failed for all sources (last error from '{}': {})",
+            source_name,
+            e
+        );
+    }
+    for (source_name, e) in &failures {
+        ui::warning(&format!(
+            "Search failed for source '{}': {}",
+            source_name, e
+        ));
+    }
+
+    let results = merge_search_results(results, query);
+
+    if json {
+        print_json(&results)?;
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        ui::dim(&format!("No plugins found matching '{}'", query));
+        return Ok(());
+    }
+
+    print_results(&results);
+    ui::dim("Add a plugin with: mpm add <source>:<id>");
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+fn print_json(results: &[SearchResult]) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(results)?);
+    Ok(())
+}
+
+#[allow(clippy::print_stdout)]
+fn print_results(results: &[SearchResult]) {
+    for result in results {
+        let mut details = vec![format!("{}:{}", result.source, result.id)];
+        if let Some(version) = &result.latest_version {
+            details.push(version.clone());
+        }
+        if let Some(downloads) = result.downloads {
+            details.push(format!("{} downloads", format_count(downloads)));
+        }
+
+        println!(
+            "{} {}",
+            style(&result.name).bold(),
+            style(format!("({})", details.join(", "))).dim()
+        );
+        if let Some(description) = &result.description {
+            println!("    {}", description);
+        }
+    }
+}
+
+/// Format a large count compactly, e.g. 1234567 -> "1.2M"
+fn format_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}K", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
@@ -0,0 +1,75 @@
+// Status command for a one-line summary of the plugins directory
+
+use crate::commands::doctor::{self, PluginCounts};
+use crate::config;
+use crate::lockfile::Lockfile;
+use console::style;
+use std::path::Path;
+
+#[allow(clippy::print_stdout)]
+pub fn status(count: bool) -> anyhow::Result<i32> {
+    // Exit codes (as for `mpm doctor`):
+    // 0 = every plugin is synced
+    // 1 = unmanaged files only
+    // 2 = missing or mismatched plugins, or no usable lockfile
+
+    let lockfile_path = config::lockfile_path();
+    let summary = if !Path::new(&lockfile_path).exists() {
+        if Path::new(&config::manifest_path()).exists() {
+            Err("not locked")
+        } else {
+            Err("not initialized")
+        }
+    } else {
+        Lockfile::load()
+            .map(|lockfile| doctor::count_plugins(&config::plugins_dir(), &lockfile))
+            .map_err(|_| "invalid lockfile")
+    };
+
+    let counts = match summary {
+        Ok(counts) => counts,
+        Err(problem) => {
+            if count {
+                println!("0 0 0 0");
+            } else {
+                println!("{} {}", style("✗").red(), problem);
+            }
+            return Ok(2);
+        }
+    };
+
+    let exit_code = if counts.missing > 0 || counts.hash_mismatch > 0 {
+        2
+    } else if counts.unmanaged > 0 {
+        1
+    } else {
+        0
+    };
+
+    if count {
+        println!(
+            "{} {} {} {}",
+            counts.synced, counts.missing, counts.hash_mismatch, counts.unmanaged
+        );
+    } else if exit_code == 0 {
+        println!("{} {} plugins synced", style("✓").green(), counts.synced);
+    } else {
+        println!("{} {}", style("✗").red(), problems(&counts));
+    }
+
+    Ok(exit_code)
+}
+
+/// The non-zero problem counts, e.g. "2 missing, 1 hash mismatch"
+fn problems(counts: &PluginCounts) -> String {
+    [
+        (counts.missing, "missing"),
+        (counts.hash_mismatch, "hash mismatch"),
+        (counts.unmanaged, "unmanaged"),
+    ]
+    .iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, what)| format!("{} {}", n, what))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
@@ -1,27 +1,106 @@
 // Sync module for synchronizing plugins directory with lockfile
 
+use crate::commands::report::Report;
 use crate::config;
+use crate::error::MpmError;
+use crate::jar;
 use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::manifest::Manifest;
+use crate::sources::{LocalSource, http};
 use crate::ui;
+use futures::future::join_all;
+use indicatif::{HumanBytes, MultiProgress, ProgressBar};
 use log::debug;
+use reqwest::StatusCode;
 use sha2::{Digest, Sha256, Sha512};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Suffix of a download still in progress in the staging directory
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// Options controlling a sync run
+#[derive(Debug, Default)]
+pub struct SyncOptions {
+    /// Preview changes without modifying the plugins directory
+    pub dry_run: bool,
+    /// Maximum parallel downloads (falls back to `sync.concurrency` in the global config)
+    pub concurrency: Option<usize>,
+    /// Only install plugins in this group (plus ungrouped plugins)
+    pub group: Option<String>,
+    /// Only sync these plugins, leaving every other file alone
+    pub include: Vec<String>,
+    /// Sync every plugin except these, whose files are left alone
+    pub exclude: Vec<String>,
+    /// Leave JARs that aren't in the lockfile in place instead of removing them
+    pub keep_unmanaged: bool,
+    /// Install from the local JAR cache instead of downloading
+    pub offline: bool,
+    /// Re-download every plugin, even those whose file already matches the lockfile
+    pub force: bool,
+    /// Print a JSON report instead of progress output
+    pub json: bool,
+}
+
+pub async fn sync_plugins_with_options(options: &SyncOptions) -> anyhow::Result<i32> {
+    let dry_run = options.dry_run;
 
-pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
     // Exit codes:
     // 0 = healthy, no issues
     // 1 = warnings only (changes detected in dry-run)
     // 2 = errors present
 
     // Load lockfile
-    let lockfile = match Lockfile::load() {
-        Ok(lockfile) => lockfile,
-        Err(_) => {
-            ui::error("Lockfile not found. Run 'mpm lock' first.");
-            return Ok(2);
+    let mut lockfile = Lockfile::load_existing()?;
+
+    // Plugins outside the group are treated as unmanaged and removed
+    if let Some(group) = &options.group {
+        let selected = Manifest::load()
+            .map_err(|_| MpmError::ManifestNotFound)?
+            .resolve_for_env(config::env())
+            .and_then(|m| m.plugins_for_group(Some(group)))?;
+        lockfile.plugin.retain(|p| selected.contains_key(&p.name));
+    }
+
+    // Disabled plugins are synced as if they weren't locked, even before a
+    // re-lock has dropped them, so their files are removed
+    if let Ok(manifest) = Manifest::load()
+        && let Ok(manifest) = manifest.resolve_for_env(config::env())
+    {
+        lockfile.plugin.retain(|p| {
+            let disabled = manifest
+                .plugins
+                .get(&p.name)
+                .is_some_and(|s| s.is_disabled());
+            if disabled {
+                debug!("Skipping disabled plugin {}", p.name);
+            }
+            !disabled
+        });
+    }
+
+    // Files of excluded plugins are still managed, so they aren't removed
+    let managed_files: std::collections::HashSet<String> =
+        lockfile.plugin.iter().map(|p| p.file.clone()).collect();
+    for name in options.include.iter().chain(&options.exclude) {
+        if !lockfile.plugin.iter().any(|p| &p.name == name) {
+            return fail(
+                options,
+                &format!("Plugin '{}' is not in the lockfile", name),
+            );
         }
-    };
+    }
+    if !options.include.is_empty() {
+        lockfile
+            .plugin
+            .retain(|p| options.include.contains(&p.name));
+    }
+    lockfile
+        .plugin
+        .retain(|p| !options.exclude.contains(&p.name));
 
     // Check if there are any GitHub plugins and warn once about version compatibility
     let has_github_plugins = lockfile.plugin.iter().any(|p| p.source == "github");
@@ -34,33 +113,42 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
 
     let plugins_dir = config::plugins_dir();
 
+    let concurrency = options
+        .concurrency
+        .unwrap_or_else(|| config::global().sync_concurrency());
+    if concurrency == 0 {
+        return fail(options, "Sync concurrency must be at least 1");
+    }
+    debug!("Downloading with concurrency {}", concurrency);
+
     if dry_run {
         ui::status("[DRY RUN]", "Previewing sync changes...");
     }
 
-    let staging_dir = format!("{}/.plugins.staging", plugins_dir);
-    let backup_dir = format!("{}/.plugins.backup", plugins_dir);
+    let staging_dir = staging_dir(&plugins_dir);
+    let backup_dir = backup_dir(&plugins_dir);
 
     // Clean up any leftover staging/backup directories
-    if !dry_run && let Err(e) = cleanup_temp_dirs(&plugins_dir) {
-        ui::error(&format!("Failed to cleanup temp directories: {}", e));
-        return Ok(2);
+    if !dry_run && let Err(e) = cleanup_keeping_partial_downloads(&plugins_dir) {
+        return fail(
+            options,
+            &format!("Failed to cleanup temp directories: {}", e),
+        );
     }
 
     // Create staging directory
     if !dry_run && let Err(e) = fs::create_dir_all(&staging_dir) {
-        ui::error(&format!("Failed to create staging directory: {}", e));
-        return Ok(2);
+        return fail(
+            options,
+            &format!("Failed to create staging directory: {}", e),
+        );
     }
 
     // Create backup of current plugins directory
     let _backup_created = if !dry_run {
         match create_backup(&plugins_dir, &backup_dir) {
             Ok(created) => created,
-            Err(e) => {
-                ui::error(&format!("Failed to create backup: {}", e));
-                return Ok(2);
-            }
+            Err(e) => return fail(options, &format!("Failed to create backup: {}", e)),
         }
     } else {
         false
@@ -69,29 +157,39 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
     // Track if we need to restore on error
     let mut needs_restore = false;
 
+    // What happened to each file, for the --json report
+    let mut downloaded: Vec<String> = Vec::new();
+    let mut already_synced: Vec<String> = Vec::new();
+    let mut removed: Vec<String> = Vec::new();
+
     let result = async {
         needs_restore = true;
 
-        // Get list of managed plugin filenames
-        let managed_files: std::collections::HashSet<String> =
-            lockfile.plugin.iter().map(|p| p.file.clone()).collect();
+        // A plugin is already synced if its file exists with the locked hash,
+        // unless a forced sync re-downloads everything
+        let is_synced = |plugin: &LockedPlugin, force: bool| -> anyhow::Result<bool> {
+            if force {
+                debug!("  ↓ {} (forced download)", plugin.name);
+                return Ok(false);
+            }
+            let target_path = Path::new(&plugins_dir).join(&plugin.file);
+            if !target_path.exists() {
+                return Ok(false);
+            }
+            // Parse hash to get algorithm
+            let (algorithm, _) = plugin.parse_hash()?;
+            Ok(verify_plugin_hash(&target_path, algorithm)
+                .is_ok_and(|existing_hash| existing_hash == plugin.hash))
+        };
 
         // Track which files need to be downloaded
         let mut files_to_download = Vec::new();
 
         for plugin in &lockfile.plugin {
-            let target_path = Path::new(&plugins_dir).join(&plugin.file);
-
-            // Check if file already exists with correct hash and filename
-            if target_path.exists() {
-                // Parse hash to get algorithm
-                let (algorithm, _) = plugin.parse_hash()?;
-                if let Ok(existing_hash) = verify_plugin_hash(&target_path, algorithm)
-                    && existing_hash == plugin.hash
-                {
-                    debug!("  ✓ {} (already synced)", plugin.name);
-                    continue;
-                }
+            if is_synced(plugin, options.force)? {
+                debug!("  ✓ {} (already synced)", plugin.name);
+                already_synced.push(plugin.file.clone());
+                continue;
             }
 
             files_to_download.push(plugin);
@@ -99,19 +197,54 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
 
         // Track if there are changes (for exit code)
         let mut has_changes = !files_to_download.is_empty();
+        downloaded.extend(files_to_download.iter().map(|p| p.file.clone()));
+
+        // Offline syncs can only install what earlier syncs cached
+        if options.offline {
+            let missing: Vec<String> = files_to_download
+                .iter()
+                .filter(|p| p.source != "local")
+                .filter(|p| !cached_jar_path(p).is_some_and(|path| path.is_file()))
+                .map(|p| format!("{} ({})", p.name, p.file))
+                .collect();
+            if !missing.is_empty() {
+                anyhow::bail!(
+                    "Offline cache is missing {} file(s):\n  {}\nRun 'mpm sync' with network access to cache them.",
+                    missing.len(),
+                    missing.join("\n  ")
+                );
+            }
+        }
 
         // Download files that need updating
-        for plugin in files_to_download {
-            if dry_run {
-                ui::action(&format!("Would download {}", plugin.name));
-            } else {
-                let staging_path = Path::new(&staging_dir).join(&plugin.file);
-                download_and_verify_with_progress(plugin, &staging_path).await?;
+        if dry_run {
+            for plugin in files_to_download {
+                match plugin.size_bytes {
+                    Some(size) => ui::action(&format!(
+                        "Would download {} ({})",
+                        plugin.name,
+                        HumanBytes(size)
+                    )),
+                    None => ui::action(&format!("Would download {}", plugin.name)),
+                }
+            }
+        } else {
+            if !files_to_download.is_empty() {
+                ui::dim(&format!(
+                    "Downloading {} plugin(s){}",
+                    files_to_download.len(),
+                    download_size_note(&files_to_download)
+                ));
             }
+            download_all(files_to_download, &staging_dir, concurrency, options.offline).await?;
         }
 
-        // Remove unmanaged .jar files
-        if dry_run {
+        // Remove unmanaged .jar files, except in a sync of only some plugins
+        if !options.include.is_empty() {
+            debug!("Keeping unmanaged files in a partial sync");
+        } else if options.keep_unmanaged || config::global().sync.keep_unmanaged {
+            debug!("Keeping unmanaged files");
+        } else if dry_run {
             // Just preview what would be removed
             let plugins_path = Path::new(&plugins_dir);
             if plugins_path.exists()
@@ -126,18 +259,19 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
                         && !managed_files.contains(filename)
                     {
                         ui::action(&format!("Would remove unmanaged file: {}", filename));
+                        removed.push(filename.to_string());
                         has_changes = true;
                     }
                 }
             }
         } else {
-            let unmanaged_removed = remove_unmanaged_files(&plugins_dir, &managed_files)?;
-            has_changes = has_changes || unmanaged_removed;
+            removed = remove_unmanaged_files(&plugins_dir, &managed_files)?;
+            has_changes = has_changes || !removed.is_empty();
         }
 
         // Atomically replace plugins
         if !dry_run {
-            atomic_replace(&plugins_dir, &staging_dir, &backup_dir)?;
+            atomic_replace(&plugins_dir, &staging_dir)?;
         }
 
         needs_restore = false;
@@ -151,6 +285,7 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
         Err(e) => {
             // Error occurred - cleanup and return exit code 2
             ui::error(&e.to_string());
+            let report = options.json.then(|| Report::error(e.to_string()));
 
             // Cleanup and restore on error
             if !dry_run
@@ -160,11 +295,15 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
                 ui::warning(&format!("Failed to restore backup: {}", restore_err));
             }
 
-            // Clean up staging and backup directories
+            // Clean up staging and backup directories, keeping partial
+            // downloads for the next sync to resume
             if !dry_run {
-                let _ = cleanup_temp_dirs(&plugins_dir);
+                let _ = cleanup_keeping_partial_downloads(&plugins_dir);
             }
 
+            if let Some(report) = report {
+                report.print()?;
+            }
             return Ok(2);
         }
     };
@@ -175,14 +314,67 @@ pub async fn sync_plugins(dry_run: bool) -> anyhow::Result<i32> {
         // Don't fail on cleanup, but log it
     }
 
-    if dry_run {
-        ui::dim(&format!("Would sync {} plugin(s)", lockfile.plugin.len()));
+    let (exit_code, message) = if dry_run {
+        let message = format!("Would sync {} plugin(s)", lockfile.plugin.len());
+        ui::dim(&message);
         // Return exit code: 0 = no changes, 1 = changes detected
-        Ok(if has_changes { 1 } else { 0 })
+        (if has_changes { 1 } else { 0 }, message)
     } else {
-        ui::success(&format!("Synced {} plugin(s)", lockfile.plugin.len()));
-        Ok(0) // Success
+        let message = format!("Synced {} plugin(s)", lockfile.plugin.len());
+        ui::success(&message);
+
+        // A failing hook is reported but doesn't fail the sync, which succeeded
+        if has_changes && let Some(command) = post_sync_command() {
+            run_post_sync_command(&command, options.json);
+        }
+        (0, message) // Success
+    };
+
+    if options.json {
+        let affected = lockfile
+            .plugin
+            .iter()
+            .filter(|p| downloaded.contains(&p.file))
+            .map(|p| p.name.clone())
+            .chain(
+                removed
+                    .iter()
+                    .map(|f| f.trim_end_matches(".jar").to_string()),
+            )
+            .collect();
+        let mut report = Report::ok(message, affected);
+        report.downloaded = Some(downloaded);
+        report.already_synced = Some(already_synced);
+        report.removed = Some(removed);
+        report.print()?;
+    }
+
+    Ok(exit_code)
+}
+
+/// Report a sync that stopped before touching the plugins directory (exit code 2)
+fn fail(options: &SyncOptions, message: &str) -> anyhow::Result<i32> {
+    ui::error(message);
+    if options.json {
+        Report::error(message).print()?;
     }
+    Ok(2)
+}
+
+/// Run a sync while serving a health check endpoint on the given port
+///
+/// The endpoint reports "syncing" for the duration of the sync and is shut
+/// down once it completes, regardless of the outcome.
+#[cfg(feature = "health-server")]
+pub async fn sync_plugins_with_health(options: &SyncOptions, port: u16) -> anyhow::Result<i32> {
+    let server = crate::health::HealthServer::start(port).await?;
+
+    server.set_syncing(true);
+    let result = sync_plugins_with_options(options).await;
+    server.set_syncing(false);
+
+    server.shutdown().await;
+    result
 }
 
 pub fn verify_plugin_hash(file_path: &Path, algorithm: &str) -> anyhow::Result<String> {
@@ -203,65 +395,169 @@ pub fn verify_plugin_hash(file_path: &Path, algorithm: &str) -> anyhow::Result<S
     Ok(format!("{}:{}", algorithm, hash_hex))
 }
 
-async fn download_and_verify_with_progress(
-    plugin: &LockedPlugin,
-    target_path: &Path,
-) -> anyhow::Result<()> {
-    // Create spinner for download
-    let pb = ui::spinner(&format!("Downloading {}...", plugin.name));
+/// Total download size, e.g. " (14.20 MiB)", when the lockfile records every file's size
+fn download_size_note(plugins: &[&LockedPlugin]) -> String {
+    plugins
+        .iter()
+        .map(|p| p.size_bytes)
+        .sum::<Option<u64>>()
+        .map(|total| format!(" ({})", HumanBytes(total)))
+        .unwrap_or_default()
+}
 
-    // Download file
-    let response = reqwest::get(&plugin.url).await?;
+/// The command to run after a sync that changed something: the manifest's
+/// `post_sync_command`, else `sync.post_command` from the global config
+fn post_sync_command() -> Option<String> {
+    Manifest::load()
+        .ok()
+        .and_then(|m| m.minecraft.post_sync_command)
+        .or_else(|| config::global().sync.post_command.clone())
+        .filter(|command| !command.trim().is_empty())
+}
 
-    // Get content length for progress (if available)
-    let total_size = response.content_length();
+/// Run the post-sync command through the shell in the project directory, with
+/// its output shown as it runs
+///
+/// With `--json` the command's stdout goes to stderr, so stdout stays valid JSON.
+fn run_post_sync_command(command: &str, json: bool) {
+    ui::action(&format!("Running post-sync command: {}", command));
+
+    #[cfg(not(windows))]
+    let mut process = std::process::Command::new("sh");
+    #[cfg(not(windows))]
+    process.arg("-c").arg(command);
+    #[cfg(windows)]
+    let mut process = std::process::Command::new("cmd");
+    #[cfg(windows)]
+    process.arg("/C").arg(command);
+
+    process.current_dir(config::config_dir());
+    if json {
+        process.stdout(std::io::stderr());
+    }
+    match process.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => ui::warning(&format!("Post-sync command failed ({})", status)),
+        Err(e) => ui::warning(&format!("Could not run post-sync command: {}", e)),
+    }
+}
 
-    // Update progress bar if we have size info
-    if let Some(size) = total_size {
-        pb.set_length(size);
-        pb.set_style(
-            indicatif::ProgressStyle::default_bar()
-                .template("{spinner:.cyan} {msg} [{bar:25.cyan/dim}] {bytes}/{total_bytes}")
-                .unwrap()
-                .progress_chars("━━╺"),
+/// Download plugins into the staging directory, at most `concurrency` at a time
+///
+/// Every download runs to completion; failures are collected and reported together.
+async fn download_all(
+    plugins: Vec<&LockedPlugin>,
+    staging_dir: &Path,
+    concurrency: usize,
+    offline: bool,
+) -> anyhow::Result<()> {
+    let multi = ui::multi_progress();
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+
+    let handles: Vec<_> = plugins
+        .into_iter()
+        .map(|plugin| {
+            let plugin = plugin.clone();
+            let staging_path = staging_dir.join(&plugin.file);
+            let semaphore = Arc::clone(&semaphore);
+            let multi = multi.clone();
+
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await?;
+                download_and_verify_with_progress(&plugin, &staging_path, &multi, offline).await
+            })
+        })
+        .collect();
+
+    let failures: Vec<String> = join_all(handles)
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(e) => Some(format!("download task failed: {}", e)),
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        anyhow::bail!(
+            "Failed to download {} plugin(s):\n  {}",
+            failures.len(),
+            failures.join("\n  ")
         );
     }
 
-    let data = response.bytes().await?;
+    Ok(())
+}
+
+async fn download_and_verify_with_progress(
+    plugin: &LockedPlugin,
+    target_path: &Path,
+    multi: &MultiProgress,
+    offline: bool,
+) -> anyhow::Result<()> {
+    // Create spinner for download
+    let pb = ui::multi_spinner(multi, &format!("Downloading {}...", plugin.name));
+
+    let mut from_cache = false;
+    let data = if plugin.source == "local" {
+        // Local plugins are copied from disk instead of downloaded
+        read_local_plugin(plugin).inspect_err(|_| {
+            ui::finish_spinner_error(&pb, &format!("{}: local file unavailable", plugin.name));
+        })?
+    } else if offline {
+        read_cached_jar(plugin).inspect_err(|_| {
+            ui::finish_spinner_error(&pb, &format!("{}: not in offline cache", plugin.name));
+        })?
+    } else if let Some(data) = cached_copy(plugin) {
+        // An earlier sync already downloaded these exact bytes
+        debug!("Using cached copy of {}", plugin.name);
+        from_cache = true;
+        data
+    } else {
+        // Download file through the shared client, so proxy settings apply
+        let data = download_with_fallbacks(plugin, &partial_path(plugin, target_path), &pb)
+            .await
+            .map_err(|e| {
+                ui::finish_spinner_error(&pb, &format!("{}: download failed", plugin.name));
+                anyhow::anyhow!("Failed to download {}: {}", plugin.name, e)
+            })?;
+
+        // Zipped plugins are locked as the JAR inside the archive
+        match &plugin.zip_entry {
+            Some(entry) => jar::extract_entry(&data, entry).map_err(|e| {
+                ui::finish_spinner_error(&pb, &format!("{}: extraction failed", plugin.name));
+                anyhow::anyhow!("Failed to extract {} from {}: {}", entry, plugin.url, e)
+            })?,
+            None => data,
+        }
+    };
     pb.set_position(data.len() as u64);
 
     // Parse hash to get algorithm and expected hash
     let (algorithm, expected_hash) = plugin.parse_hash()?;
 
     // Compute hash using the correct algorithm
-    let computed_hash = match algorithm {
-        "sha256" => {
-            let mut hasher = Sha256::new();
-            hasher.update(&data);
-            hex::encode(hasher.finalize())
-        }
-        "sha512" => {
-            let mut hasher = Sha512::new();
-            hasher.update(&data);
-            hex::encode(hasher.finalize())
-        }
-        _ => {
-            ui::finish_spinner_error(&pb, &format!("{}: unsupported hash algorithm", plugin.name));
-            anyhow::bail!("Unsupported hash algorithm: {}", algorithm);
-        }
+    let Some(computed_hash) = hash_hex(algorithm, &data) else {
+        ui::finish_spinner_error(&pb, &format!("{}: unsupported hash algorithm", plugin.name));
+        anyhow::bail!(
+            "Unsupported hash algorithm for {}: {}",
+            plugin.name,
+            algorithm
+        );
     };
 
     // Compare computed hash with expected hash
     if computed_hash != expected_hash {
         ui::finish_spinner_error(&pb, &format!("{}: hash mismatch", plugin.name));
-        anyhow::bail!(
-            "Hash mismatch for {}: expected {}:{}, got {}:{}",
-            plugin.name,
-            algorithm,
-            expected_hash,
-            algorithm,
-            computed_hash
-        );
+        // Resuming these bytes would only fail again, so the next sync starts over
+        let _ = fs::remove_file(partial_path(plugin, target_path));
+        return Err(MpmError::HashMismatch {
+            plugin: plugin.name.clone(),
+            expected: format!("{}:{}", algorithm, expected_hash),
+            actual: format!("{}:{}", algorithm, computed_hash),
+        }
+        .into());
     }
 
     // Write to staging
@@ -269,13 +565,226 @@ async fn download_and_verify_with_progress(
         fs::create_dir_all(parent)?;
     }
     fs::write(target_path, &data)?;
+    let _ = fs::remove_file(partial_path(plugin, target_path));
+
+    // Keep a verified copy for later syncs, including offline ones
+    if !offline && !from_cache && plugin.source != "local" && config::global().cache.enabled {
+        cache_jar(plugin, &data);
+    }
 
     ui::finish_download_success(&pb, &plugin.name);
 
     Ok(())
 }
 
-fn create_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<bool> {
+/// Where an in-progress download is written, so an interrupted sync can resume it
+///
+/// The name includes the start of the locked hash, so a partial file is never
+/// resumed into a different build of the same filename.
+fn partial_path(plugin: &LockedPlugin, target_path: &Path) -> PathBuf {
+    let hash = plugin.parse_hash().map_or("", |(_, hash)| hash);
+    let mut name = target_path.as_os_str().to_owned();
+    name.push(format!(
+        ".{}{}",
+        hash.get(..16).unwrap_or(hash),
+        PARTIAL_SUFFIX
+    ));
+    PathBuf::from(name)
+}
+
+/// Download a plugin from its locked URL, or from its fallback URLs in turn
+///
+/// A 404 stops at once, since the locked file is gone rather than
+/// unreachable. Whichever URL serves the file, its hash is checked as usual.
+async fn download_with_fallbacks(
+    plugin: &LockedPlugin,
+    partial: &Path,
+    pb: &ProgressBar,
+) -> anyhow::Result<Vec<u8>> {
+    let mut urls = plugin.download_urls().peekable();
+    loop {
+        let url = urls.next().expect("at least the primary URL");
+        let error = match download_resuming(plugin, url, partial, pb).await {
+            Ok(data) => return Ok(data),
+            Err(e) => e,
+        };
+        let not_found = matches!(
+            error.downcast_ref::<MpmError>(),
+            Some(MpmError::NetworkError { status: 404, .. })
+        );
+        if not_found || urls.peek().is_none() {
+            return Err(error);
+        }
+        ui::warning(&format!(
+            "Download of {} from {} failed ({}), trying the next URL",
+            plugin.name, url, error
+        ));
+    }
+}
+
+/// Download a plugin into its partial file and return the complete file
+///
+/// Bytes already in the partial file from an interrupted sync are kept and
+/// only the rest is requested with a `Range` header. A server that ignores
+/// the range sends the whole file, which then replaces the partial one.
+async fn download_resuming(
+    plugin: &LockedPlugin,
+    url: &str,
+    partial: &Path,
+    pb: &ProgressBar,
+) -> anyhow::Result<Vec<u8>> {
+    let mut offset = fs::metadata(partial).map_or(0, |m| m.len());
+    let mut response = if offset > 0 {
+        debug!("Resuming {} from byte {}", plugin.name, offset);
+        http::download_from_offset(url, offset).await?
+    } else {
+        http::download_with_response(url).await?
+    };
+
+    // The partial file is already as long as (or longer than) the whole file
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        debug!("Cannot resume {}, downloading it again", plugin.name);
+        offset = 0;
+        response = http::download_with_response(url).await?;
+    }
+    if !response.status().is_success() {
+        return Err(MpmError::NetworkError {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        }
+        .into());
+    }
+
+    let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if offset > 0 && !resumed {
+        debug!(
+            "Server ignored the range for {}, downloading it again",
+            plugin.name
+        );
+        offset = 0;
+    }
+
+    // Update progress bar if we have size info
+    if let Some(size) = response.content_length() {
+        pb.set_length(offset + size);
+        pb.set_style(
+            indicatif::ProgressStyle::default_bar()
+                .template("{spinner:.cyan} {msg} [{bar:25.cyan/dim}] {bytes}/{total_bytes}")
+                .unwrap()
+                .progress_chars("━━╺"),
+        );
+    }
+    pb.set_position(offset);
+
+    if let Some(parent) = partial.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = if resumed {
+        fs::OpenOptions::new().append(true).open(partial)?
+    } else {
+        fs::File::create(partial)?
+    };
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        pb.inc(chunk.len() as u64);
+    }
+    file.flush()?;
+
+    Ok(fs::read(partial)?)
+}
+
+/// Where a verified copy of a plugin is kept, addressed by its content hash
+///
+/// Files live in `<cache dir>/jars/<algorithm>/<first 2 hex digits>/<hash>.jar`,
+/// so identical files are stored once whatever plugin or filename they're
+/// locked under.
+fn cached_jar_path(plugin: &LockedPlugin) -> Option<PathBuf> {
+    let (algorithm, hash) = plugin.parse_hash().ok()?;
+    let prefix = hash.get(..2)?;
+    Some(
+        config::cache_dir()?
+            .join("jars")
+            .join(algorithm)
+            .join(prefix)
+            .join(format!("{}.jar", hash)),
+    )
+}
+
+/// A cached copy of a plugin, if there is one and it still matches the lockfile
+fn cached_copy(plugin: &LockedPlugin) -> Option<Vec<u8>> {
+    if !config::global().cache.enabled {
+        return None;
+    }
+    let data = fs::read(cached_jar_path(plugin)?).ok()?;
+    let (algorithm, expected_hash) = plugin.parse_hash().ok()?;
+    if hash_hex(algorithm, &data)? != expected_hash {
+        debug!("Ignoring cached copy of {}: hash mismatch", plugin.name);
+        return None;
+    }
+    Some(data)
+}
+
+/// Hex digest of `data`, or `None` for an unsupported algorithm
+fn hash_hex(algorithm: &str, data: &[u8]) -> Option<String> {
+    match algorithm {
+        "sha256" => Some(hex::encode(Sha256::digest(data))),
+        "sha512" => Some(hex::encode(Sha512::digest(data))),
+        _ => None,
+    }
+}
+
+fn read_cached_jar(plugin: &LockedPlugin) -> anyhow::Result<Vec<u8>> {
+    let path = cached_jar_path(plugin)
+        .ok_or_else(|| anyhow::anyhow!("No cache directory for offline plugin {}", plugin.name))?;
+    fs::read(&path).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read {} from offline cache '{}': {}",
+            plugin.name,
+            path.display(),
+            e
+        )
+    })
+}
+
+/// Copy a verified plugin into the JAR cache, skipping it if that fails
+fn cache_jar(plugin: &LockedPlugin, data: &[u8]) {
+    let Some(path) = cached_jar_path(plugin) else {
+        return;
+    };
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, data));
+    if let Err(e) = written {
+        debug!(
+            "Failed to cache {} at {}: {}",
+            plugin.name,
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Read a `local` plugin from the path recorded in its lockfile URL
+fn read_local_plugin(plugin: &LockedPlugin) -> anyhow::Result<Vec<u8>> {
+    let path = LocalSource::path_from_url(&plugin.url).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid URL for local plugin {}: expected 'local:<path>', got '{}'",
+            plugin.name,
+            plugin.url
+        )
+    })?;
+    fs::read(&path).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to read local plugin {} from '{}': {}",
+            plugin.name,
+            path.display(),
+            e
+        )
+    })
+}
+
+fn create_backup(plugins_dir: &str, backup_dir: &Path) -> anyhow::Result<bool> {
     let plugins_path = Path::new(plugins_dir);
     if !plugins_path.exists() {
         return Ok(false);
@@ -291,7 +800,7 @@ fn create_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<bool> {
             let path = entry.path();
             if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("jar") {
                 let filename = path.file_name().unwrap();
-                let backup_path = Path::new(backup_dir).join(filename);
+                let backup_path = backup_dir.join(filename);
                 fs::copy(&path, &backup_path)?;
                 backed_up = true;
             }
@@ -301,8 +810,7 @@ fn create_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<bool> {
     Ok(backed_up)
 }
 
-fn restore_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<()> {
-    let backup_path = Path::new(backup_dir);
+fn restore_backup(plugins_dir: &str, backup_path: &Path) -> anyhow::Result<()> {
     if !backup_path.exists() {
         return Ok(());
     }
@@ -328,7 +836,7 @@ fn restore_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<()> {
         for entry in entries {
             let entry = entry?;
             let path = entry.path();
-            if path.is_file() {
+            if path.is_file() && !is_partial_download(&path) {
                 let filename = path.file_name().unwrap();
                 let target_path = plugins_path.join(filename);
                 fs::copy(&path, &target_path)?;
@@ -339,55 +847,24 @@ fn restore_backup(plugins_dir: &str, backup_dir: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn atomic_replace(plugins_dir: &str, staging_dir: &str, _backup_dir: &str) -> anyhow::Result<()> {
+fn atomic_replace(plugins_dir: &str, staging_path: &Path) -> anyhow::Result<()> {
     let plugins_path = Path::new(plugins_dir);
-    let staging_path = Path::new(staging_dir);
 
-    // Get list of files in staging (these are the ones we downloaded)
-    let mut staged_files = std::collections::HashSet::new();
+    // Move verified files from staging over their counterparts in the plugins
+    // directory. Staging lives inside the plugins directory, so each move is a
+    // rename on the same volume and the old file is never missing.
     if staging_path.exists()
         && let Ok(entries) = fs::read_dir(staging_path)
     {
         for entry in entries {
             let entry = entry?;
             let path = entry.path();
-            if path.is_file()
-                && let Some(filename) = path.file_name().and_then(|n| n.to_str())
-            {
-                staged_files.insert(filename.to_string());
-            }
-        }
-    }
-
-    // Remove .jar files that are being replaced (exist in staging)
-    if plugins_path.exists()
-        && let Ok(entries) = fs::read_dir(plugins_path)
-    {
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-            // Only remove .jar files that are being replaced, preserve manifest and lockfile
-            if path.is_file()
-                && path.extension().and_then(|s| s.to_str()) == Some("jar")
-                && let Some(filename) = path.file_name().and_then(|n| n.to_str())
-                && staged_files.contains(filename)
-            {
-                fs::remove_file(&path)?;
-            }
-        }
-    }
-
-    // Copy verified files from staging to plugins directory
-    if staging_path.exists()
-        && let Ok(entries) = fs::read_dir(staging_path)
-    {
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
+            if path.is_file() && !is_partial_download(&path) {
                 let filename = path.file_name().unwrap();
                 let target_path = plugins_path.join(filename);
-                fs::copy(&path, &target_path)?;
+                replace_file(&path, &target_path).map_err(|e| {
+                    anyhow::anyhow!("Failed to replace '{}': {}", target_path.display(), e)
+                })?;
             }
         }
     }
@@ -395,16 +872,54 @@ fn atomic_replace(plugins_dir: &str, staging_dir: &str, _backup_dir: &str) -> an
     Ok(())
 }
 
+/// Rename `from` to `to`, replacing `to` if it exists
+#[cfg(not(windows))]
+fn replace_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::rename(from, to)
+}
+
+/// Rename `from` to `to`, replacing `to` if it exists
+///
+/// Uses `MoveFileExW` so the replacement is a single call that is flushed to
+/// disk before returning.
+#[cfg(windows)]
+fn replace_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{
+        MOVEFILE_REPLACE_EXISTING, MOVEFILE_WRITE_THROUGH, MoveFileExW,
+    };
+
+    let wide = |path: &Path| -> Vec<u16> {
+        path.as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    };
+    let (from, to) = (wide(from), wide(to));
+    // SAFETY: both pointers are NUL-terminated UTF-16 strings that outlive the call
+    let moved = unsafe {
+        MoveFileExW(
+            from.as_ptr(),
+            to.as_ptr(),
+            MOVEFILE_REPLACE_EXISTING | MOVEFILE_WRITE_THROUGH,
+        )
+    };
+    if moved == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
 fn remove_unmanaged_files(
     plugins_dir: &str,
     managed_files: &std::collections::HashSet<String>,
-) -> anyhow::Result<bool> {
+) -> anyhow::Result<Vec<String>> {
     let plugins_path = Path::new(plugins_dir);
+    let mut removed = Vec::new();
     if !plugins_path.exists() {
-        return Ok(false);
+        return Ok(removed);
     }
 
-    let mut removed_any = false;
     if let Ok(entries) = fs::read_dir(plugins_path) {
         for entry in entries {
             let entry = entry?;
@@ -416,24 +931,64 @@ fn remove_unmanaged_files(
                 if filename.ends_with(".jar") && !managed_files.contains(filename) {
                     ui::action(&format!("Removing unmanaged file: {}", filename));
                     fs::remove_file(&path)?;
-                    removed_any = true;
+                    removed.push(filename.to_string());
                 }
             }
         }
     }
 
-    Ok(removed_any)
+    Ok(removed)
+}
+
+/// Directory new downloads are verified in before they replace existing files
+pub fn staging_dir(plugins_dir: &str) -> PathBuf {
+    Path::new(plugins_dir).join(".plugins.staging")
+}
+
+/// Directory the current JARs are copied to while a sync is in progress
+pub fn backup_dir(plugins_dir: &str) -> PathBuf {
+    Path::new(plugins_dir).join(".plugins.backup")
+}
+
+/// Whether a staged file is an unfinished download (see `partial_path`)
+fn is_partial_download(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(PARTIAL_SUFFIX)
+}
+
+/// Remove what an interrupted sync left behind, except unfinished downloads
+///
+/// Partial files stay in the staging directory so the next sync can resume them.
+fn cleanup_keeping_partial_downloads(plugins_dir: &str) -> anyhow::Result<()> {
+    let backup_dir = backup_dir(plugins_dir);
+    if backup_dir.exists() {
+        fs::remove_dir_all(&backup_dir)?;
+    }
+
+    let staging_dir = staging_dir(plugins_dir);
+    if let Ok(entries) = fs::read_dir(&staging_dir) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else if !is_partial_download(&path) {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+
+    Ok(())
 }
 
-fn cleanup_temp_dirs(plugins_dir: &str) -> anyhow::Result<()> {
-    let staging_dir = format!("{}/.plugins.staging", plugins_dir);
-    let backup_dir = format!("{}/.plugins.backup", plugins_dir);
+/// Remove the staging and backup directories a sync leaves behind if interrupted
+pub fn cleanup_temp_dirs(plugins_dir: &str) -> anyhow::Result<()> {
+    let staging_dir = staging_dir(plugins_dir);
+    let backup_dir = backup_dir(plugins_dir);
 
-    if Path::new(&staging_dir).exists() {
+    if staging_dir.exists() {
         fs::remove_dir_all(&staging_dir)?;
     }
 
-    if Path::new(&backup_dir).exists() {
+    if backup_dir.exists() {
         fs::remove_dir_all(&backup_dir)?;
     }
 
@@ -0,0 +1,140 @@
+// Update command for upgrading plugins to their latest compatible versions
+
+use crate::commands::lock;
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::Lockfile;
+use crate::manifest::Manifest;
+use crate::ui;
+use std::collections::HashMap;
+
+pub async fn update(names: Vec<String>, dry_run: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = healthy, no issues
+    // 1 = warnings only (changes detected in dry-run)
+    // 2 = errors present
+
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    // Plugins as the selected environment sees them; pins are cleared in `manifest`
+    let resolved = manifest.resolve_for_env(config::env())?;
+
+    // Validate requested names up front so typos don't silently do nothing
+    for name in &names {
+        if !resolved.plugins.contains_key(name) {
+            anyhow::bail!("Plugin '{}' not found in manifest", name);
+        }
+    }
+
+    // Disabled plugins aren't locked, so there is nothing to update
+    let targets: Vec<String> = if names.is_empty() {
+        resolved
+            .plugins
+            .iter()
+            .filter(|(_, spec)| !spec.is_disabled())
+            .map(|(name, _)| name.clone())
+            .collect()
+    } else {
+        names
+            .into_iter()
+            .filter(|name| {
+                let disabled = resolved.plugins[name].is_disabled();
+                if disabled {
+                    ui::warning(&format!("Skipping disabled plugin '{}'", name));
+                }
+                !disabled
+            })
+            .collect()
+    };
+
+    if dry_run {
+        ui::status("[DRY RUN]", "Previewing plugin updates...");
+    }
+
+    // The current lockfile, used to report what changed and to keep the
+    // entries of plugins that aren't updated
+    let existing = Lockfile::load().ok();
+    let locked_versions: HashMap<&str, &str> = existing
+        .iter()
+        .flat_map(|lockfile| &lockfile.plugin)
+        .map(|p| (p.name.as_str(), p.version.as_str()))
+        .collect();
+
+    let minecraft_version = resolved.minecraft.version.clone();
+    let mut updated = Vec::new();
+    let mut changes = Vec::new();
+    let mut failed = 0;
+
+    // Resolve each target without its version pin. Failures are reported per
+    // plugin and leave that plugin's pin and lock entry untouched.
+    for name in &targets {
+        let mut spec = resolved.plugins[name].clone();
+        spec.version = None;
+
+        let spinner = ui::spinner(&format!("Resolving {}...", name));
+        let options = resolved.resolve_options(&spec);
+        match lock::resolve_plugin(name, &spec, Some(&minecraft_version), &options).await {
+            Ok(locked) => {
+                ui::finish_spinner_resolved(&spinner, name, &locked.version);
+                if let Some(entry) = manifest.plugin_mut(config::env(), name) {
+                    entry.version = None;
+                }
+                let old_version = locked_versions.get(name.as_str()).map(|v| v.to_string());
+                if old_version.as_deref() != Some(locked.version.as_str()) {
+                    changes.push((name.clone(), old_version, locked.version.clone()));
+                }
+                updated.push(locked);
+            }
+            Err(e) => {
+                ui::finish_spinner_error(&spinner, &format!("{}: {}", name, e));
+                failed += 1;
+            }
+        }
+    }
+
+    for (name, old_version, new_version) in &changes {
+        let prefix = if dry_run { "Would update" } else { "Updating" };
+        ui::action(&format!(
+            "{} {}: {} → {}",
+            prefix,
+            name,
+            old_version.as_deref().unwrap_or("(not locked)"),
+            new_version
+        ));
+    }
+
+    if dry_run {
+        ui::dim(&format!(
+            "Would update {} plugin(s), {} failed",
+            changes.len(),
+            failed
+        ));
+        return Ok(if failed > 0 {
+            2
+        } else if changes.is_empty() {
+            0
+        } else {
+            1
+        });
+    }
+
+    // The lockfile is built from the versions just resolved, so it locks
+    // exactly what was reported
+    let lockfile = lock::lock_resolved(&resolved, existing.as_ref(), updated);
+
+    // Pins are only cleared once the lockfile matching them is written
+    lockfile.save_with_backup()?;
+    manifest.save()?;
+
+    if changes.is_empty() {
+        ui::success("All plugins are up to date");
+    } else {
+        ui::success(&format!("Updated {} plugin(s)", changes.len()));
+    }
+
+    if failed > 0 {
+        ui::error(&format!("{} plugin(s) failed to update", failed));
+        return Ok(2);
+    }
+
+    Ok(0)
+}
@@ -0,0 +1,93 @@
+// Upgrade command for moving outdated plugins to their newest versions
+
+use crate::commands::outdated::{self, OutdatedPlugin};
+use crate::commands::{lock, sync};
+use crate::config;
+use crate::error::MpmError;
+use crate::lockfile::Lockfile;
+use crate::manifest::Manifest;
+use crate::ui;
+
+pub async fn upgrade(names: Vec<String>, yes: bool, no_sync: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = plugins upgraded, or nothing to upgrade
+    // 1 = lock or sync finished with warnings
+    // 2 = errors present
+
+    let mut manifest = Manifest::load().map_err(|_| MpmError::ManifestNotFound)?;
+    let resolved = manifest.resolve_for_env(config::env())?;
+    let lockfile = Lockfile::load_existing()?;
+
+    // Validate requested names up front so typos don't silently do nothing
+    for name in &names {
+        if !resolved.plugins.contains_key(name) {
+            anyhow::bail!("Plugin '{}' not found in manifest", name);
+        }
+    }
+
+    let locked: Vec<_> = lockfile
+        .plugin
+        .into_iter()
+        .filter(|p| names.is_empty() || names.contains(&p.name))
+        .collect();
+    let (outdated, failed) = outdated::find_outdated(&resolved, locked).await?;
+
+    if outdated.is_empty() {
+        ui::success("All plugins are up to date");
+        return Ok(if failed > 0 { 2 } else { 0 });
+    }
+
+    // Named plugins and --yes upgrade without asking; otherwise each is confirmed
+    let selected: Vec<&OutdatedPlugin> = if yes || !names.is_empty() {
+        outdated.iter().collect()
+    } else {
+        outdated
+            .iter()
+            .filter(|p| {
+                ui::confirm(&format!(
+                    "Upgrade {} {} → {}?",
+                    p.name, p.current, p.available
+                ))
+            })
+            .collect()
+    };
+    if selected.is_empty() {
+        ui::dim("No plugins upgraded. Pass --yes to upgrade without prompting.");
+        return Ok(if failed > 0 { 2 } else { 0 });
+    }
+
+    // Clear the pins, then resolve the new versions through the normal lock path
+    for plugin in &selected {
+        if let Some(entry) = manifest.plugin_mut(config::env(), &plugin.name) {
+            entry.version = None;
+        }
+    }
+    manifest.save()?;
+    let mut exit_code = lock::lock(false).await?;
+
+    if !no_sync {
+        exit_code = exit_code.max(sync::sync_plugins_with_options(&Default::default()).await?);
+    }
+
+    // Report the versions that actually ended up in the lockfile
+    let lockfile = Lockfile::load()?;
+    let rows: Vec<[String; 3]> = selected
+        .iter()
+        .map(|p| {
+            let new_version = lockfile
+                .plugin
+                .iter()
+                .find(|locked| locked.name == p.name)
+                .map_or_else(|| "(not locked)".to_string(), |l| l.version.clone());
+            [p.name.clone(), p.current.clone(), new_version]
+        })
+        .collect();
+    ui::table(["NAME", "OLD", "NEW"], &rows);
+    ui::success(&format!("Upgraded {} plugin(s)", selected.len()));
+
+    if failed > 0 {
+        ui::error(&format!("{} plugin(s) could not be checked", failed));
+        return Ok(2);
+    }
+    Ok(exit_code)
+}
@@ -0,0 +1,52 @@
+// Verify command for a fast, offline check of plugin file hashes
+
+use crate::commands::sync::verify_plugin_hash;
+use crate::config;
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::ui;
+use std::path::Path;
+
+pub fn verify(quiet: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = every plugin file matches its locked hash
+    // 1 = a file is missing or its hash differs
+    // 2 = the lockfile can't be read
+
+    let lockfile = Lockfile::load_existing()?;
+    let plugins_dir = config::plugins_dir();
+
+    let mut failed = 0;
+    for plugin in &lockfile.plugin {
+        let problem = check_plugin(Path::new(&plugins_dir), plugin);
+        if problem.is_some() {
+            failed += 1;
+        }
+        if quiet {
+            continue;
+        }
+        match problem {
+            None => ui::success(&format!("{} ({})", plugin.name, plugin.file)),
+            Some(problem) => ui::error(&format!("{} ({}): {}", plugin.name, plugin.file, problem)),
+        }
+    }
+
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+/// What is wrong with a plugin's file, if anything
+fn check_plugin(plugins_dir: &Path, plugin: &LockedPlugin) -> Option<String> {
+    let file_path = plugins_dir.join(&plugin.file);
+    if !file_path.is_file() {
+        return Some("missing".to_string());
+    }
+
+    let (algorithm, _) = match plugin.parse_hash() {
+        Ok(parsed) => parsed,
+        Err(e) => return Some(e.to_string()),
+    };
+    match verify_plugin_hash(&file_path, algorithm) {
+        Ok(hash) if hash == plugin.hash => None,
+        Ok(_) => Some("hash mismatch".to_string()),
+        Err(e) => Some(format!("hash check failed: {}", e)),
+    }
+}
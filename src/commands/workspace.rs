@@ -0,0 +1,263 @@
+// Workspace command for managing the plugins of several servers together
+
+use crate::config;
+use crate::constants;
+use crate::ui;
+use crate::workspace::Workspace;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Register a server directory in the workspace
+pub fn add(name: &str, path: &str) -> anyhow::Result<()> {
+    let mut workspace = Workspace::load_or_default()?;
+    if workspace.servers.contains_key(name) {
+        anyhow::bail!(
+            "Server '{}' is already in {}",
+            name,
+            constants::WORKSPACE_FILE
+        );
+    }
+
+    let dir = Workspace::server_dir(path);
+    if !dir.is_dir() {
+        anyhow::bail!("Server directory '{}' does not exist", path);
+    }
+    if !dir.join(constants::MANIFEST_FILE).exists() {
+        ui::warning(&format!(
+            "'{}' has no {} yet. Run 'mpm init' there before locking it.",
+            path,
+            constants::MANIFEST_FILE
+        ));
+    }
+
+    workspace.servers.insert(name.to_string(), path.to_string());
+    workspace.save()?;
+    ui::success(&format!("Added server {} ({})", name, path));
+    Ok(())
+}
+
+/// Print every registered server
+#[allow(clippy::print_stdout)]
+pub fn list() -> anyhow::Result<()> {
+    let workspace = Workspace::load()?;
+    if workspace.servers.is_empty() {
+        println!("No servers in {}", constants::WORKSPACE_FILE);
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 3]> = workspace
+        .servers
+        .iter()
+        .map(|(name, path)| {
+            let manifest = Workspace::server_dir(path).join(constants::MANIFEST_FILE);
+            let state = if manifest.exists() {
+                "present"
+            } else {
+                "missing"
+            };
+            [name.clone(), path.clone(), state.to_string()]
+        })
+        .collect();
+    ui::table(["NAME", "PATH", "MANIFEST"], &rows);
+    Ok(())
+}
+
+/// Run `mpm lock` in the named servers, or every server with `all`
+pub fn lock(names: &[String], all: bool) -> anyhow::Result<i32> {
+    let servers = select_servers(names, all)?;
+    run_in_servers(&servers, &["lock"], false)
+}
+
+/// Run `mpm sync` in the named servers, or every server with `all`
+pub fn sync(names: &[String], all: bool, parallel: bool) -> anyhow::Result<i32> {
+    let servers = select_servers(names, all)?;
+    run_in_servers(&servers, &["sync"], parallel)
+}
+
+/// Run `mpm doctor` in every server and report the results together
+#[allow(clippy::print_stdout)]
+pub fn doctor(json: bool) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = every server is healthy
+    // 1 = warnings only
+    // 2 = errors in at least one server
+
+    let servers = select_servers(&[], true)?;
+    let mut reports = serde_json::Map::new();
+    let mut exit_code = 0;
+    let mut rows = Vec::new();
+
+    for (name, dir) in &servers {
+        let output = mpm_command(dir, &["doctor", "--json"])?.output()?;
+        let report: serde_json::Value =
+            serde_json::from_slice(&output.stdout).unwrap_or_else(|_| {
+                serde_json::json!({
+                    "status": "error",
+                    "exit_code": 2,
+                    "issues": [{
+                        "severity": "error",
+                        "code": "DOCTOR_FAILED",
+                        "message": String::from_utf8_lossy(&output.stderr).trim(),
+                    }],
+                })
+            });
+
+        let server_exit = report["exit_code"].as_i64().unwrap_or(2) as i32;
+        exit_code = exit_code.max(server_exit);
+
+        let issues = report["issues"].as_array().cloned().unwrap_or_default();
+        let count = |severity: &str| {
+            issues
+                .iter()
+                .filter(|i| i["severity"] == severity)
+                .count()
+                .to_string()
+        };
+        rows.push([
+            name.clone(),
+            report["status"].as_str().unwrap_or("error").to_string(),
+            count("error"),
+            count("warning"),
+        ]);
+        reports.insert(name.clone(), report);
+    }
+
+    if json {
+        let output = serde_json::json!({
+            "schema_version": constants::SCHEMA_VERSION,
+            "exit_code": exit_code,
+            "servers": reports,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(exit_code);
+    }
+
+    ui::table(["SERVER", "STATUS", "ERRORS", "WARNINGS"], &rows);
+    for (name, report) in &reports {
+        for issue in report["issues"].as_array().into_iter().flatten() {
+            let message = format!(
+                "{}: {}: {}",
+                name,
+                issue["code"].as_str().unwrap_or_default(),
+                issue["message"].as_str().unwrap_or_default()
+            );
+            match issue["severity"].as_str() {
+                Some("error") => ui::error(&message),
+                Some("warning") => ui::warning(&message),
+                _ => {}
+            }
+        }
+    }
+    Ok(exit_code)
+}
+
+/// The servers to run in, as (name, directory), in name order
+fn select_servers(names: &[String], all: bool) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let workspace = Workspace::load()?;
+    if workspace.servers.is_empty() {
+        anyhow::bail!(
+            "No servers in {}. Register one with 'mpm workspace add <name> <path>'.",
+            constants::WORKSPACE_FILE
+        );
+    }
+
+    for name in names {
+        if !workspace.servers.contains_key(name) {
+            anyhow::bail!(
+                "Server '{}' not found in {}",
+                name,
+                constants::WORKSPACE_FILE
+            );
+        }
+    }
+
+    Ok(workspace
+        .servers
+        .iter()
+        .filter(|(name, _)| all || names.contains(name))
+        .map(|(name, path)| (name.clone(), Workspace::server_dir(path)))
+        .collect())
+}
+
+/// Run an mpm command in each server, one after another or all at once
+///
+/// In parallel, each server's output is printed once it has finished so
+/// the servers' lines don't interleave.
+fn run_in_servers(
+    servers: &[(String, PathBuf)],
+    args: &[&str],
+    parallel: bool,
+) -> anyhow::Result<i32> {
+    // Exit codes:
+    // 0 = the command succeeded in every server
+    // otherwise the highest exit code any server returned
+
+    let command = args.join(" ");
+    let mut codes = Vec::new();
+
+    if parallel {
+        let outputs: Vec<std::io::Result<Output>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = servers
+                .iter()
+                .map(|(_, dir)| {
+                    scope.spawn(move || mpm_command(dir, args).and_then(|mut c| c.output()))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("server thread panicked"))
+                .collect()
+        });
+        for ((name, _), output) in servers.iter().zip(outputs) {
+            ui::action(&format!("{}: mpm {}", name, command));
+            let output = output?;
+            std::io::stdout().write_all(&output.stdout)?;
+            std::io::stderr().write_all(&output.stderr)?;
+            codes.push((name, output.status.code().unwrap_or(2)));
+        }
+    } else {
+        for (name, dir) in servers {
+            ui::action(&format!("{}: mpm {}", name, command));
+            let status = mpm_command(dir, args)?.status()?;
+            codes.push((name, status.code().unwrap_or(2)));
+        }
+    }
+
+    let failed: Vec<&str> = codes
+        .iter()
+        .filter(|(_, code)| *code != 0)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if failed.is_empty() {
+        ui::success(&format!(
+            "Ran mpm {} in {} server(s)",
+            command,
+            servers.len()
+        ));
+    } else {
+        ui::error(&format!(
+            "mpm {} failed in {} of {} server(s): {}",
+            command,
+            failed.len(),
+            servers.len(),
+            failed.join(", ")
+        ));
+    }
+    Ok(codes.iter().map(|(_, code)| *code).max().unwrap_or(0))
+}
+
+/// This mpm binary, set up to run in a server directory
+///
+/// The selected `--env` is passed on, so each server uses the same environment.
+/// `PM_PLUGINS_DIR` is not: every server resolves its own plugins directory,
+/// rather than all syncing into one and removing each other's JARs.
+fn mpm_command(dir: &Path, args: &[&str]) -> std::io::Result<Command> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command.env("PM_DIR", dir).env_remove("PM_PLUGINS_DIR");
+    if let Some(env) = config::env() {
+        command.args(["--env", env]);
+    }
+    command.args(args);
+    Ok(command)
+}
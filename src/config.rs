@@ -1,14 +1,48 @@
 // Config module for shared configuration utilities
 
 use crate::constants;
+use crate::manifest::Manifest;
+use crate::sources::REGISTRY;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Global config loaded once at startup
+static GLOBAL: OnceLock<GlobalConfig> = OnceLock::new();
+
+/// Environment selected with `--env`, set once at startup
+static ENV: OnceLock<Option<String>> = OnceLock::new();
 
 pub fn config_dir() -> String {
     std::env::var("PM_DIR").unwrap_or_else(|_| ".".to_string())
 }
 
+/// Plugins directory for the manifest in the config directory, if there is one
 pub fn plugins_dir() -> String {
-    std::env::var("PM_PLUGINS_DIR")
-        .unwrap_or_else(|_| format!("{}/{}", config_dir(), constants::PLUGINS_DIR))
+    match Manifest::load() {
+        Ok(manifest) => plugins_dir_from_manifest(&manifest),
+        Err(_) => resolve_plugins_dir(None),
+    }
+}
+
+/// Plugins directory for a manifest
+///
+/// `PM_PLUGINS_DIR` wins, then `[minecraft] plugins_dir` (relative to the
+/// manifest's directory), then `plugins/` next to the manifest.
+pub fn plugins_dir_from_manifest(manifest: &Manifest) -> String {
+    resolve_plugins_dir(manifest.minecraft.plugins_dir.as_deref())
+}
+
+fn resolve_plugins_dir(configured: Option<&str>) -> String {
+    if let Ok(dir) = std::env::var("PM_PLUGINS_DIR") {
+        return dir;
+    }
+    match configured {
+        Some(dir) if Path::new(dir).is_absolute() => dir.to_string(),
+        Some(dir) => format!("{}/{}", config_dir(), dir),
+        None => format!("{}/{}", config_dir(), constants::PLUGINS_DIR),
+    }
 }
 
 pub fn manifest_path() -> String {
@@ -20,11 +54,314 @@ pub fn manifest_path() -> String {
     }
 }
 
+/// Workspace manifest in the config directory
+pub fn workspace_path() -> String {
+    let dir = config_dir();
+    if dir == "." {
+        constants::WORKSPACE_FILE.to_string()
+    } else {
+        format!("{}/{}", dir, constants::WORKSPACE_FILE)
+    }
+}
+
+/// `plugins.lock`, or `plugins.<env>.lock` when an environment is selected
 pub fn lockfile_path() -> String {
+    let file = match env() {
+        Some(env) => format!("plugins.{}.lock", env),
+        None => constants::LOCKFILE_FILE.to_string(),
+    };
     let dir = config_dir();
     if dir == "." {
-        constants::LOCKFILE_FILE.to_string()
+        file
     } else {
-        format!("{}/{}", dir, constants::LOCKFILE_FILE)
+        format!("{}/{}", dir, file)
+    }
+}
+
+/// Select the environment (`--env`) for the rest of the run
+///
+/// The name becomes part of the lockfile name, so only letters, digits, `-`
+/// and `_` are accepted.
+pub fn set_env(env: Option<String>) -> anyhow::Result<()> {
+    if let Some(name) = &env
+        && (name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+    {
+        anyhow::bail!(
+            "Invalid environment name '{}': use only letters, digits, '-' and '_'",
+            name
+        );
+    }
+    ENV.set(env)
+        .map_err(|_| anyhow::anyhow!("Environment already selected"))
+}
+
+/// The environment selected with `--env`, if any
+pub fn env() -> Option<&'static str> {
+    ENV.get().and_then(|env| env.as_deref())
+}
+
+/// `plugins.sha256` beside the lockfile, written by `lock --save-checksums`
+pub fn checksums_path() -> String {
+    let lockfile = lockfile_path();
+    format!("{}.sha256", lockfile.trim_end_matches(".lock"))
+}
+
+/// Copy of the previous lockfile, kept by `lock` for `rollback`
+pub fn lockfile_backup_path() -> String {
+    format!("{}.bak", lockfile_path())
+}
+
+/// Directory holding the user-level configuration
+/// ($XDG_CONFIG_HOME/mpm, %APPDATA%\mpm on Windows, or ~/.config/mpm)
+pub fn global_config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME")
+        && !dir.is_empty()
+    {
+        return Some(PathBuf::from(dir).join("mpm"));
+    }
+
+    #[cfg(windows)]
+    if let Ok(dir) = std::env::var("APPDATA") {
+        return Some(PathBuf::from(dir).join("mpm"));
+    }
+
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config").join("mpm"))
+}
+
+/// Directory for cached API responses
+/// ($XDG_CACHE_HOME/mpm, %LOCALAPPDATA%\mpm\cache on Windows, or ~/.cache/mpm)
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_CACHE_HOME")
+        && !dir.is_empty()
+    {
+        return Some(PathBuf::from(dir).join("mpm"));
+    }
+
+    #[cfg(windows)]
+    if let Ok(dir) = std::env::var("LOCALAPPDATA") {
+        return Some(PathBuf::from(dir).join("mpm").join("cache"));
+    }
+
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".cache").join("mpm"))
+}
+
+pub fn global_config_path() -> Option<PathBuf> {
+    global_config_dir().map(|dir| dir.join(constants::GLOBAL_CONFIG_FILE))
+}
+
+/// Per-run HTTP settings from the command line, taking precedence over the config file
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HttpOverrides {
+    pub timeout_secs: Option<u64>,
+    pub max_retries: Option<u32>,
+}
+
+/// Load the global config and keep it for the rest of the run
+///
+/// Called once from `main`; everything else reads it through [`global`].
+pub fn load_global(overrides: HttpOverrides) -> anyhow::Result<&'static GlobalConfig> {
+    if let Some(config) = GLOBAL.get() {
+        return Ok(config);
+    }
+    let mut config = GlobalConfig::load()?;
+    if let Some(secs) = overrides.timeout_secs {
+        config.http_timeout_secs = secs;
+    }
+    if let Some(retries) = overrides.max_retries {
+        config.http.max_retries = retries;
+    }
+    Ok(GLOBAL.get_or_init(|| config))
+}
+
+/// The global config loaded at startup
+pub fn global() -> &'static GlobalConfig {
+    GLOBAL.get_or_init(|| GlobalConfig::load().unwrap_or_default())
+}
+
+/// User-level configuration shared by every project
+///
+/// Values here are fallbacks: CLI flags and project files take precedence.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GlobalConfig {
+    /// Minecraft version used by `init` and `import` when none is given or detected
+    pub default_mc_version: Option<String>,
+    /// Timeout for each HTTP request in seconds (0 disables the timeout)
+    pub http_timeout_secs: u64,
+    /// Maximum number of plugins downloaded at once during sync
+    pub download_concurrency: usize,
+    /// GitHub token, used when `GITHUB_TOKEN` is not set
+    pub github_token: Option<String>,
+    /// Hangar API key, used when `HANGAR_API_KEY` is not set
+    pub hangar_api_key: Option<String>,
+    /// Sources to try, in order, when a plugin is given without a source
+    pub source_priority: Option<Vec<String>>,
+    /// Let GitHub plugins resolve to pre-releases (overridden per plugin by `prerelease`)
+    pub include_prereleases: bool,
+    pub http: HttpConfig,
+    pub cache: CacheConfig,
+    pub security: SecurityConfig,
+    pub sync: SyncConfig,
+    pub lock: LockConfig,
+}
+
+impl GlobalConfig {
+    /// Time one source gets to look up a plugin in `add`, `import`, `info` and `outdated`
+    ///
+    /// Long enough for a request and all of its retries to use their full
+    /// timeout, or 3 minutes when requests have no timeout.
+    pub fn source_timeout(&self) -> Duration {
+        match self.http_timeout_secs {
+            0 => Duration::from_secs(constants::DEFAULT_SOURCE_TIMEOUT_SECS),
+            secs => Duration::from_secs(secs.saturating_mul(u64::from(self.http.max_retries) + 1)),
+        }
+    }
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            default_mc_version: None,
+            http_timeout_secs: constants::DEFAULT_HTTP_TIMEOUT_SECS,
+            download_concurrency: constants::DEFAULT_DOWNLOAD_CONCURRENCY,
+            github_token: None,
+            hangar_api_key: None,
+            source_priority: None,
+            include_prereleases: false,
+            http: HttpConfig::default(),
+            cache: CacheConfig::default(),
+            security: SecurityConfig::default(),
+            sync: SyncConfig::default(),
+            lock: LockConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// Times a request is retried after a transient failure (0 disables retries)
+    pub max_retries: u32,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: constants::DEFAULT_HTTP_MAX_RETRIES,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Reuse recent API responses from the cache directory
+    pub enabled: bool,
+    /// How long responses are kept, overriding each source's default (0 disables caching)
+    pub ttl_secs: Option<u64>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct SecurityConfig {
+    /// File listing plugins that may not be added (one `id` or `source:id` per line)
+    pub deny_list: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Maximum number of plugins downloaded at once (overrides `download_concurrency`)
+    pub concurrency: Option<usize>,
+    /// Leave JARs that aren't in the lockfile in place (as if `--keep-unmanaged` were passed)
+    pub keep_unmanaged: bool,
+    /// Shell command run after a sync changes the plugins directory
+    pub post_command: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct LockConfig {
+    /// Maximum number of plugins resolved at once
+    pub concurrency: Option<usize>,
+    /// Days before `mpm outdated` warns that the lockfile is stale (0 disables the warning)
+    pub warn_stale_days: Option<u64>,
+}
+
+impl GlobalConfig {
+    /// Load the global config, falling back to defaults when no file exists
+    pub fn load() -> anyhow::Result<Self> {
+        let Some(path) = global_config_path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(&path)?;
+        Self::parse(&text)
+            .map_err(|e| anyhow::anyhow!("Invalid global config '{}': {}", path.display(), e))
+    }
+
+    /// Parse and validate the contents of a global config file
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let config: Self = toml::from_str(text)?;
+        for source in config.source_priority.iter().flatten() {
+            REGISTRY
+                .get_or_error(source)
+                .map_err(|e| anyhow::anyhow!("source_priority: {}", e))?;
+        }
+        Ok(config)
+    }
+
+    /// Minecraft version to fall back on when none is given or detected
+    pub fn default_minecraft_version(&self) -> &str {
+        self.default_mc_version
+            .as_deref()
+            .unwrap_or(constants::DEFAULT_MC_VERSION)
+    }
+
+    /// Concurrency for sync downloads when no `--concurrency` flag is given
+    pub fn sync_concurrency(&self) -> usize {
+        self.sync.concurrency.unwrap_or(self.download_concurrency)
+    }
+
+    /// Concurrency for lock resolution when no `--concurrency` flag is given
+    pub fn lock_concurrency(&self) -> usize {
+        self.lock
+            .concurrency
+            .unwrap_or(constants::DEFAULT_LOCK_CONCURRENCY)
+    }
+
+    /// Lockfile age in days that `mpm outdated` warns about
+    pub fn lock_warn_stale_days(&self) -> u64 {
+        self.lock
+            .warn_stale_days
+            .unwrap_or(constants::DEFAULT_LOCK_WARN_STALE_DAYS)
+    }
+
+    /// Path to the deny-list file, resolved relative to the config directory
+    pub fn deny_list_path(&self) -> Option<PathBuf> {
+        let deny_list = self.security.deny_list.as_ref()?;
+        let path = Path::new(deny_list);
+        if path.is_absolute() {
+            Some(path.to_path_buf())
+        } else {
+            global_config_dir().map(|dir| dir.join(path))
+        }
     }
 }
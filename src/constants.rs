@@ -2,9 +2,28 @@
 
 pub const MANIFEST_FILE: &str = "plugins.toml";
 pub const LOCKFILE_FILE: &str = "plugins.lock";
+/// Lists the servers `mpm workspace` manages together
+pub const WORKSPACE_FILE: &str = "mpm-workspace.toml";
 pub const PLUGINS_DIR: &str = "plugins";
+pub const SERVER_PROPERTIES_FILE: &str = "server.properties";
+pub const GLOBAL_CONFIG_FILE: &str = "config.toml";
 pub const DEFAULT_MC_VERSION: &str = "1.21.11";
+/// Loaders resolved when neither the plugin nor the manifest names any (a Paper server)
+pub const DEFAULT_LOADERS: &[&str] = &["paper", "spigot", "bukkit"];
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 4;
+/// Plugins resolved at once by `mpm lock`
+pub const DEFAULT_LOCK_CONCURRENCY: usize = 4;
+/// Lockfile age after which `mpm outdated` warns that it's stale
+pub const DEFAULT_LOCK_WARN_STALE_DAYS: u64 = 30;
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 120;
+pub const DEFAULT_HTTP_MAX_RETRIES: u32 = 3;
+/// Time a source gets to look up a plugin when requests have no timeout
+pub const DEFAULT_SOURCE_TIMEOUT_SECS: u64 = 180;
 
 /// Schema version for the doctor --json output format.
 /// Increment only on breaking changes to ensure future integrations can safely evolve.
 pub const SCHEMA_VERSION: u32 = 1;
+
+/// Schema version written to plugins.lock.
+/// Bump it together with a new migration in `lockfile::MIGRATIONS`.
+pub const LOCKFILE_SCHEMA_VERSION: u32 = 1;
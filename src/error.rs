@@ -0,0 +1,212 @@
+// Structured errors with machine-readable codes for --json-errors
+
+use serde_json::json;
+use std::fmt;
+
+/// Errors that scripts may want to tell apart without parsing messages
+///
+/// Commands still return `anyhow::Result`; these are raised with `?` or
+/// `anyhow::Error::new` and recovered from the error chain when reporting.
+#[derive(Debug)]
+pub enum MpmError {
+    ManifestNotFound,
+    LockfileNotFound,
+    /// `source` is `None` when every source was tried
+    PluginNotFound {
+        source: Option<String>,
+        id: String,
+    },
+    /// `version` is `None` when no version at all supports the Minecraft version
+    VersionNotCompatible {
+        plugin: String,
+        version: Option<String>,
+        mc_version: String,
+        supported: Vec<String>,
+    },
+    HashMismatch {
+        plugin: String,
+        expected: String,
+        actual: String,
+    },
+    NetworkError {
+        url: String,
+        status: u16,
+    },
+    SourceNotSupported {
+        source: String,
+        supported: Vec<String>,
+    },
+}
+
+impl MpmError {
+    /// Stable identifier used in `--json-errors` output
+    pub fn code(&self) -> &'static str {
+        match self {
+            MpmError::ManifestNotFound => "MANIFEST_NOT_FOUND",
+            MpmError::LockfileNotFound => "LOCKFILE_NOT_FOUND",
+            MpmError::PluginNotFound { .. } => "PLUGIN_NOT_FOUND",
+            MpmError::VersionNotCompatible { .. } => "VERSION_NOT_COMPATIBLE",
+            MpmError::HashMismatch { .. } => "HASH_MISMATCH",
+            MpmError::NetworkError { .. } => "NETWORK_ERROR",
+            MpmError::SourceNotSupported { .. } => "SOURCE_NOT_SUPPORTED",
+        }
+    }
+
+    /// Variant fields as a JSON object
+    pub fn context(&self) -> serde_json::Value {
+        match self {
+            MpmError::ManifestNotFound | MpmError::LockfileNotFound => json!({}),
+            MpmError::PluginNotFound { source, id } => json!({ "source": source, "id": id }),
+            MpmError::VersionNotCompatible {
+                plugin,
+                version,
+                mc_version,
+                supported,
+            } => json!({
+                "plugin": plugin,
+                "version": version,
+                "mc_version": mc_version,
+                "supported": supported,
+            }),
+            MpmError::HashMismatch {
+                plugin,
+                expected,
+                actual,
+            } => json!({ "plugin": plugin, "expected": expected, "actual": actual }),
+            MpmError::NetworkError { url, status } => json!({ "url": url, "status": status }),
+            MpmError::SourceNotSupported { source, supported } => {
+                json!({ "source": source, "supported": supported })
+            }
+        }
+    }
+}
+
+impl fmt::Display for MpmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MpmError::ManifestNotFound => write!(f, "Manifest not found. Run 'mpm init' first."),
+            MpmError::LockfileNotFound => write!(f, "Lockfile not found. Run 'mpm lock' first."),
+            MpmError::PluginNotFound { source: None, id } => {
+                write!(f, "Plugin '{}' not found in any source.", id)
+            }
+            MpmError::PluginNotFound {
+                source: Some(source),
+                id,
+            } => write!(f, "Plugin '{}' not found in {}", id, source_label(source)),
+            MpmError::VersionNotCompatible {
+                plugin,
+                version,
+                mc_version,
+                supported,
+            } => {
+                let supported = if supported.is_empty() {
+                    "unknown".to_string()
+                } else {
+                    supported.join(", ")
+                };
+                match version {
+                    Some(version) => write!(
+                        f,
+                        "Plugin '{}' version '{}' is not compatible with Minecraft {}. Compatible versions: {}",
+                        plugin, version, mc_version, supported
+                    ),
+                    None => write!(
+                        f,
+                        "No versions of plugin '{}' are compatible with Minecraft {}. Latest version supports: {}",
+                        plugin, mc_version, supported
+                    ),
+                }
+            }
+            MpmError::HashMismatch {
+                plugin,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Hash mismatch for {}: expected {}, got {}",
+                plugin, expected, actual
+            ),
+            MpmError::NetworkError { url, status: 404 } => {
+                write!(f, "Resource not found: {}", url)
+            }
+            MpmError::NetworkError { url, status } => {
+                let status = reqwest::StatusCode::from_u16(*status)
+                    .map_or_else(|_| status.to_string(), |s| s.to_string());
+                write!(f, "HTTP request failed: {} ({})", url, status)
+            }
+            MpmError::SourceNotSupported { source, supported } => write!(
+                f,
+                "Unsupported source: '{}'. Supported sources: {}",
+                source,
+                supported.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MpmError {}
+
+/// Display name used in not-found messages ("Modrinth", "Hangar", ...)
+fn source_label(source: &str) -> String {
+    let mut chars = source.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Print an error as a single JSON object on stderr
+///
+/// The code comes from the first `MpmError` in the chain; anything else is
+/// reported as `ERROR` with an empty context. The message is always the
+/// outermost one, matching what the plain-text output shows.
+#[allow(clippy::print_stderr)]
+pub fn print_json(err: &anyhow::Error) {
+    let structured = err.chain().find_map(|e| e.downcast_ref::<MpmError>());
+    let output = json!({
+        "error_code": structured.map_or("ERROR", MpmError::code),
+        "message": err.to_string(),
+        "context": structured.map_or_else(|| json!({}), MpmError::context),
+    });
+    eprintln!("{}", output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_match_plain_errors() {
+        assert_eq!(
+            MpmError::PluginNotFound {
+                source: Some("modrinth".to_string()),
+                id: "worldedit".to_string(),
+            }
+            .to_string(),
+            "Plugin 'worldedit' not found in Modrinth"
+        );
+        assert_eq!(
+            MpmError::NetworkError {
+                url: "https://x".to_string(),
+                status: 503,
+            }
+            .to_string(),
+            "HTTP request failed: https://x (503 Service Unavailable)"
+        );
+        assert_eq!(
+            MpmError::NetworkError {
+                url: "https://x".to_string(),
+                status: 404,
+            }
+            .to_string(),
+            "Resource not found: https://x"
+        );
+    }
+
+    #[test]
+    fn test_code_found_through_context() {
+        let err = anyhow::Error::new(MpmError::LockfileNotFound).context("outer");
+        let structured = err.chain().find_map(|e| e.downcast_ref::<MpmError>());
+        assert_eq!(structured.map(MpmError::code), Some("LOCKFILE_NOT_FOUND"));
+    }
+}
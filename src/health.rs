@@ -0,0 +1,137 @@
+// Health check server for reporting sync state to orchestrators
+//
+// Serves a minimal HTTP endpoint on the requested port:
+//   200 OK                  {"status":"idle"}     when no sync is running
+//   503 Service Unavailable {"status":"syncing"}  while a sync is in progress
+
+use log::debug;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// Running health check server
+pub struct HealthServer {
+    syncing: Arc<AtomicBool>,
+    local_addr: SocketAddr,
+    shutdown_tx: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl HealthServer {
+    /// Bind the health server on all interfaces and start serving in the background
+    pub async fn start(port: u16) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to bind health server on port {}: {}", port, e))?;
+        let local_addr = listener.local_addr()?;
+        debug!("Health server listening on {}", local_addr);
+
+        let syncing = Arc::new(AtomicBool::new(false));
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+
+        let state = Arc::clone(&syncing);
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            let state = Arc::clone(&state);
+                            tokio::spawn(async move {
+                                if let Err(e) = respond(stream, state.load(Ordering::SeqCst)).await {
+                                    debug!("Health check connection failed: {}", e);
+                                }
+                            });
+                        }
+                        Err(e) => debug!("Health server accept failed: {}", e),
+                    },
+                }
+            }
+        });
+
+        Ok(Self {
+            syncing,
+            local_addr,
+            shutdown_tx,
+            handle,
+        })
+    }
+
+    /// Address the server is bound to
+    #[allow(dead_code)]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Mark whether a sync is currently in progress
+    pub fn set_syncing(&self, syncing: bool) {
+        self.syncing.store(syncing, Ordering::SeqCst);
+    }
+
+    /// Stop accepting connections and wait for the server task to exit
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(());
+        let _ = self.handle.await;
+        debug!("Health server on {} stopped", self.local_addr);
+    }
+}
+
+/// Read the request and write a fixed JSON response based on the sync state
+async fn respond(mut stream: TcpStream, syncing: bool) -> std::io::Result<()> {
+    // The request content doesn't matter; read what's available so the client
+    // isn't reset before it has finished sending
+    let mut buf = [0u8; 1024];
+    let _ = stream.read(&mut buf).await?;
+
+    let (status_line, body) = if syncing {
+        ("503 Service Unavailable", r#"{"status":"syncing"}"#)
+    } else {
+        ("200 OK", r#"{"status":"idle"}"#)
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status_line,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(addr: SocketAddr) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_idle_and_syncing() {
+        let server = HealthServer::start(0).await.unwrap();
+        let addr = SocketAddr::from(([127, 0, 0, 1], server.local_addr().port()));
+
+        let response = get(addr).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(r#"{"status":"idle"}"#));
+
+        server.set_syncing(true);
+        let response = get(addr).await;
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable"));
+        assert!(response.ends_with(r#"{"status":"syncing"}"#));
+
+        server.shutdown().await;
+    }
+}
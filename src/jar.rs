@@ -0,0 +1,126 @@
+// JAR module for inspecting plugin JAR files
+
+use crate::manifest::ServerType;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PluginYml {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VelocityPluginJson {
+    id: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+}
+
+/// Plugin descriptors in the order they are tried for a server type
+///
+/// Proxies look for their own descriptor first, since some JARs bundle a
+/// plugin.yml for Bukkit alongside it.
+fn descriptor_names(server_type: Option<ServerType>) -> &'static [&'static str] {
+    match server_type {
+        Some(ServerType::Velocity) => &["velocity-plugin.json", "plugin.yml", "bungee.yml"],
+        Some(ServerType::Bungeecord | ServerType::Waterfall) => {
+            &["bungee.yml", "plugin.yml", "velocity-plugin.json"]
+        }
+        _ => &["plugin.yml", "bungee.yml", "velocity-plugin.json"],
+    }
+}
+
+/// Read the plugin name and version from a JAR's plugin.yml (or bungee.yml,
+/// or velocity-plugin.json)
+pub fn read_plugin_yml_from_jar(jar_path: &Path) -> anyhow::Result<(String, Option<String>)> {
+    read_plugin_descriptor(jar_path, None)
+}
+
+/// Read the plugin name and version, preferring the descriptor of the server type
+pub fn read_plugin_descriptor(
+    jar_path: &Path,
+    server_type: Option<ServerType>,
+) -> anyhow::Result<(String, Option<String>)> {
+    // Open JAR file as ZIP archive
+    let file = fs::File::open(jar_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    // Use the first descriptor present in the root of the JAR
+    let names = descriptor_names(server_type);
+    let descriptor = names
+        .iter()
+        .copied()
+        .find(|name| archive.by_name(name).is_ok())
+        .ok_or_else(|| anyhow::anyhow!("JAR has no {}", names.join(", ")))?;
+
+    // Read the contents
+    let mut contents = String::new();
+    archive.by_name(descriptor)?.read_to_string(&mut contents)?;
+
+    // Velocity names plugins by id, with an optional display name
+    let plugin_data = if descriptor.ends_with(".json") {
+        let velocity: VelocityPluginJson = serde_json::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", descriptor, e))?;
+        PluginYml {
+            name: velocity.name.or(velocity.id),
+            version: velocity.version,
+        }
+    } else {
+        serde_yaml::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", descriptor, e))?
+    };
+
+    let name = plugin_data
+        .name
+        .ok_or_else(|| anyhow::anyhow!("{} missing 'name' field", descriptor))?;
+
+    let version = plugin_data.version;
+
+    Ok((name, version))
+}
+
+/// Compute the SHA-256 of a file in lockfile format (`sha256:<hex>`)
+pub fn compute_sha256(file_path: &Path) -> anyhow::Result<String> {
+    let data = fs::read(file_path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&data);
+    let hash_hex = hex::encode(hasher.finalize());
+    Ok(format!("sha256:{}", hash_hex))
+}
+
+/// Find the file in a ZIP archive whose name (without its directory) matches
+/// `pattern` in full, returning its path within the archive and its contents
+pub fn extract_matching(data: &[u8], pattern: &Regex) -> anyhow::Result<(String, Vec<u8>)> {
+    let archive = zip::ZipArchive::new(Cursor::new(data))?;
+    let entry = archive
+        .file_names()
+        .filter(|path| !path.ends_with('/'))
+        .find(|path| pattern.is_match(path.rsplit('/').next().unwrap_or(path)))
+        .map(str::to_string);
+    let Some(entry) = entry else {
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        anyhow::bail!(
+            "No file in the archive matches the unzip pattern. Archive contents: {}",
+            names.join(", ")
+        );
+    };
+    let contents = extract_entry(data, &entry)?;
+    Ok((entry, contents))
+}
+
+/// Read one file out of a ZIP archive by its path within the archive
+pub fn extract_entry(data: &[u8], entry: &str) -> anyhow::Result<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+    let mut file = archive
+        .by_name(entry)
+        .map_err(|_| anyhow::anyhow!("'{}' not found in the archive", entry))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
@@ -1,41 +1,110 @@
 // Lockfile module for handling dependency lock files
 
 use crate::config;
+use crate::constants::LOCKFILE_SCHEMA_VERSION;
+use crate::error::MpmError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Lockfile {
+    /// Missing in lockfiles written before the schema was versioned (v0)
+    #[serde(default)]
+    pub schema_version: u32,
+    /// When `mpm lock` last changed the lockfile (RFC 3339, UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_at: Option<String>,
     pub plugin: Vec<LockedPlugin>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LockedPlugin {
     pub name: String,
     pub source: String,
     pub version: String,
     pub file: String,
     pub url: String,
+    /// Fallback URLs for the same file, tried in order when `url` fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urls: Option<Vec<String>>,
     pub hash: String,
+    /// Missing for lockfiles written before sizes were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// The source's canonical project identifier, which can differ from the
+    /// manifest `id` (e.g. a Modrinth project ID for a slug)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_id: Option<String>,
+    /// Path of the JAR inside the downloaded ZIP, for plugins shipped zipped
+    /// (`file` and `hash` then describe the extracted JAR)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zip_entry: Option<String>,
 }
 
 impl Lockfile {
     pub fn load() -> anyhow::Result<Self> {
         let path = config::lockfile_path();
         let text = std::fs::read_to_string(&path)?;
+
+        let schema_version = read_schema_version(&text)?;
+        if schema_version > LOCKFILE_SCHEMA_VERSION {
+            anyhow::bail!(
+                "{} uses schema version {}, but this mpm only understands up to version {}. \
+                Upgrade mpm to read it.",
+                path,
+                schema_version,
+                LOCKFILE_SCHEMA_VERSION
+            );
+        }
+        if schema_version < LOCKFILE_SCHEMA_VERSION {
+            let migrated = migrate(schema_version, &text)?;
+            return Ok(toml::from_str(&migrated)?);
+        }
+
         Ok(toml::from_str(&text)?)
     }
 
+    /// Load the lockfile, pointing at `mpm lock` when there isn't one yet
+    ///
+    /// Unlike a plain `load()` error, parse and schema errors are kept intact.
+    pub fn load_existing() -> anyhow::Result<Self> {
+        if !std::path::Path::new(&config::lockfile_path()).exists() {
+            return Err(MpmError::LockfileNotFound.into());
+        }
+        Self::load()
+    }
+
+    /// Write the lockfile atomically (via a temporary file and a rename)
     pub fn save(&self) -> anyhow::Result<()> {
         let dir = config::config_dir();
         std::fs::create_dir_all(&dir)?;
         let path = config::lockfile_path();
         let text = toml::to_string_pretty(self)?;
-        std::fs::write(&path, text)?;
+        let temp_path = format!("{}.new", path);
+        std::fs::write(&temp_path, text)?;
+        std::fs::rename(&temp_path, &path)?;
         Ok(())
     }
 
+    /// Save, first copying the current lockfile to `plugins.lock.bak` if it changes
+    ///
+    /// Only one backup is kept; it is what `mpm rollback` restores.
+    pub fn save_with_backup(&self) -> anyhow::Result<()> {
+        let path = config::lockfile_path();
+        if let Ok(existing) = std::fs::read_to_string(&path) {
+            if existing == toml::to_string_pretty(self)? {
+                return Ok(());
+            }
+            std::fs::write(config::lockfile_backup_path(), existing)?;
+        }
+        self.save()
+    }
+
     pub fn new() -> Self {
-        Self { plugin: Vec::new() }
+        Self {
+            schema_version: LOCKFILE_SCHEMA_VERSION,
+            locked_at: None,
+            plugin: Vec::new(),
+        }
     }
 
     pub fn add_plugin(&mut self, plugin: LockedPlugin) {
@@ -45,6 +114,76 @@ impl Lockfile {
     pub fn sort_by_name(&mut self) {
         self.plugin.sort_by(|a, b| a.name.cmp(&b.name));
     }
+
+    /// Days since `locked_at`, or `None` when it's missing or unreadable
+    pub fn age_days(&self) -> Option<i64> {
+        let locked_at = chrono::DateTime::parse_from_rfc3339(self.locked_at.as_deref()?).ok()?;
+        Some((chrono::Utc::now() - locked_at.with_timezone(&chrono::Utc)).num_days())
+    }
+}
+
+/// A migration rewrites a lockfile table from one schema version to the next
+type Migration = fn(&mut toml::Table) -> anyhow::Result<()>;
+
+/// Registered migrations; `MIGRATIONS[n]` upgrades schema version n to n + 1
+const MIGRATIONS: [Migration; LOCKFILE_SCHEMA_VERSION as usize] = [migrate_v0_to_v1];
+
+fn read_schema_version(text: &str) -> anyhow::Result<u32> {
+    let table: toml::Table = toml::from_str(text)?;
+    match table.get("schema_version") {
+        None => Ok(0),
+        Some(value) => value
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid schema_version in lockfile: {}", value)),
+    }
+}
+
+/// Upgrade raw lockfile TOML from `old_version` to the current schema version
+///
+/// Migrations are applied in sequence, so a v0 lockfile passes through every
+/// one of them.
+pub fn migrate(old_version: u32, raw_toml: &str) -> anyhow::Result<String> {
+    let mut table: toml::Table = toml::from_str(raw_toml)?;
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(old_version as usize) {
+        migration(&mut table).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to migrate lockfile from schema version {} to {}: {}",
+                version,
+                version + 1,
+                e
+            )
+        })?;
+    }
+    table.insert(
+        "schema_version".to_string(),
+        toml::Value::Integer(LOCKFILE_SCHEMA_VERSION.into()),
+    );
+    Ok(toml::to_string_pretty(&table)?)
+}
+
+/// v0 -> v1: prefix bare hex hashes with their algorithm
+///
+/// The algorithm is inferred from the digest length (128 hex characters for
+/// SHA-512, otherwise SHA-256).
+fn migrate_v0_to_v1(table: &mut toml::Table) -> anyhow::Result<()> {
+    let Some(plugins) = table.get_mut("plugin").and_then(|p| p.as_array_mut()) else {
+        return Ok(());
+    };
+    for plugin in plugins {
+        let Some(hash) = plugin.get_mut("hash") else {
+            continue;
+        };
+        let Some(hex) = hash.as_str().filter(|h| !h.contains(':')) else {
+            continue;
+        };
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("hash '{}' is neither 'algorithm:hash' nor hex", hex);
+        }
+        let algorithm = if hex.len() == 128 { "sha512" } else { "sha256" };
+        *hash = toml::Value::String(format!("{}:{}", algorithm, hex));
+    }
+    Ok(())
 }
 
 impl LockedPlugin {
@@ -61,4 +200,114 @@ impl LockedPlugin {
         }
         Ok((parts[0], parts[1]))
     }
+
+    /// The primary URL followed by any fallbacks
+    pub fn download_urls(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.url).chain(self.urls.iter().flatten())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256_HEX: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_migrate_v0_to_v1_prefixes_bare_hashes() {
+        let sha512_hex = "ab".repeat(64);
+        let mut table: toml::Table = toml::from_str(&format!(
+            r#"
+[[plugin]]
+name = "a"
+hash = "{}"
+
+[[plugin]]
+name = "b"
+hash = "{}"
+
+[[plugin]]
+name = "c"
+hash = "sha1:abc"
+"#,
+            SHA256_HEX, sha512_hex
+        ))
+        .unwrap();
+
+        migrate_v0_to_v1(&mut table).unwrap();
+
+        let hashes: Vec<&str> = table["plugin"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["hash"].as_str().unwrap())
+            .collect();
+        assert_eq!(hashes[0], format!("sha256:{}", SHA256_HEX));
+        assert_eq!(hashes[1], format!("sha512:{}", sha512_hex));
+        assert_eq!(hashes[2], "sha1:abc");
+    }
+
+    #[test]
+    fn test_migrate_v0_to_v1_rejects_garbage_hash() {
+        let mut table: toml::Table =
+            toml::from_str("[[plugin]]\nname = \"a\"\nhash = \"not-a-hash\"\n").unwrap();
+        assert!(migrate_v0_to_v1(&mut table).is_err());
+    }
+
+    #[test]
+    fn test_migrate_sets_schema_version() {
+        let raw = format!(
+            r#"
+[[plugin]]
+name = "a"
+source = "modrinth"
+version = "1.0.0"
+file = "a.jar"
+url = "https://example.com/a.jar"
+hash = "{}"
+"#,
+            SHA256_HEX
+        );
+
+        let migrated = migrate(0, &raw).unwrap();
+        let lockfile: Lockfile = toml::from_str(&migrated).unwrap();
+        assert_eq!(lockfile.schema_version, LOCKFILE_SCHEMA_VERSION);
+        assert_eq!(lockfile.plugin[0].hash, format!("sha256:{}", SHA256_HEX));
+        assert_eq!(
+            read_schema_version(&migrated).unwrap(),
+            LOCKFILE_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_read_schema_version_defaults_to_zero() {
+        assert_eq!(read_schema_version("plugin = []").unwrap(), 0);
+        assert_eq!(
+            read_schema_version("schema_version = 1\nplugin = []").unwrap(),
+            1
+        );
+        assert!(read_schema_version("schema_version = -1\nplugin = []").is_err());
+    }
+
+    #[test]
+    fn test_upstream_id_is_optional() {
+        let raw = r#"
+schema_version = 1
+
+[[plugin]]
+name = "worldedit"
+source = "modrinth"
+version = "7.3.0"
+file = "worldedit.jar"
+url = "https://example.com/worldedit.jar"
+hash = "sha512:abc123"
+"#;
+        let mut lockfile: Lockfile = toml::from_str(raw).unwrap();
+        assert_eq!(lockfile.plugin[0].upstream_id, None);
+        assert!(!toml::to_string(&lockfile).unwrap().contains("upstream_id"));
+
+        lockfile.plugin[0].upstream_id = Some("1u6JkXh5".to_string());
+        let text = toml::to_string(&lockfile).unwrap();
+        assert!(text.contains("upstream_id = \"1u6JkXh5\""));
+    }
 }
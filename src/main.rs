@@ -2,10 +2,17 @@ mod cli;
 mod commands;
 mod config;
 mod constants;
+mod error;
+#[cfg(feature = "health-server")]
+mod health;
+mod jar;
 mod lockfile;
 mod manifest;
+#[cfg(feature = "sbom")]
+mod sbom;
 mod sources;
 mod ui;
+mod workspace;
 
 use clap::Parser;
 use cli::Cli;
@@ -16,61 +23,455 @@ use log::{LevelFilter, error};
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logger based on debug flag
-    // If RUST_LOG is set, it takes precedence; otherwise use --debug flag
+    if let Some(path) = &cli.log {
+        let command: Vec<String> = std::env::args().skip(1).collect();
+        if let Err(e) = ui::set_log_file(path, &command.join(" ")) {
+            report_error(
+                &anyhow::anyhow!("Could not open log file '{}': {}", path, e),
+                cli.json_errors,
+            );
+            exit(2);
+        }
+    }
+
+    // Initialize logger based on the verbosity flags
+    // -v (or --debug) shows debug logs and -vv adds mpm's trace logs, such as
+    // HTTP headers; otherwise RUST_LOG takes precedence over the INFO default
+    let verbosity = cli.verbose.max(u8::from(cli.debug));
     let mut builder = Builder::from_default_env();
-    if std::env::var("RUST_LOG").is_err() {
-        // Only set default level if RUST_LOG is not explicitly set
+    match verbosity {
+        0 if std::env::var("RUST_LOG").is_ok() => {}
         // Default to INFO so user-facing messages are visible
-        // Use DEBUG when --debug flag is set for detailed diagnostics
-        builder.filter_level(if cli.debug {
-            LevelFilter::Debug
-        } else {
-            LevelFilter::Info
-        });
+        0 => {
+            builder.filter_level(LevelFilter::Info);
+        }
+        1 => {
+            builder.filter_level(LevelFilter::Debug);
+        }
+        _ => {
+            builder
+                .filter_level(LevelFilter::Debug)
+                .filter_module(env!("CARGO_CRATE_NAME"), LevelFilter::Trace);
+        }
     }
     builder.init();
+    ui::set_verbose(verbosity > 0);
+
+    let json_errors = cli.json_errors;
 
-    match cli.command {
-        Some(cli::Commands::Init { version }) => {
-            commands::init::init(version)?;
+    // Load the user-level config once; commands read it through config::global()
+    let http_overrides = config::HttpOverrides {
+        timeout_secs: cli.timeout,
+        max_retries: cli.retries,
+    };
+    if let Err(e) = config::load_global(http_overrides) {
+        report_error(&e, json_errors);
+        exit(2);
+    }
+
+    if let Err(e) = config::set_env(cli.env.clone()) {
+        report_error(&e, json_errors);
+        exit(2);
+    }
+
+    // Build the HTTP client up front so a malformed proxy URL stops us here
+    sources::http::client();
+
+    match run(cli.command, json_errors, verbosity > 0).await {
+        Err(e) if json_errors => {
+            ui::log("error", &format!("{:#}", e));
+            error::print_json(&e);
+            exit(1);
+        }
+        Err(e) => {
+            ui::log("error", &format!("{:#}", e));
+            ui::finish_log(1);
+            Err(e)
+        }
+        Ok(()) => {
+            ui::finish_log(0);
+            Ok(())
+        }
+    }
+}
+
+/// Exit with a code, first finishing the `--log` file
+fn exit(code: i32) -> ! {
+    ui::finish_log(code);
+    std::process::exit(code)
+}
+
+/// Log an error, or print it as JSON with --json-errors
+fn report_error(e: &anyhow::Error, json_errors: bool) {
+    ui::log("error", &format!("{:#}", e));
+    if json_errors {
+        error::print_json(e);
+    } else {
+        error!("{}", e);
+    }
+}
+
+async fn run(
+    command: Option<cli::Commands>,
+    json_errors: bool,
+    verbose: bool,
+) -> anyhow::Result<()> {
+    match command {
+        Some(cli::Commands::Init {
+            version,
+            server_type,
+            force,
+            yes,
+            plugins,
+        }) => {
+            commands::init::init(version, server_type, force, yes)?;
+            if !plugins.is_empty() {
+                commands::init::add_plugins(&plugins).await?;
+            }
         }
         Some(cli::Commands::Add {
-            spec,
+            specs,
             no_update,
             skip_compatibility,
+            upgrade,
+            note,
+            dry_run,
+            list_versions,
+            json,
         }) => {
-            commands::add::add(spec, no_update, skip_compatibility).await?;
+            ui::set_buffered(json);
+            let result = if list_versions {
+                commands::add::list_versions(&specs, skip_compatibility, json).await
+            } else {
+                commands::add::add(
+                    &specs,
+                    no_update,
+                    skip_compatibility,
+                    upgrade,
+                    json,
+                    note,
+                    dry_run,
+                )
+                .await
+            };
+            if json && let Err(e) = &result {
+                commands::report::print_error(e);
+                exit(1);
+            }
+            result?;
+        }
+        Some(cli::Commands::Remove {
+            specs,
+            no_update,
+            purge,
+            json,
+        }) => {
+            ui::set_buffered(json);
+            let result = commands::remove::remove(&specs, no_update, purge, json).await;
+            if json && let Err(e) = &result {
+                commands::report::print_error(e);
+                exit(1);
+            }
+            result?;
+        }
+        Some(cli::Commands::Lock {
+            dry_run,
+            concurrency,
+            group,
+            update,
+            #[cfg(feature = "sbom")]
+            sbom,
+            save_checksums,
+            checksums_file,
+            json,
+            check,
+            fail_on_new,
+            fail_on_removed,
+        }) => {
+            ui::set_buffered(json);
+            let options = commands::lock::LockOptions {
+                dry_run,
+                concurrency,
+                group,
+                update,
+                json,
+                check,
+                verbose,
+                fail_on_new,
+                fail_on_removed,
+                checksums_path: save_checksums
+                    .then(|| checksums_file.unwrap_or_else(config::checksums_path)),
+                #[cfg(feature = "sbom")]
+                sbom_path: sbom,
+            };
+            match commands::lock::lock_with_options(&options).await {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) if json => {
+                    commands::report::print_error(&e);
+                    exit(2);
+                }
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Update { names, dry_run }) => {
+            match commands::update::update(names, dry_run).await {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Pin {
+            names,
+            all,
+            no_update,
+        }) => {
+            commands::pin::pin(names, all, no_update).await?;
         }
-        Some(cli::Commands::Remove { spec, no_update }) => {
-            commands::remove::remove(spec, no_update).await?;
+        Some(cli::Commands::Unpin {
+            names,
+            all,
+            no_update,
+        }) => {
+            commands::pin::unpin(names, all, no_update).await?;
+        }
+        Some(cli::Commands::Disable { names, no_update }) => {
+            commands::disable::disable(names, no_update).await?;
         }
-        Some(cli::Commands::Lock { dry_run }) => match commands::lock::lock(dry_run).await {
-            Ok(exit_code) => std::process::exit(exit_code),
+        Some(cli::Commands::Enable { names, no_update }) => {
+            commands::disable::enable(names, no_update).await?;
+        }
+        Some(cli::Commands::Outdated {
+            json,
+            fail_on_outdated,
+        }) => match commands::outdated::outdated(json, fail_on_outdated, verbose).await {
+            Ok(exit_code) => exit(exit_code),
             Err(e) => {
-                error!("{}", e);
-                std::process::exit(2);
+                report_error(&e, json_errors);
+                exit(2);
             }
         },
-        Some(cli::Commands::Sync { dry_run }) => {
-            match commands::sync::sync_plugins(dry_run).await {
-                Ok(exit_code) => std::process::exit(exit_code),
+        Some(cli::Commands::Upgrade {
+            names,
+            yes,
+            no_sync,
+        }) => match commands::upgrade::upgrade(names, yes, no_sync).await {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                report_error(&e, json_errors);
+                exit(2);
+            }
+        },
+        Some(cli::Commands::Sync {
+            dry_run,
+            concurrency,
+            group,
+            include,
+            exclude,
+            keep_unmanaged,
+            force,
+            offline,
+            json,
+            #[cfg(feature = "health-server")]
+            health_port,
+        }) => {
+            ui::set_buffered(json);
+            let options = commands::sync::SyncOptions {
+                dry_run,
+                concurrency,
+                group,
+                include,
+                exclude,
+                keep_unmanaged,
+                force,
+                offline,
+                json,
+            };
+            #[cfg(feature = "health-server")]
+            let result = match health_port {
+                Some(port) => commands::sync::sync_plugins_with_health(&options, port).await,
+                None => commands::sync::sync_plugins_with_options(&options).await,
+            };
+            #[cfg(not(feature = "health-server"))]
+            let result = commands::sync::sync_plugins_with_options(&options).await;
+
+            match result {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) if json => {
+                    commands::report::print_error(&e);
+                    exit(2);
+                }
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Diff { json }) => match commands::diff::diff(json) {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                report_error(&e, json_errors);
+                exit(2);
+            }
+        },
+        Some(cli::Commands::Export { format, output }) => {
+            commands::export::export(format, output)?;
+        }
+        Some(cli::Commands::Generate { what }) => match what {
+            cli::GenerateAction::Checksums { format, output } => {
+                commands::generate::checksums(format, output)?
+            }
+        },
+        Some(cli::Commands::Rollback { confirm }) => {
+            match commands::rollback::rollback(confirm).await {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Clean { cache, all }) => {
+            commands::clean::clean(cache, all)?;
+        }
+        Some(cli::Commands::Prune { dry_run, purge }) => {
+            match commands::prune::prune(dry_run, purge) {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Search {
+            query,
+            source,
+            limit,
+            json,
+        }) => {
+            commands::search::search(&query, source.as_deref(), limit, json).await?;
+        }
+        Some(cli::Commands::Info { spec, json }) => {
+            commands::info::info(spec, json).await?;
+        }
+        Some(cli::Commands::List { json }) => {
+            commands::list::list(json, verbose)?;
+        }
+        Some(cli::Commands::Status { count }) => match commands::status::status(count) {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                report_error(&e, json_errors);
+                exit(2);
+            }
+        },
+        Some(cli::Commands::Verify { quiet }) => match commands::verify::verify(quiet) {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                if !quiet {
+                    report_error(&e, json_errors);
+                }
+                exit(2);
+            }
+        },
+        Some(cli::Commands::Doctor {
+            json,
+            fix,
+            yes,
+            check_network,
+            check_auth,
+            plugin,
+        }) => {
+            match commands::doctor::check_health(json, fix, yes, check_network, check_auth, plugin)
+                .await
+            {
+                Ok(exit_code) => exit(exit_code),
                 Err(e) => {
-                    error!("{}", e);
-                    std::process::exit(2);
+                    report_error(&e, json_errors);
+                    exit(2);
                 }
             }
         }
-        Some(cli::Commands::Doctor { json }) => match commands::doctor::check_health(json) {
-            Ok(exit_code) => std::process::exit(exit_code),
+        Some(cli::Commands::Lint { fix }) => match commands::lint::lint(fix) {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                report_error(&e, json_errors);
+                exit(2);
+            }
+        },
+        Some(cli::Commands::Import {
+            version,
+            server_type,
+            from,
+            source,
+            dry_run,
+            overwrite,
+            yes,
+        }) => match commands::import::import_plugins(
+            version,
+            server_type,
+            from,
+            source.as_deref(),
+            dry_run,
+            overwrite,
+            yes,
+        )
+        .await
+        {
+            Ok(exit_code) => exit(exit_code),
             Err(e) => {
-                error!("{}", e);
-                std::process::exit(2);
+                report_error(&e, json_errors);
+                exit(2);
             }
         },
-        Some(cli::Commands::Import { version }) => {
-            commands::import::import_plugins(version).await?;
+        Some(cli::Commands::Config { action }) => match action {
+            cli::ConfigAction::Get { key } => commands::config::get(&key)?,
+            cli::ConfigAction::Set { key, value } => commands::config::set(&key, &value)?,
+            cli::ConfigAction::List => commands::config::list()?,
+        },
+        Some(cli::Commands::Workspace { action }) => {
+            let result = match action {
+                cli::WorkspaceAction::Add { name, path } => {
+                    commands::workspace::add(&name, &path).map(|()| 0)
+                }
+                cli::WorkspaceAction::List => commands::workspace::list().map(|()| 0),
+                cli::WorkspaceAction::Lock { servers, all } => {
+                    commands::workspace::lock(&servers, all)
+                }
+                cli::WorkspaceAction::Sync {
+                    servers,
+                    all,
+                    parallel,
+                } => commands::workspace::sync(&servers, all, parallel),
+                cli::WorkspaceAction::Doctor { json } => commands::workspace::doctor(json),
+            };
+            match result {
+                Ok(exit_code) => exit(exit_code),
+                Err(e) => {
+                    report_error(&e, json_errors);
+                    exit(2);
+                }
+            }
+        }
+        Some(cli::Commands::Completions { shell }) => {
+            commands::completions::completions(shell)?;
         }
+        Some(cli::Commands::Migrate {
+            from,
+            file,
+            version,
+            server_type,
+            dry_run,
+        }) => match commands::migrate::migrate(from, file, version, server_type, dry_run).await {
+            Ok(exit_code) => exit(exit_code),
+            Err(e) => {
+                report_error(&e, json_errors);
+                exit(2);
+            }
+        },
         None => {
             // This case should not be reached due to arg_required_else_help,
             // but handle it gracefully just in case
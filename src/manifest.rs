@@ -1,25 +1,139 @@
 // Manifest module for handling package manifests
 
 use crate::config;
+use crate::constants;
+use crate::sources::ResolveOptions;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Deref;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
     pub minecraft: MinecraftSpec,
     pub plugins: BTreeMap<String, PluginSpec>,
+    /// Named plugin sets for environment-specific syncs (e.g. lobby, survival)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<BTreeMap<String, Vec<String>>>,
+    /// Per-environment plugins (`[env.<name>.plugins]`), selected with `--env`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, EnvSpec>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Plugins added to (or replacing entries in) `[plugins]` for one environment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvSpec {
+    #[serde(default)]
+    pub plugins: BTreeMap<String, PluginSpec>,
+}
+
+/// A manifest with an environment's plugins merged into `[plugins]`
+///
+/// Only for reading; changes are made to (and saved from) the [`Manifest`]
+/// it was resolved from.
+#[derive(Debug)]
+pub struct ResolvedManifest(Manifest);
+
+impl Deref for ResolvedManifest {
+    type Target = Manifest;
+
+    fn deref(&self) -> &Manifest {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinecraftSpec {
     pub version: String,
+    /// Server software, which decides the loaders plugins are resolved for
+    #[serde(default, alias = "platform", skip_serializing_if = "Option::is_none")]
+    pub server_type: Option<ServerType>,
+    /// Plugins directory, relative to this manifest (default: `plugins`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugins_dir: Option<String>,
+    /// Shell command run after a sync changes the plugins directory, e.g. to
+    /// restart the server (overrides `sync.post_command` in the global config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_sync_command: Option<String>,
+}
+
+/// Server software the plugins run on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerType {
+    Paper,
+    Spigot,
+    Bukkit,
+    Fabric,
+    Forge,
+    Bungeecord,
+    Velocity,
+    Waterfall,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl ServerType {
+    /// Loaders whose builds run on this server software
+    pub fn loaders(self) -> &'static [&'static str] {
+        match self {
+            ServerType::Paper => constants::DEFAULT_LOADERS,
+            ServerType::Spigot => &["spigot", "bukkit"],
+            ServerType::Bukkit => &["bukkit"],
+            ServerType::Fabric => &["fabric"],
+            ServerType::Forge => &["forge"],
+            ServerType::Bungeecord => &["bungeecord"],
+            ServerType::Velocity => &["velocity"],
+            ServerType::Waterfall => &["waterfall", "bungeecord"],
+        }
+    }
+
+    /// Hangar platform to download for, if this is a proxy
+    ///
+    /// Hangar publishes BungeeCord plugins under `WATERFALL`.
+    pub fn hangar_platform(self) -> Option<&'static str> {
+        match self {
+            ServerType::Bungeecord | ServerType::Waterfall => Some("WATERFALL"),
+            ServerType::Velocity => Some("VELOCITY"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginSpec {
     pub source: String,
     pub id: String,
     pub version: Option<String>,
+    /// Loaders to resolve builds for, overriding the server type's defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loaders: Option<Vec<String>>,
+    /// Hangar platform to download for (`PAPER`, `WATERFALL` or `VELOCITY`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// Free-form annotation, e.g. why the plugin is pinned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Accept pre-releases, overriding `include_prereleases` in the global config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prerelease: Option<bool>,
+    /// Regex matching the whole filename of the GitHub release asset to download
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_pattern: Option<String>,
+    /// Least stable Hangar channel to accept (`RELEASE` when unset, `BETA` or `ALPHA`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Regex matching the whole filename of the JAR to extract, for GitHub
+    /// releases that ship the plugin inside a `.zip` asset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unzip: Option<String>,
+    /// Leave the plugin out of the lockfile and the plugins directory
+    /// without removing it from the manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+}
+
+impl PluginSpec {
+    pub fn is_disabled(&self) -> bool {
+        self.disabled == Some(true)
+    }
 }
 
 impl Manifest {
@@ -37,4 +151,172 @@ impl Manifest {
         std::fs::write(&path, text)?;
         Ok(())
     }
+
+    /// Loaders to resolve a plugin for: its own list, else the server type's
+    pub fn loaders_for(&self, spec: &PluginSpec) -> Vec<String> {
+        spec.loaders
+            .clone()
+            .unwrap_or_else(|| self.default_loaders())
+    }
+
+    /// Filters to resolve a plugin's versions with
+    pub fn resolve_options(&self, spec: &PluginSpec) -> ResolveOptions {
+        ResolveOptions {
+            loaders: self.loaders_for(spec),
+            platform: spec.platform.clone().or_else(|| self.default_platform()),
+            prerelease: spec
+                .prerelease
+                .unwrap_or(config::global().include_prereleases),
+            asset_pattern: spec.asset_pattern.clone(),
+            channel: spec.channel.clone(),
+            unzip: spec.unzip.clone(),
+        }
+    }
+
+    /// Loaders for the manifest's server type (paper when unset)
+    pub fn default_loaders(&self) -> Vec<String> {
+        self.minecraft
+            .server_type
+            .map_or(constants::DEFAULT_LOADERS, ServerType::loaders)
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    /// Hangar platform for the manifest's server type (none unless it is a proxy)
+    pub fn default_platform(&self) -> Option<String> {
+        self.minecraft
+            .server_type
+            .and_then(ServerType::hangar_platform)
+            .map(str::to_string)
+    }
+
+    /// Plugins to process for a group: its members plus every plugin not in any group
+    ///
+    /// With no group, all plugins are returned.
+    pub fn plugins_for_group(
+        &self,
+        group: Option<&str>,
+    ) -> anyhow::Result<BTreeMap<String, PluginSpec>> {
+        let Some(group) = group else {
+            return Ok(self.plugins.clone());
+        };
+
+        let groups = self.groups.as_ref();
+        let members = groups.and_then(|g| g.get(group)).ok_or_else(|| {
+            let available: Vec<&str> = groups
+                .map(|g| g.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            if available.is_empty() {
+                anyhow::anyhow!(
+                    "Group '{}' not found: the manifest defines no groups",
+                    group
+                )
+            } else {
+                anyhow::anyhow!(
+                    "Group '{}' not found in manifest. Available groups: {}",
+                    group,
+                    available.join(", ")
+                )
+            }
+        })?;
+
+        let grouped: HashSet<&String> = groups.into_iter().flatten().flat_map(|(_, m)| m).collect();
+        Ok(self
+            .plugins
+            .iter()
+            .filter(|(name, _)| members.contains(name) || !grouped.contains(name))
+            .map(|(name, spec)| (name.clone(), spec.clone()))
+            .collect())
+    }
+
+    /// Group members that are not declared in `[plugins]` or any environment,
+    /// as (group, plugin) pairs
+    pub fn undeclared_group_members(&self) -> Vec<(&str, &str)> {
+        let declared_in_env = |member: &str| {
+            self.env
+                .iter()
+                .flatten()
+                .any(|(_, env)| env.plugins.contains_key(member))
+        };
+        self.groups
+            .iter()
+            .flatten()
+            .flat_map(|(group, members)| members.iter().map(move |m| (group.as_str(), m.as_str())))
+            .filter(|(_, member)| !self.plugins.contains_key(*member) && !declared_in_env(member))
+            .collect()
+    }
+
+    /// Pairs of plugin names whose ids match ignoring case, from the same
+    /// source or different ones, in name order
+    ///
+    /// Such plugins usually resolve to the same project, so it would be
+    /// installed twice.
+    pub fn same_id_plugins(&self) -> Vec<(&str, &str)> {
+        let plugins: Vec<(&String, &PluginSpec)> = self
+            .plugins
+            .iter()
+            .filter(|(_, spec)| !spec.id.trim().is_empty())
+            .collect();
+        let mut pairs = Vec::new();
+        for (i, (name, spec)) in plugins.iter().enumerate() {
+            for (other, other_spec) in &plugins[i + 1..] {
+                if spec.id.eq_ignore_ascii_case(&other_spec.id) {
+                    pairs.push((name.as_str(), other.as_str()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Merge an environment's plugins into `[plugins]`
+    ///
+    /// Environment entries are added, replacing any base plugin with the same
+    /// name. With no environment the manifest is returned as it is.
+    pub fn resolve_for_env(&self, env: Option<&str>) -> anyhow::Result<ResolvedManifest> {
+        let mut resolved = self.clone();
+        resolved.env = None;
+
+        if let Some(env) = env {
+            let envs = self.env.as_ref();
+            let overrides = envs.and_then(|e| e.get(env)).ok_or_else(|| {
+                let available: Vec<&str> = envs
+                    .map(|e| e.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                if available.is_empty() {
+                    anyhow::anyhow!(
+                        "Environment '{}' not found: the manifest defines no environments",
+                        env
+                    )
+                } else {
+                    anyhow::anyhow!(
+                        "Environment '{}' not found in manifest. Available environments: {}",
+                        env,
+                        available.join(", ")
+                    )
+                }
+            })?;
+            resolved.plugins.extend(overrides.plugins.clone());
+        }
+
+        Ok(ResolvedManifest(resolved))
+    }
+
+    /// The entry for a plugin as the environment sees it, for editing in place
+    pub fn plugin_mut(&mut self, env: Option<&str>, name: &str) -> Option<&mut PluginSpec> {
+        let in_env = env.is_some_and(|env| {
+            self.env
+                .as_ref()
+                .and_then(|e| e.get(env))
+                .is_some_and(|e| e.plugins.contains_key(name))
+        });
+        if in_env {
+            self.env
+                .as_mut()
+                .and_then(|e| e.get_mut(env?))
+                .and_then(|e| e.plugins.get_mut(name))
+        } else {
+            self.plugins.get_mut(name)
+        }
+    }
 }
@@ -0,0 +1,169 @@
+// SBOM module for emitting a CycloneDX Software Bill of Materials
+
+use crate::lockfile::{LockedPlugin, Lockfile};
+use crate::manifest::Manifest;
+use serde::Serialize;
+
+/// CycloneDX specification version emitted by mpm
+const SPEC_VERSION: &str = "1.4";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: Metadata,
+    components: Vec<Component>,
+}
+
+#[derive(Debug, Serialize)]
+struct Metadata {
+    tools: Vec<Tool>,
+    component: Component,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    vendor: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Component {
+    #[serde(rename = "type")]
+    component_type: &'static str,
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<Hash>,
+}
+
+#[derive(Debug, Serialize)]
+struct Hash {
+    alg: &'static str,
+    content: String,
+}
+
+impl Bom {
+    /// Build a BOM describing the server and every locked plugin
+    pub fn from_lockfile(manifest: &Manifest, lockfile: &Lockfile) -> Self {
+        let components = lockfile
+            .plugin
+            .iter()
+            .map(|plugin| {
+                // The manifest ID is what the source knows the plugin by;
+                // fall back to the lockfile name if the manifest has drifted
+                let id = manifest
+                    .plugins
+                    .get(&plugin.name)
+                    .map(|spec| spec.id.as_str())
+                    .unwrap_or(&plugin.name);
+                plugin_component(plugin, id)
+            })
+            .collect();
+
+        Self {
+            bom_format: "CycloneDX",
+            spec_version: SPEC_VERSION,
+            version: 1,
+            metadata: Metadata {
+                tools: vec![Tool {
+                    vendor: "mpm",
+                    name: "mpm",
+                    version: env!("CARGO_PKG_VERSION"),
+                }],
+                component: Component {
+                    component_type: "application",
+                    name: "minecraft-server".to_string(),
+                    version: manifest.minecraft.version.clone(),
+                    purl: None,
+                    hashes: Vec::new(),
+                },
+            },
+            components,
+        }
+    }
+
+    /// Write the BOM as pretty-printed JSON
+    pub fn save(&self, path: &str) -> anyhow::Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text)
+            .map_err(|e| anyhow::anyhow!("Failed to write SBOM to '{}': {}", path, e))?;
+        Ok(())
+    }
+}
+
+fn plugin_component(plugin: &LockedPlugin, id: &str) -> Component {
+    let hashes = match plugin.parse_hash() {
+        Ok(("sha256", content)) => vec![Hash {
+            alg: "SHA-256",
+            content: content.to_string(),
+        }],
+        Ok(("sha512", content)) => vec![Hash {
+            alg: "SHA-512",
+            content: content.to_string(),
+        }],
+        _ => Vec::new(),
+    };
+
+    Component {
+        component_type: "library",
+        name: plugin.name.clone(),
+        version: plugin.version.clone(),
+        purl: Some(purl(&plugin.source, id, &plugin.version)),
+        hashes,
+    }
+}
+
+/// Build a Package URL of the form pkg:maven/<source>/<id>@<version>
+fn purl(source: &str, id: &str, version: &str) -> String {
+    format!(
+        "pkg:maven/{}/{}@{}",
+        urlencoding::encode(source),
+        urlencoding::encode(id),
+        urlencoding::encode(version)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purl_encodes_segments() {
+        assert_eq!(
+            purl("modrinth", "worldedit", "7.3.0"),
+            "pkg:maven/modrinth/worldedit@7.3.0"
+        );
+        assert_eq!(
+            purl("hangar", "GeyserMC/Geyser", "2.4.0+b1"),
+            "pkg:maven/hangar/GeyserMC%2FGeyser@2.4.0%2Bb1"
+        );
+    }
+
+    #[test]
+    fn test_plugin_component_hash_algorithms() {
+        let plugin = LockedPlugin {
+            name: "worldedit".to_string(),
+            source: "modrinth".to_string(),
+            version: "7.3.0".to_string(),
+            file: "worldedit.jar".to_string(),
+            url: "https://example.com/worldedit.jar".to_string(),
+            urls: None,
+            hash: "sha512:abc123".to_string(),
+            size_bytes: None,
+            upstream_id: None,
+            zip_entry: None,
+        };
+
+        let component = plugin_component(&plugin, "worldedit");
+        assert_eq!(component.component_type, "library");
+        assert_eq!(component.hashes.len(), 1);
+        assert_eq!(component.hashes[0].alg, "SHA-512");
+        assert_eq!(component.hashes[0].content, "abc123");
+    }
+}
@@ -0,0 +1,172 @@
+// On-disk cache for API responses
+
+use crate::config;
+use anyhow::Result;
+use log::debug;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a fetched response may be served from the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheBehavior {
+    /// Always fetch, and don't store the response
+    Bypass,
+    /// Reuse the response until it is this old
+    CacheFor(Duration),
+    /// Reuse the response until the cache is cleared
+    #[allow(dead_code)] // For immutable resources; no source needs it yet
+    CacheForever,
+}
+
+impl CacheBehavior {
+    /// Time to keep a response, after applying the `[cache]` global config
+    ///
+    /// `None` means the cache is not used.
+    pub fn ttl(self) -> Option<Duration> {
+        let cache_config = &config::global().cache;
+        if !cache_config.enabled {
+            return None;
+        }
+        let ttl = match self {
+            CacheBehavior::Bypass => return None,
+            CacheBehavior::CacheFor(ttl) => ttl,
+            CacheBehavior::CacheForever => Duration::MAX,
+        };
+        match cache_config.ttl_secs {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(ttl),
+        }
+    }
+}
+
+/// Return the cached value for `key` if it hasn't expired, otherwise run
+/// `fetch` and cache its result for `ttl`
+///
+/// Values are stored as JSON in `<cache dir>/<sha256(key)>.json`, with the
+/// expiry (seconds since the Unix epoch) in a `.meta` file next to it. Errors
+/// from `fetch` are returned without being cached, and a cache that can't be
+/// read or written is skipped rather than failing the request.
+pub async fn get_cached<T, F>(key: &str, ttl: Duration, fetch: F) -> Result<T>
+where
+    T: DeserializeOwned + Serialize,
+    F: Future<Output = Result<T>>,
+{
+    match config::cache_dir() {
+        Some(dir) => get_cached_in(&dir, key, ttl, fetch).await,
+        None => fetch.await,
+    }
+}
+
+async fn get_cached_in<T, F>(dir: &Path, key: &str, ttl: Duration, fetch: F) -> Result<T>
+where
+    T: DeserializeOwned + Serialize,
+    F: Future<Output = Result<T>>,
+{
+    let name = hex::encode(Sha256::digest(key.as_bytes()));
+    let data_path = dir.join(format!("{}.json", name));
+    let meta_path = dir.join(format!("{}.meta", name));
+
+    if let Some(value) = read_entry(&data_path, &meta_path) {
+        debug!("Cache hit for {}", key);
+        return Ok(value);
+    }
+
+    let value = fetch.await?;
+
+    let expires_at = now_secs().saturating_add(ttl.as_secs());
+    let written = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&data_path, serde_json::to_vec(&value)?))
+        // The meta file is written last, so a readable expiry implies readable data
+        .and_then(|_| fs::write(&meta_path, expires_at.to_string()));
+    if let Err(e) = written {
+        debug!("Failed to cache response for {}: {}", key, e);
+    }
+
+    Ok(value)
+}
+
+/// Read an unexpired cache entry
+fn read_entry<T: DeserializeOwned>(data_path: &Path, meta_path: &Path) -> Option<T> {
+    let expires_at: u64 = fs::read_to_string(meta_path).ok()?.trim().parse().ok()?;
+    if now_secs() >= expires_at {
+        return None;
+    }
+    serde_json::from_slice(&fs::read(data_path).ok()?).ok()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(300);
+
+    #[tokio::test]
+    async fn test_cache_hit_skips_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first: Vec<u32> = get_cached_in(dir.path(), "key", TTL, async { Ok(vec![1, 2]) })
+            .await
+            .unwrap();
+        assert_eq!(first, vec![1, 2]);
+
+        let second: Vec<u32> = get_cached_in(dir.path(), "key", TTL, async {
+            anyhow::bail!("should not be fetched")
+        })
+        .await
+        .unwrap();
+        assert_eq!(second, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refetched() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let _: u32 = get_cached_in(dir.path(), "key", Duration::ZERO, async { Ok(1) })
+            .await
+            .unwrap();
+        let value: u32 = get_cached_in(dir.path(), "key", TTL, async { Ok(2) })
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let result: Result<u32> =
+            get_cached_in(dir.path(), "key", TTL, async { anyhow::bail!("offline") }).await;
+        assert!(result.is_err());
+
+        let value: u32 = get_cached_in(dir.path(), "key", TTL, async { Ok(3) })
+            .await
+            .unwrap();
+        assert_eq!(value, 3);
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_kept_apart() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let _: u32 = get_cached_in(dir.path(), "a", TTL, async { Ok(1) })
+            .await
+            .unwrap();
+        let value: u32 = get_cached_in(dir.path(), "b", TTL, async { Ok(2) })
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+    }
+}
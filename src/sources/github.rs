@@ -1,17 +1,42 @@
 // GitHub Releases source implementation
 
+use crate::config;
+use crate::jar;
 use crate::sources::hash::{self, HashAlgorithm};
 use crate::sources::http;
 use crate::sources::search::{self, ParsedId, Searchable};
-use crate::sources::source_trait::{PluginSource, ResolvedVersion};
+use crate::sources::source_trait::{
+    PluginSource, ResolutionDetails, ResolveOptions, ResolvedVersion, SearchResult,
+};
+use crate::sources::version_matcher::{self, VersionConstraint};
 use async_trait::async_trait;
+use regex::Regex;
 use serde::Deserialize;
 
+/// Words marking a JAR that isn't the plugin itself, e.g. `Plugin-1.0-sources.jar`
+const SECONDARY_JAR_MARKERS: &[&str] = &["sources", "javadoc", "api", "slim"];
+
 #[derive(Debug, Deserialize)]
 struct Release {
     #[serde(rename = "tag_name")]
     tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
     assets: Vec<Asset>,
+    /// API URL of the release itself
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+}
+
+impl Release {
+    /// Whether the release can be picked without naming its tag
+    fn is_eligible(&self, include_prereleases: bool) -> bool {
+        !self.draft && (include_prereleases || !self.prerelease)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +50,8 @@ struct Asset {
 struct Repository {
     name: String,
     owner: RepositoryOwner,
+    #[serde(default)]
+    description: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,9 +71,35 @@ impl Searchable for Repository {
     }
 }
 
+/// Rate limit status for the client making the request
+#[derive(Debug, Deserialize)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    rate: RateLimit,
+}
+
 pub struct GitHubSource;
 
 impl GitHubSource {
+    /// Client for api.github.com, authenticated when GITHUB_TOKEN is set
+    fn api_client() -> &'static reqwest::Client {
+        http::client_for_source("github")
+    }
+
+    /// Fetch the current core API rate limit
+    ///
+    /// Querying this endpoint does not count against the limit.
+    pub async fn rate_limit() -> anyhow::Result<RateLimit> {
+        let response: RateLimitResponse =
+            http::fetch_json_with(Self::api_client(), "https://api.github.com/rate_limit").await?;
+        Ok(response.rate)
+    }
+
     /// Search for a repository by name
     async fn search_repository(&self, search_name: &str) -> anyhow::Result<(String, String)> {
         let search_query = format!("{} in:name", urlencoding::encode(search_name));
@@ -55,7 +108,8 @@ impl GitHubSource {
             urlencoding::encode(&search_query)
         );
 
-        let search_result: SearchResponse = http::fetch_json(&search_url).await?;
+        let search_result: SearchResponse =
+            http::fetch_json_with(Self::api_client(), &search_url).await?;
 
         if search_result.items.is_empty() {
             anyhow::bail!("No repositories found matching '{}' on GitHub", search_name);
@@ -77,35 +131,100 @@ impl GitHubSource {
     }
 
     /// Fetch release by tag or latest
+    ///
+    /// Drafts are never picked, and pre-releases only with `include_prereleases`
+    /// or when their tag is requested exactly.
     async fn fetch_release(
         owner: &str,
         repo: &str,
         requested_version: Option<&str>,
+        include_prereleases: bool,
     ) -> anyhow::Result<Release> {
-        let url = if let Some(version) = requested_version {
-            format!(
-                "https://api.github.com/repos/{}/{}/releases/tags/{}",
-                owner, repo, version
-            )
-        } else {
-            format!(
-                "https://api.github.com/repos/{}/{}/releases/latest",
-                owner, repo
-            )
+        let constraint = requested_version
+            .map(version_matcher::parse_constraint)
+            .transpose()?;
+        let tag = match &constraint {
+            Some(VersionConstraint::Exact(tag)) => tag,
+            None | Some(VersionConstraint::Latest) => {
+                return Self::fetch_latest_release(owner, repo, include_prereleases).await;
+            }
+            Some(range) => {
+                return Self::fetch_matching_release(owner, repo, range, include_prereleases).await;
+            }
         };
 
-        http::fetch_json(&url).await.map_err(|_| {
-            if let Some(version) = requested_version {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases/tags/{}",
+            owner, repo, tag
+        );
+        http::fetch_json_with(Self::api_client(), &url)
+            .await
+            .map_err(|_| {
                 anyhow::anyhow!(
                     "Release '{}' not found for repository '{}/{}'",
-                    version,
+                    tag,
                     owner,
                     repo
                 )
-            } else {
-                anyhow::anyhow!("No releases found for repository '{}/{}'", owner, repo)
+            })
+    }
+
+    /// Fetch the newest release that can be picked
+    ///
+    /// `/releases/latest` already skips drafts and pre-releases, so it is used
+    /// unless pre-releases are wanted or it returns something else.
+    async fn fetch_latest_release(
+        owner: &str,
+        repo: &str,
+        include_prereleases: bool,
+    ) -> anyhow::Result<Release> {
+        if !include_prereleases {
+            let url = format!(
+                "https://api.github.com/repos/{}/{}/releases/latest",
+                owner, repo
+            );
+            if let Ok(release) = http::fetch_json_with::<Release>(Self::api_client(), &url).await
+                && release.is_eligible(false)
+            {
+                return Ok(release);
             }
-        })
+        }
+
+        Self::list_releases(owner, repo)
+            .await?
+            .into_iter()
+            .find(|r| r.is_eligible(include_prereleases))
+            .ok_or_else(|| anyhow::anyhow!("No releases found for repository '{}/{}'", owner, repo))
+    }
+
+    /// Find the newest release whose tag satisfies a range constraint
+    async fn fetch_matching_release(
+        owner: &str,
+        repo: &str,
+        constraint: &VersionConstraint,
+        include_prereleases: bool,
+    ) -> anyhow::Result<Release> {
+        Self::list_releases(owner, repo)
+            .await?
+            .into_iter()
+            .find(|r| r.is_eligible(include_prereleases) && constraint.matches(&r.tag_name))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No release matching '{}' found for repository '{}/{}'",
+                    constraint,
+                    owner,
+                    repo
+                )
+            })
+    }
+
+    /// The 100 most recent releases, newest first, including drafts and pre-releases
+    async fn list_releases(owner: &str, repo: &str) -> anyhow::Result<Vec<Release>> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases?per_page=100",
+            owner, repo
+        );
+        http::fetch_json_with(Self::api_client(), &url).await
     }
 }
 
@@ -122,49 +241,293 @@ impl PluginSource for GitHubSource {
         Ok(())
     }
 
+    async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        // GitHub caps per_page at 100
+        let search_query = format!("{} topic:minecraft-plugin in:name", query);
+        let url = format!(
+            "https://api.github.com/search/repositories?q={}&sort=stars&order=desc&per_page={}",
+            urlencoding::encode(&search_query),
+            limit.min(100)
+        );
+
+        let response: SearchResponse = http::fetch_json_with(Self::api_client(), &url).await?;
+        Ok(response
+            .items
+            .into_iter()
+            .map(|repo| SearchResult {
+                id: format!("{}/{}", repo.owner.login, repo.name),
+                name: repo.name,
+                source: self.name().to_string(),
+                description: repo.description.filter(|d| !d.is_empty()),
+                // GitHub does not report download counts for repositories
+                downloads: None,
+                // A release needs a request per repository, which the
+                // unauthenticated limit of 60 an hour can't spare
+                latest_version: None,
+            })
+            .collect())
+    }
+
     async fn resolve_version(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        minecraft_version: Option<&str>,
+    ) -> anyhow::Result<ResolvedVersion> {
+        let options = ResolveOptions {
+            prerelease: config::global().include_prereleases,
+            ..Default::default()
+        };
+        self.resolve_version_with(plugin_id, requested_version, minecraft_version, &options)
+            .await
+    }
+
+    async fn resolve_version_with(
         &self,
         plugin_id: &str,
         requested_version: Option<&str>,
         _minecraft_version: Option<&str>,
+        options: &ResolveOptions,
     ) -> anyhow::Result<ResolvedVersion> {
+        // Catch malformed patterns before touching the network
+        if let Some(pattern) = &options.asset_pattern {
+            full_match("asset_pattern", pattern)?;
+        }
+        let unzip = options
+            .unzip
+            .as_deref()
+            .map(|pattern| full_match("unzip", pattern))
+            .transpose()?;
+
         // GitHub Releases don't have built-in Minecraft version metadata
         // Resolve plugin ID to owner/repo
         let (owner, repo) = self.resolve_repo_id(plugin_id).await?;
 
-        // Verify repository exists
+        // Verify repository exists; a renamed repository answers under its new name
         let repo_url = format!("https://api.github.com/repos/{}/{}", owner, repo);
-        http::fetch_json::<Repository>(&repo_url)
+        let repository = http::fetch_json_with::<Repository>(Self::api_client(), &repo_url)
             .await
             .map_err(|_| anyhow::anyhow!("Repository '{}/{}' not found on GitHub", owner, repo))?;
 
         // Fetch release
-        let release = Self::fetch_release(&owner, &repo, requested_version).await?;
+        let release =
+            Self::fetch_release(&owner, &repo, requested_version, options.prerelease).await?;
 
-        // Find the first .jar file in assets
-        let jar_asset = release
-            .assets
-            .iter()
-            .find(|a| a.name.ends_with(".jar"))
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No .jar file found in release '{}' for '{}/{}'",
-                    release.tag_name,
-                    owner,
-                    repo
-                )
-            })?;
+        let asset = match &unzip {
+            Some(_) => select_zip_asset(&release.assets, options.asset_pattern.as_deref()),
+            None => select_jar_asset(&release.assets, options.asset_pattern.as_deref()),
+        }
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{} in release '{}' for '{}/{}'. Available assets: {}",
+                e,
+                release.tag_name,
+                owner,
+                repo,
+                asset_names(&release.assets)
+            )
+        })?;
 
         // Download the file to compute hash
-        let response = http::download_with_response(&jar_asset.browser_download_url).await?;
-        let data = response.bytes().await?;
+        let response = http::download_with_response(&asset.browser_download_url).await?;
+        let data = response.bytes().await?.to_vec();
+
+        // A zipped plugin is locked as the JAR inside, which is what sync installs
+        let (filename, data, zip_entry) = match &unzip {
+            Some(pattern) => {
+                let (entry, jar) = jar::extract_matching(&data, pattern).map_err(|e| {
+                    anyhow::anyhow!(
+                        "{} (unzip '{}', asset '{}' in release '{}' for '{}/{}')",
+                        e,
+                        options.unzip.as_deref().unwrap_or_default(),
+                        asset.name,
+                        release.tag_name,
+                        owner,
+                        repo
+                    )
+                })?;
+                let filename = entry.rsplit('/').next().unwrap_or(&entry).to_string();
+                (filename, jar, Some(entry))
+            }
+            None => (asset.name.clone(), data, None),
+        };
         let hash_str = hash::compute_hash(&data, HashAlgorithm::Sha256);
 
         Ok(ResolvedVersion {
             version: release.tag_name,
-            filename: jar_asset.name.clone(),
-            url: jar_asset.browser_download_url.clone(),
+            filename,
+            url: asset.browser_download_url.clone(),
+            urls: Vec::new(),
             hash: hash_str,
+            size_bytes: Some(data.len() as u64),
+            canonical_id: Some(format!("{}/{}", repository.owner.login, repository.name)),
+            zip_entry,
+            details: ResolutionDetails {
+                api_url: release.url,
+                published_at: release.published_at,
+                ..Default::default()
+            },
         })
     }
 }
+
+/// Compile a pattern from the manifest that must match a whole filename
+fn full_match(field: &str, pattern: &str) -> anyhow::Result<Regex> {
+    Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|e| anyhow::anyhow!("Invalid {} '{}': {}", field, pattern, e))
+}
+
+/// Names of a release's assets for error messages, or "none"
+fn asset_names(assets: &[Asset]) -> String {
+    if assets.is_empty() {
+        return "none".to_string();
+    }
+    let names: Vec<&str> = assets.iter().map(|a| a.name.as_str()).collect();
+    names.join(", ")
+}
+
+/// Pick the `.zip` asset holding a zipped plugin: the first asset matching
+/// the pattern, or without one the first `.zip`
+fn select_zip_asset<'a>(assets: &'a [Asset], pattern: Option<&str>) -> anyhow::Result<&'a Asset> {
+    if pattern.is_some() {
+        return select_jar_asset(assets, pattern);
+    }
+    assets
+        .iter()
+        .find(|a| a.name.ends_with(".zip"))
+        .ok_or_else(|| anyhow::anyhow!("No .zip file"))
+}
+
+/// Pick the release asset to download
+///
+/// With a pattern, the first asset whose whole filename matches it. Without
+/// one, the first `.jar` that isn't a sources, javadoc, API, or slim build,
+/// falling back to the first `.jar` if that's all there is.
+fn select_jar_asset<'a>(assets: &'a [Asset], pattern: Option<&str>) -> anyhow::Result<&'a Asset> {
+    if let Some(pattern) = pattern {
+        let regex = full_match("asset_pattern", pattern)?;
+        return assets
+            .iter()
+            .find(|a| regex.is_match(&a.name))
+            .ok_or_else(|| anyhow::anyhow!("No asset matches asset_pattern '{}'", pattern));
+    }
+
+    let jars: Vec<&Asset> = assets.iter().filter(|a| a.name.ends_with(".jar")).collect();
+    jars.iter()
+        .find(|a| !is_secondary_jar(&a.name))
+        .or_else(|| jars.first())
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("No .jar file"))
+}
+
+/// Whether a filename has a word like `sources` or `api` between its separators
+fn is_secondary_jar(filename: &str) -> bool {
+    let stem = filename.strip_suffix(".jar").unwrap_or(filename);
+    stem.split(['-', '_', '.', ' ']).any(|word| {
+        SECONDARY_JAR_MARKERS
+            .iter()
+            .any(|m| word.eq_ignore_ascii_case(m))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(json: &str) -> Release {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_drafts_are_never_eligible() {
+        let draft = release(r#"{"tag_name": "v2.0", "draft": true, "assets": []}"#);
+        assert!(!draft.is_eligible(false));
+        assert!(!draft.is_eligible(true));
+    }
+
+    #[test]
+    fn test_prereleases_are_eligible_only_when_included() {
+        let beta = release(r#"{"tag_name": "v2.0-beta", "prerelease": true, "assets": []}"#);
+        assert!(!beta.is_eligible(false));
+        assert!(beta.is_eligible(true));
+
+        // Older API responses may omit the flags entirely
+        let stable = release(r#"{"tag_name": "v1.0", "assets": []}"#);
+        assert!(stable.is_eligible(false));
+    }
+
+    fn assets(names: &[&str]) -> Vec<Asset> {
+        names
+            .iter()
+            .map(|name| Asset {
+                name: name.to_string(),
+                browser_download_url: format!("https://example.com/{}", name),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_skips_secondary_jars_without_a_pattern() {
+        let jars = assets(&[
+            "Plugin-1.0-sources.jar",
+            "Plugin-1.0-API.jar",
+            "Plugin-1.0.jar",
+            "Plugin-1.0-javadoc.jar",
+        ]);
+        assert_eq!(
+            select_jar_asset(&jars, None).unwrap().name,
+            "Plugin-1.0.jar"
+        );
+
+        // Words merely containing a marker are not secondary builds
+        let capital = assets(&["Capital-1.0.jar"]);
+        assert_eq!(
+            select_jar_asset(&capital, None).unwrap().name,
+            "Capital-1.0.jar"
+        );
+
+        let only_api = assets(&["Plugin-1.0-api.jar"]);
+        assert_eq!(
+            select_jar_asset(&only_api, None).unwrap().name,
+            "Plugin-1.0-api.jar"
+        );
+    }
+
+    #[test]
+    fn test_pattern_must_match_the_whole_filename() {
+        let jars = assets(&["Plugin-1.0-sources.jar", "Plugin-1.0.jar"]);
+        let picked = select_jar_asset(&jars, Some(r"Plugin-[0-9.]+\.jar")).unwrap();
+        assert_eq!(picked.name, "Plugin-1.0.jar");
+
+        let err = select_jar_asset(&jars, Some("Other.*")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("No asset matches asset_pattern 'Other.*'")
+        );
+        assert!(select_jar_asset(&jars, Some("(")).is_err());
+    }
+
+    #[test]
+    fn test_errors_list_every_asset() {
+        let files = assets(&["server.jar", "changelog.md", "checksums.txt"]);
+        assert_eq!(
+            asset_names(&files),
+            "server.jar, changelog.md, checksums.txt"
+        );
+        assert_eq!(asset_names(&[]), "none");
+    }
+
+    #[test]
+    fn test_zipped_plugins_use_the_first_zip_or_the_pattern() {
+        let files = assets(&["Plugin-1.0.jar", "Plugin-1.0-bundle.zip", "extras.zip"]);
+        assert_eq!(
+            select_zip_asset(&files, None).unwrap().name,
+            "Plugin-1.0-bundle.zip"
+        );
+        assert_eq!(
+            select_zip_asset(&files, Some("extras\\.zip")).unwrap().name,
+            "extras.zip"
+        );
+        assert!(select_zip_asset(&assets(&["Plugin-1.0.jar"]), None).is_err());
+    }
+}
@@ -1,18 +1,127 @@
 // Hangar source implementation (PaperMC plugin repository)
 
+use crate::error::MpmError;
+use crate::sources::cache::{self, CacheBehavior};
 use crate::sources::hash::{self, HashAlgorithm};
 use crate::sources::http;
 use crate::sources::search::{self, ParsedId, Searchable};
-use crate::sources::source_trait::{PluginSource, ResolvedVersion};
+use crate::sources::source_trait::{
+    AvailableVersion, PluginInfo, PluginSource, ResolveOptions, ResolvedVersion, SearchResult,
+};
 use crate::sources::version_data::{DownloadInfo, NormalizedVersion};
+use crate::sources::version_matcher::{self, VersionConstraint};
 use crate::sources::version_selector::{self, SelectionConfig};
 use async_trait::async_trait;
+use futures::future::join_all;
+use log::debug;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+/// How long project and version responses are reused from the cache
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Platform whose download is preferred when a plugin doesn't name one
+const DEFAULT_PLATFORM: &str = "PAPER";
+
+/// Release channels, most stable first; a plugin's channel accepts itself and
+/// every channel before it (RELEASE when the plugin doesn't name one)
+const CHANNELS: &[&str] = &["RELEASE", "BETA", "ALPHA"];
+
+/// Endpoint that exchanges an API key for a short-lived JWT
+const AUTHENTICATE_URL: &str = "https://hangar.papermc.io/api/v1/authenticate";
+
+/// The Hangar API client, built on first use
+static HANGAR_CLIENT: OnceCell<reqwest::Client> = OnceCell::const_new();
+
+#[derive(Debug, Deserialize)]
+struct AuthenticateResponse {
+    token: String,
+}
+
+/// Client for the Hangar API, authenticated when HANGAR_API_KEY is set
+///
+/// Hangar doesn't accept the API key on ordinary requests: it is exchanged
+/// once for a JWT, which is sent as the `Authorization` header from then on
+/// so private and unlisted projects can be looked up.
+pub async fn hangar_client() -> anyhow::Result<reqwest::Client> {
+    HANGAR_CLIENT
+        .get_or_try_init(|| async {
+            let Some(key) = http::hangar_api_key() else {
+                return Ok(http::client().clone());
+            };
+            let response = http::post_with(http::client(), AUTHENTICATE_URL, &[("apiKey", &key)])
+                .await
+                .map_err(|e| {
+                    e.context(format!(
+                        "Failed to authenticate with Hangar at {}",
+                        AUTHENTICATE_URL
+                    ))
+                })?;
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "Hangar rejected {} ({}). Check that the key exists and hasn't been revoked.",
+                    http::HANGAR_API_KEY_ENV,
+                    response.status()
+                );
+            }
+            let auth: AuthenticateResponse = response.json().await?;
+            debug!(
+                "Authenticated with Hangar using {}",
+                http::HANGAR_API_KEY_ENV
+            );
+            http::authenticated_client(&auth.token, http::HANGAR_API_KEY_ENV)
+        })
+        .await
+        .cloned()
+}
+
+/// Fetch JSON from the Hangar API through `hangar_client`
+async fn hangar_fetch_json<T: DeserializeOwned>(
+    url: &str,
+    cache: CacheBehavior,
+) -> anyhow::Result<T> {
+    let client = hangar_client().await?;
+    match cache.ttl() {
+        Some(ttl) => {
+            let value: serde_json::Value =
+                cache::get_cached(url, ttl, http::fetch_json_with(&client, url)).await?;
+            Ok(serde_json::from_value(value)?)
+        }
+        None => http::fetch_json_with(&client, url).await,
+    }
+}
 
 #[derive(Debug, Deserialize)]
 struct Project {
     name: String,
     namespace: Namespace,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    stats: Option<ProjectStats>,
+    #[serde(default)]
+    settings: Option<ProjectSettings>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectSettings {
+    #[serde(default)]
+    license: Option<ProjectLicense>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectLicense {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "type", default)]
+    license_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectStats {
+    downloads: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +143,13 @@ struct Version {
     #[serde(rename = "platformDependencies")]
     platform_dependencies: std::collections::HashMap<String, Vec<String>>,
     downloads: std::collections::HashMap<String, Download>,
+    #[serde(default)]
+    channel: Option<VersionChannel>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct VersionChannel {
+    name: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -51,6 +167,8 @@ struct FileInfo {
     name: Option<String>,
     #[serde(rename = "sha256Hash")]
     sha256_hash: Option<String>,
+    #[serde(rename = "sizeBytes")]
+    size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +176,35 @@ struct VersionsResponse {
     result: Vec<Version>,
 }
 
+/// Platforms with a download in any of the versions, sorted
+fn available_platforms(versions: &[Version]) -> Vec<String> {
+    let platforms: std::collections::BTreeSet<&String> = versions
+        .iter()
+        .flat_map(|v| v.downloads.iter())
+        .filter(|(_, d)| d.download_url.is_some() || d.external_url.is_some())
+        .map(|(platform, _)| platform)
+        .collect();
+    platforms.into_iter().cloned().collect()
+}
+
+/// Position of a channel in `CHANNELS`, ignoring case
+fn channel_rank(name: &str) -> Option<usize> {
+    CHANNELS.iter().position(|c| c.eq_ignore_ascii_case(name))
+}
+
+/// Whether a version's channel is at least as stable as the one at `max_rank`
+///
+/// ALPHA accepts every channel, including custom ones such as Snapshot.
+/// Versions the API returns without a channel are accepted.
+fn in_channel(v: &Version, max_rank: usize) -> bool {
+    if max_rank + 1 >= CHANNELS.len() {
+        return true;
+    }
+    v.channel
+        .as_ref()
+        .is_none_or(|c| channel_rank(&c.name).is_some_and(|rank| rank <= max_rank))
+}
+
 // Implement Searchable for Project
 impl Searchable for Project {
     fn search_name(&self) -> &str {
@@ -69,35 +216,44 @@ pub struct HangarSource;
 
 impl HangarSource {
     /// Normalize a Hangar API version to our common format
-    fn normalize_version(v: &Version) -> Option<NormalizedVersion> {
-        // Get download - prefer PAPER platform, fallback to first available
-        let download = v
-            .downloads
-            .get("PAPER")
-            .filter(|d| d.download_url.is_some() || d.external_url.is_some())
-            .or_else(|| {
-                v.downloads
-                    .values()
-                    .find(|d| d.download_url.is_some() || d.external_url.is_some())
-            })?;
+    ///
+    /// With a platform, only that platform's download is used. Without one,
+    /// PAPER is preferred, falling back to the first available download.
+    fn normalize_version(v: &Version, platform: Option<&str>) -> Option<NormalizedVersion> {
+        let has_url = |d: &&Download| d.download_url.is_some() || d.external_url.is_some();
+        let download = match platform {
+            Some(platform) => v.downloads.get(platform).filter(has_url)?,
+            None => v
+                .downloads
+                .get(DEFAULT_PLATFORM)
+                .filter(has_url)
+                .or_else(|| v.downloads.values().find(has_url))?,
+        };
 
         let url = download
             .download_url
             .as_ref()
             .or(download.external_url.as_ref())?;
+        // With both, the external URL is kept as a fallback for Hangar's CDN
+        let mirror = download
+            .external_url
+            .as_ref()
+            .filter(|external| *external != url);
 
         // Get MC versions from platform dependencies
         let mc_versions: Vec<String> = v
             .platform_dependencies
-            .values()
-            .flatten()
+            .iter()
+            .filter(|(p, _)| platform.is_none_or(|platform| p.as_str() == platform))
+            .flat_map(|(_, versions)| versions)
             .cloned()
             .collect();
 
         // Build download info based on available file info
-        let download_info = if let Some(file_info) = &download.file_info {
+        let mut download_info = if let Some(file_info) = &download.file_info {
             if let (Some(name), Some(hash)) = (&file_info.name, &file_info.sha256_hash) {
                 DownloadInfo::with_hash(url, name, hash::format_hash(hash, HashAlgorithm::Sha256))
+                    .with_size(file_info.size_bytes)
             } else {
                 // fileInfo exists but incomplete - need to compute hash
                 DownloadInfo::without_hash(url, file_info.name.clone())
@@ -106,6 +262,9 @@ impl HangarSource {
             // No fileInfo - need to compute hash
             DownloadInfo::url_only(url)
         };
+        if let Some(mirror) = mirror {
+            download_info = download_info.with_mirror(mirror);
+        }
 
         Some(NormalizedVersion {
             version: v.name.clone(),
@@ -115,28 +274,122 @@ impl HangarSource {
         })
     }
 
-    /// Fetch versions from the Hangar API
-    async fn fetch_versions(author: &str, slug: &str) -> anyhow::Result<Vec<NormalizedVersion>> {
+    /// Build the version list URL, filtered by platform when given
+    fn versions_url(author: &str, slug: &str, platform: Option<&str>) -> String {
         let url = format!(
             "https://hangar.papermc.io/api/v1/projects/{}/{}/versions",
             author, slug
         );
+        match platform {
+            Some(platform) => format!("{}?platform={}", url, urlencoding::encode(platform)),
+            None => url,
+        }
+    }
 
-        let response: VersionsResponse = http::fetch_json(&url).await?;
-        Ok(response
+    /// Fetch versions from the Hangar API, limited to a channel rank when given
+    async fn fetch_versions(
+        author: &str,
+        slug: &str,
+        platform: Option<&str>,
+        max_channel: Option<usize>,
+    ) -> anyhow::Result<Vec<NormalizedVersion>> {
+        let url = Self::versions_url(author, slug, platform);
+
+        let response: VersionsResponse =
+            hangar_fetch_json(&url, CacheBehavior::CacheFor(CACHE_TTL)).await?;
+        let versions: Vec<NormalizedVersion> = response
             .result
             .iter()
-            .filter_map(Self::normalize_version)
-            .collect())
+            .filter(|v| max_channel.is_none_or(|max| in_channel(v, max)))
+            .filter_map(|v| Self::normalize_version(v, platform))
+            .collect();
+
+        // Name the platforms that do have downloads rather than reporting no versions
+        if let Some(platform) = platform
+            && versions.is_empty()
+        {
+            let response: VersionsResponse = hangar_fetch_json(
+                &Self::versions_url(author, slug, None),
+                CacheBehavior::CacheFor(CACHE_TTL),
+            )
+            .await?;
+            let available = available_platforms(&response.result);
+            if !available.is_empty() {
+                anyhow::bail!(
+                    "Plugin '{}/{}' has no {} downloads. Available platforms: {}",
+                    author,
+                    slug,
+                    platform,
+                    available.join(", ")
+                );
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Fetch project details, mapping any request failure to a not-found error
+    async fn fetch_project(author: &str, slug: &str) -> anyhow::Result<Project> {
+        // A rejected API key is reported as such rather than as a missing project
+        hangar_client().await?;
+        let project_url = format!(
+            "https://hangar.papermc.io/api/v1/projects/{}/{}",
+            author, slug
+        );
+        hangar_fetch_json(&project_url, CacheBehavior::CacheFor(CACHE_TTL))
+            .await
+            .map_err(|_| {
+                MpmError::PluginNotFound {
+                    source: Some("hangar".to_string()),
+                    id: format!("{}/{}", author, slug),
+                }
+                .into()
+            })
+    }
+
+    /// Name of a project's latest release, or None if it has none or the
+    /// lookup fails, which search results can do without
+    async fn latest_release(namespace: &Namespace) -> Option<String> {
+        let url = format!(
+            "https://hangar.papermc.io/api/v1/projects/{}/{}/latestrelease",
+            namespace.owner, namespace.slug
+        );
+        let result = async {
+            let response = http::get_with(&hangar_client().await?, &url).await?;
+            if !response.status().is_success() {
+                anyhow::bail!("HTTP {}", response.status());
+            }
+            Ok(response.text().await?)
+        };
+        match result.await {
+            Ok(version) => Some(version.trim().to_string()).filter(|v| !v.is_empty()),
+            Err(e) => {
+                debug!("Failed to look up latest release of {}: {}", url, e);
+                None
+            }
+        }
+    }
+
+    /// Look up a project by its slug alone
+    ///
+    /// Many projects are published under an owner of the same name (e.g.
+    /// `ViaVersion/ViaVersion`), so `<slug>/<slug>` is tried directly first.
+    /// Only if that doesn't exist is the fuzzier search API used.
+    async fn get_project_by_slug(&self, slug: &str) -> anyhow::Result<Project> {
+        match Self::fetch_project(slug, slug).await {
+            Ok(project) => Ok(project),
+            Err(_) => self.search_project(slug).await,
+        }
     }
 
     /// Search for a project by name and return the best match
-    async fn search_project(&self, search_name: &str) -> anyhow::Result<(String, String)> {
+    async fn search_project(&self, search_name: &str) -> anyhow::Result<Project> {
         let search_url = format!(
             "https://hangar.papermc.io/api/v1/projects?q={}",
             urlencoding::encode(search_name)
         );
-        let search_result: SearchResponse = http::fetch_json(&search_url).await?;
+        let search_result: SearchResponse =
+            hangar_fetch_json(&search_url, CacheBehavior::CacheFor(CACHE_TTL)).await?;
 
         if search_result.result.is_empty() {
             anyhow::bail!("No projects found matching '{}' in Hangar", search_name);
@@ -145,15 +398,17 @@ impl HangarSource {
         let mut results = search_result.result;
         search::rank_search_results(&mut results, search_name);
 
-        let project = results.into_iter().next().unwrap();
-        Ok((project.namespace.owner, project.namespace.slug))
+        Ok(results.into_iter().next().unwrap())
     }
 
     /// Parse plugin ID and resolve to owner/slug
     async fn resolve_plugin_id(&self, plugin_id: &str) -> anyhow::Result<(String, String)> {
         match search::parse_owner_name_id(plugin_id) {
             ParsedId::Full { owner, name } => Ok((owner, name)),
-            ParsedId::SearchTerm(_) => self.search_project(plugin_id).await,
+            ParsedId::SearchTerm(_) => {
+                let project = self.get_project_by_slug(plugin_id).await?;
+                Ok((project.namespace.owner, project.namespace.slug))
+            }
         }
     }
 }
@@ -171,32 +426,223 @@ impl PluginSource for HangarSource {
         Ok(())
     }
 
+    async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let url = format!(
+            "https://hangar.papermc.io/api/v1/projects?q={}&limit={}",
+            urlencoding::encode(query),
+            limit
+        );
+
+        let response: SearchResponse = hangar_fetch_json(&url, CacheBehavior::Bypass).await?;
+        let latest_versions = join_all(
+            response
+                .result
+                .iter()
+                .map(|project| Self::latest_release(&project.namespace)),
+        )
+        .await;
+        Ok(response
+            .result
+            .into_iter()
+            .zip(latest_versions)
+            .map(|(project, latest_version)| SearchResult {
+                name: project.name,
+                id: format!("{}/{}", project.namespace.owner, project.namespace.slug),
+                source: self.name().to_string(),
+                description: project.description.filter(|d| !d.is_empty()),
+                downloads: project.stats.map(|s| s.downloads),
+                latest_version,
+            })
+            .collect())
+    }
+
+    async fn info(
+        &self,
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+    ) -> anyhow::Result<PluginInfo> {
+        let (author, slug) = self.resolve_plugin_id(plugin_id).await?;
+        let project = Self::fetch_project(&author, &slug).await?;
+
+        let versions = Self::fetch_versions(&author, &slug, None, Some(0)).await?;
+        let display_id = format!("{}/{}", author, slug);
+        let config = SelectionConfig::new(&display_id);
+        let compatible_versions =
+            version_selector::compatible_versions(versions.clone(), minecraft_version, &config);
+        let latest =
+            version_selector::select_version(versions, None, minecraft_version, &config).await?;
+
+        // Custom licenses only carry a name; standard ones may only carry a type
+        let license = project
+            .settings
+            .and_then(|s| s.license)
+            .and_then(|l| l.name.filter(|n| !n.is_empty()).or(l.license_type));
+
+        Ok(PluginInfo {
+            id: display_id,
+            source: self.name().to_string(),
+            name: Some(project.name),
+            description: project.description.filter(|d| !d.is_empty()),
+            author: Some(project.namespace.owner),
+            license,
+            latest,
+            compatible_versions,
+        })
+    }
+
+    async fn list_versions(
+        &self,
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+        options: &ResolveOptions,
+    ) -> anyhow::Result<Vec<AvailableVersion>> {
+        let platform = options.platform.as_deref().map(str::to_uppercase);
+        let (author, slug) = self.resolve_plugin_id(plugin_id).await?;
+
+        // Every channel is listed, since an exact version can always be pinned
+        let versions = Self::fetch_versions(&author, &slug, platform.as_deref(), None).await?;
+        let config = SelectionConfig::new(format!("{}/{}", author, slug));
+        Ok(version_selector::available_versions(
+            versions,
+            minecraft_version,
+            &config,
+        ))
+    }
+
     async fn resolve_version(
         &self,
         plugin_id: &str,
         requested_version: Option<&str>,
         minecraft_version: Option<&str>,
     ) -> anyhow::Result<ResolvedVersion> {
+        self.resolve_version_with(
+            plugin_id,
+            requested_version,
+            minecraft_version,
+            &ResolveOptions::default(),
+        )
+        .await
+    }
+
+    async fn resolve_version_with(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        minecraft_version: Option<&str>,
+        options: &ResolveOptions,
+    ) -> anyhow::Result<ResolvedVersion> {
+        // Hangar platform keys are upper case (PAPER, WATERFALL, VELOCITY)
+        let platform = options.platform.as_deref().map(str::to_uppercase);
+
+        let channel = options.channel.as_deref().unwrap_or(CHANNELS[0]);
+        let max_channel = channel_rank(channel).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid Hangar channel '{}': expected {}",
+                channel,
+                CHANNELS.join(", ")
+            )
+        })?;
+        // An exact version can always be pinned, whatever its channel
+        let pinned = requested_version.is_some_and(|v| {
+            matches!(
+                version_matcher::parse_constraint(v),
+                Ok(VersionConstraint::Exact(_))
+            )
+        });
+
         // Resolve plugin ID to owner/slug
         let (author, slug) = self.resolve_plugin_id(plugin_id).await?;
 
         // Verify project exists
-        let project_url = format!(
-            "https://hangar.papermc.io/api/v1/projects/{}/{}",
-            author, slug
-        );
-        http::fetch_json::<Project>(&project_url)
-            .await
-            .map_err(|_| anyhow::anyhow!("Plugin '{}/{}' not found in Hangar", author, slug))?;
+        Self::fetch_project(&author, &slug).await?;
 
-        // Fetch all versions
-        let versions = Self::fetch_versions(&author, &slug).await?;
+        // Fetch all versions for the platform and channel
+        let versions = Self::fetch_versions(
+            &author,
+            &slug,
+            platform.as_deref(),
+            (!pinned).then_some(max_channel),
+        )
+        .await?;
 
         // Use version selector with plugin ID for error messages
         let display_id = format!("{}/{}", author, slug);
         let config = SelectionConfig::new(&display_id);
+        let api_url = Self::versions_url(&author, &slug, platform.as_deref());
 
         version_selector::select_version(versions, requested_version, minecraft_version, &config)
             .await
+            .map(|resolved| resolved.with_canonical_id(display_id).with_api_url(api_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version() -> Version {
+        serde_json::from_value(serde_json::json!({
+            "name": "1.0.0",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "platformDependencies": {
+                "PAPER": ["1.20.6"],
+                "VELOCITY": ["3.3"]
+            },
+            "downloads": {
+                "PAPER": { "downloadUrl": "https://example.com/paper.jar" },
+                "VELOCITY": { "downloadUrl": "https://example.com/velocity.jar" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_versions_url_filters_platform() {
+        assert_eq!(
+            HangarSource::versions_url("Owner", "Plugin", Some("VELOCITY")),
+            "https://hangar.papermc.io/api/v1/projects/Owner/Plugin/versions?platform=VELOCITY"
+        );
+        assert_eq!(
+            HangarSource::versions_url("Owner", "Plugin", None),
+            "https://hangar.papermc.io/api/v1/projects/Owner/Plugin/versions"
+        );
+    }
+
+    #[test]
+    fn test_normalize_version_uses_requested_platform() {
+        let velocity = HangarSource::normalize_version(&version(), Some("VELOCITY")).unwrap();
+        assert_eq!(velocity.mc_versions, vec!["3.3"]);
+
+        let default = HangarSource::normalize_version(&version(), None).unwrap();
+        assert_eq!(default.mc_versions.len(), 2);
+
+        assert!(HangarSource::normalize_version(&version(), Some("WATERFALL")).is_none());
+    }
+
+    #[test]
+    fn test_channel_filtering() {
+        let in_named_channel = |name: &str, max: &str| {
+            let mut v = version();
+            v.channel = Some(VersionChannel {
+                name: name.to_string(),
+            });
+            in_channel(&v, channel_rank(max).unwrap())
+        };
+        assert!(in_named_channel("Release", "RELEASE"));
+        assert!(!in_named_channel("Beta", "RELEASE"));
+        assert!(in_named_channel("Beta", "beta"));
+        assert!(!in_named_channel("Alpha", "BETA"));
+        assert!(!in_named_channel("Snapshot", "BETA"));
+        assert!(in_named_channel("Snapshot", "ALPHA"));
+        assert!(
+            in_channel(&version(), 0),
+            "Versions without a channel are kept"
+        );
+        assert_eq!(channel_rank("stable"), None);
+    }
+
+    #[test]
+    fn test_available_platforms() {
+        assert_eq!(available_platforms(&[version()]), vec!["PAPER", "VELOCITY"]);
     }
 }
@@ -1,18 +1,157 @@
 // Shared HTTP client utilities
 
+use crate::config;
+use crate::error::MpmError;
+use crate::sources::cache::{self, CacheBehavior};
+use crate::ui;
 use anyhow::Result;
-use reqwest::{Client, Response, StatusCode};
+use log::{debug, trace};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, RANGE, RETRY_AFTER};
+use reqwest::{Client, NoProxy, Proxy, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use std::time::Duration;
 
 /// User-Agent string for all HTTP requests
 const USER_AGENT: &str = concat!("mpm/", env!("CARGO_PKG_VERSION"));
 
+/// Delay before the first retry; doubled after every attempt (1s, 2s, 4s, ...)
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound on a server-requested `Retry-After` delay
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Environment variable holding a GitHub personal access token
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Environment variable holding a Modrinth personal access token
+pub const MODRINTH_TOKEN_ENV: &str = "MODRINTH_TOKEN";
+
+/// Environment variable holding a Hangar API key
+pub const HANGAR_API_KEY_ENV: &str = "HANGAR_API_KEY";
+
+/// Proxy for HTTPS requests
+const HTTPS_PROXY_ENV: &str = "HTTPS_PROXY";
+
+/// Proxy for every request not covered by `HTTPS_PROXY`
+const HTTP_PROXY_ENV: &str = "HTTP_PROXY";
+
 lazy_static::lazy_static! {
     /// Shared HTTP client with proper User-Agent
-    static ref CLIENT: Client = Client::builder()
-        .user_agent(USER_AGENT)
+    static ref CLIENT: Client = build_client();
+
+    /// GitHub API client carrying the user's token, if one is configured
+    static ref GITHUB_CLIENT: Option<Client> = github_token()
+        .and_then(|token| token_client(&format!("Bearer {}", token), GITHUB_TOKEN_ENV));
+
+    /// Modrinth API client carrying the user's token, if one is set
+    ///
+    /// Modrinth takes the bare token, without a `Bearer` prefix.
+    static ref MODRINTH_CLIENT: Option<Client> =
+        modrinth_token().and_then(|token| token_client(&token, MODRINTH_TOKEN_ENV));
+}
+
+/// A client sending `authorization` with every request
+///
+/// Fails if the token read from `token_env` isn't a valid header value,
+/// e.g. because it contains a newline.
+pub fn authenticated_client(authorization: &str, token_env: &str) -> Result<Client> {
+    let mut auth = HeaderValue::from_str(authorization).map_err(|_| {
+        anyhow::anyhow!(
+            "{} contains characters that can't be sent in an HTTP header",
+            token_env
+        )
+    })?;
+    auth.set_sensitive(true);
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, auth);
+
+    Ok(client_builder(config::global().http_timeout_secs)
+        .default_headers(headers)
         .build()
-        .expect("Failed to create HTTP client");
+        .expect("Failed to create HTTP client"))
+}
+
+/// The authenticated client for a token, or None with a warning if the
+/// token can't be used, so requests fall back to the anonymous client
+fn token_client(authorization: &str, token_env: &str) -> Option<Client> {
+    match authenticated_client(authorization, token_env) {
+        Ok(client) => Some(client),
+        Err(e) => {
+            ui::warning(&format!("{}. Sending requests without it.", e));
+            None
+        }
+    }
+}
+
+/// Build the shared client
+///
+/// # Panics
+/// If a proxy environment variable holds a malformed URL, so a bad proxy
+/// setting stops mpm at startup instead of surfacing as request failures.
+pub fn build_client() -> Client {
+    client_with_timeout(config::global().http_timeout_secs)
+}
+
+/// A client whose requests each time out after `timeout_secs` (0 for no timeout)
+pub fn client_with_timeout(timeout_secs: u64) -> Client {
+    client_builder(timeout_secs)
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// Builder with the settings shared by every client
+fn client_builder(timeout_secs: u64) -> reqwest::ClientBuilder {
+    let mut builder = Client::builder().user_agent(USER_AGENT);
+    for proxy in proxies_from_env() {
+        builder = builder.proxy(proxy);
+    }
+    match timeout_secs {
+        0 => builder,
+        secs => builder.timeout(Duration::from_secs(secs)),
+    }
+}
+
+/// Proxies from `HTTPS_PROXY` (HTTPS only) and `HTTP_PROXY` (everything else),
+/// skipping the hosts in `NO_PROXY`
+///
+/// Lowercase variable names are accepted too. Earlier proxies take precedence,
+/// so HTTPS requests use `HTTPS_PROXY` when both are set.
+fn proxies_from_env() -> Vec<Proxy> {
+    let no_proxy = NoProxy::from_env();
+    let mut proxies = Vec::new();
+
+    if let Some(url) = proxy_env(HTTPS_PROXY_ENV) {
+        let proxy = Proxy::https(&url)
+            .unwrap_or_else(|e| panic!("Invalid {} '{}': {}", HTTPS_PROXY_ENV, url, e));
+        proxies.push(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(url) = proxy_env(HTTP_PROXY_ENV) {
+        let proxy = Proxy::all(&url)
+            .unwrap_or_else(|e| panic!("Invalid {} '{}': {}", HTTP_PROXY_ENV, url, e));
+        proxies.push(proxy.no_proxy(no_proxy));
+    }
+
+    proxies
+}
+
+/// A proxy variable by its upper- or lowercase name, ignoring empty values
+fn proxy_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .or_else(|_| std::env::var(name.to_lowercase()))
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+}
+
+/// The proxy HTTPS requests go through, with any password hidden
+pub fn proxy_description() -> Option<String> {
+    let url = proxy_env(HTTPS_PROXY_ENV).or_else(|| proxy_env(HTTP_PROXY_ENV))?;
+    Some(match reqwest::Url::parse(&url) {
+        Ok(mut parsed) if parsed.password().is_some() => {
+            let _ = parsed.set_password(Some("****"));
+            parsed.to_string()
+        }
+        _ => url,
+    })
 }
 
 /// Get a reference to the shared HTTP client
@@ -20,16 +159,189 @@ pub fn client() -> &'static Client {
     &CLIENT
 }
 
-/// Fetch JSON from a URL and deserialize it
-pub async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T> {
-    let response: Response = CLIENT.get(url).send().await?;
+/// Get the client to use for API requests to a given source
+///
+/// GitHub requests are authenticated when `GITHUB_TOKEN` is set, raising the
+/// rate limit from 60 to 5000 requests per hour, and Modrinth requests when
+/// `MODRINTH_TOKEN` is set. Every other source uses the shared anonymous client.
+pub fn client_for_source(source: &str) -> &'static Client {
+    let authenticated = match source {
+        "github" => GITHUB_CLIENT.as_ref(),
+        "modrinth" => MODRINTH_CLIENT.as_ref(),
+        _ => None,
+    };
+    authenticated.unwrap_or(&CLIENT)
+}
 
-    if response.status() == StatusCode::NOT_FOUND {
-        anyhow::bail!("Resource not found: {}", url);
+/// Whether `client_for_source` sends a token for this source
+pub fn is_authenticated(source: &str) -> bool {
+    !std::ptr::eq(client_for_source(source), client())
+}
+
+/// GitHub token from the environment or the global config, ignoring empty values
+pub fn github_token() -> Option<String> {
+    std::env::var(GITHUB_TOKEN_ENV)
+        .ok()
+        .or_else(|| config::global().github_token.clone())
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Hangar API key from the environment or the global config, ignoring empty values
+pub fn hangar_api_key() -> Option<String> {
+    std::env::var(HANGAR_API_KEY_ENV)
+        .ok()
+        .or_else(|| config::global().hangar_api_key.clone())
+        .filter(|key| !key.trim().is_empty())
+}
+
+/// Modrinth token from the environment, ignoring empty values
+pub fn modrinth_token() -> Option<String> {
+    std::env::var(MODRINTH_TOKEN_ENV)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Send a GET request, retrying transient failures with exponential backoff
+///
+/// Connection errors, timeouts, 429, 502, 503 and 504 are retried up to
+/// `http.max_retries` times (3 by default). A 429 waits for the server's
+/// `Retry-After` when given. Every other response, including 403 and 404, is
+/// returned immediately for the caller to handle.
+async fn send_with_retry(client: &Client, url: &str) -> Result<Response> {
+    send_request_with_retry(url, || client.get(url)).await
+}
+
+/// Send the request built by `request`, retrying as `send_with_retry` does
+///
+/// Log lines name `url`. Query parameters the builder adds beyond it are
+/// treated as secret, so errors leave out the request URL entirely.
+async fn send_request_with_retry(
+    url: &str,
+    request: impl Fn() -> RequestBuilder,
+) -> Result<Response> {
+    let max_retries = config::global().http.max_retries;
+    let mut delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 0;
+
+    loop {
+        let (client, request) = request().build_split();
+        let request = request?;
+        let secret_query = request.url().query().is_some() && !url.contains('?');
+        trace!(
+            "{} {} request headers: {:?}",
+            request.method(),
+            url,
+            request.headers()
+        );
+        let result = client
+            .execute(request)
+            .await
+            .map_err(|e| if secret_query { e.without_url() } else { e });
+        if let Ok(response) = &result {
+            trace!(
+                "{} from {} response headers: {:?}",
+                response.status(),
+                url,
+                response.headers()
+            );
+        }
+        let (reason, wait) = match &result {
+            Ok(response) if is_transient_status(response.status()) => (
+                response.status().to_string(),
+                retry_after(response).unwrap_or(delay),
+            ),
+            Err(e) if e.is_connect() || e.is_timeout() => (e.to_string(), delay),
+            _ => return Ok(result?),
+        };
+
+        if attempt >= max_retries {
+            return Ok(result?);
+        }
+        attempt += 1;
+        debug!(
+            "Request to {} failed ({}), retrying in {}s (attempt {}/{})",
+            url,
+            reason,
+            wait.as_secs(),
+            attempt,
+            max_retries
+        );
+        tokio::time::sleep(wait).await;
+        delay *= 2;
+    }
+}
+
+/// Whether a status code indicates a failure worth retrying
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Delay requested by a `Retry-After` header given in seconds
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds: u64 = response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Fetch JSON from a URL and deserialize it
+///
+/// With a caching behavior, a recent response for the same URL is reused
+/// instead of making the request (see `sources::cache`).
+pub async fn fetch_json<T: DeserializeOwned>(url: &str, cache: CacheBehavior) -> Result<T> {
+    match cache.ttl() {
+        Some(ttl) => {
+            // Cache the raw JSON so response types don't need to be serializable
+            let value: serde_json::Value =
+                cache::get_cached(url, ttl, fetch_json_with(&CLIENT, url)).await?;
+            Ok(serde_json::from_value(value)?)
+        }
+        None => fetch_json_with(&CLIENT, url).await,
     }
+}
+
+/// Fetch JSON using a specific client (see `client_for_source`)
+pub async fn fetch_json_with<T: DeserializeOwned>(client: &Client, url: &str) -> Result<T> {
+    let response: Response = send_with_retry(client, url).await?;
+    json_response(url, response).await
+}
+
+/// Send a GET request with a given client, retrying transient failures
+///
+/// For callers that need the response headers; see `json_response`. Pick
+/// the client with `client_for_source`.
+pub async fn get_with(client: &Client, url: &str) -> Result<Response> {
+    send_with_retry(client, url).await
+}
 
+/// Send a POST request with a given client, retrying transient failures
+///
+/// `query` is for secrets such as API keys: it is added to the request but
+/// never appears in log lines or errors, which name only `url`.
+pub async fn post_with(client: &Client, url: &str, query: &[(&str, &str)]) -> Result<Response> {
+    let full_url = reqwest::Url::parse_with_params(url, query)?;
+    send_request_with_retry(url, || client.post(full_url.clone())).await
+}
+
+/// Deserialize a JSON response, failing on an unsuccessful status
+pub async fn json_response<T: DeserializeOwned>(url: &str, response: Response) -> Result<T> {
     if !response.status().is_success() {
-        anyhow::bail!("HTTP request failed: {} ({})", url, response.status());
+        return Err(MpmError::NetworkError {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        }
+        .into());
     }
 
     let result = response.json().await?;
@@ -37,16 +349,19 @@ pub async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T> {
 }
 
 /// Fetch JSON from a URL, returning None for 404 errors
-#[allow(dead_code)]
 pub async fn fetch_json_optional<T: DeserializeOwned>(url: &str) -> Result<Option<T>> {
-    let response: Response = CLIENT.get(url).send().await?;
+    let response: Response = send_with_retry(&CLIENT, url).await?;
 
     if response.status() == StatusCode::NOT_FOUND {
         return Ok(None);
     }
 
     if !response.status().is_success() {
-        anyhow::bail!("HTTP request failed: {} ({})", url, response.status());
+        return Err(MpmError::NetworkError {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        }
+        .into());
     }
 
     let result = response.json().await?;
@@ -56,7 +371,7 @@ pub async fn fetch_json_optional<T: DeserializeOwned>(url: &str) -> Result<Optio
 /// Fetch raw bytes from a URL
 #[allow(dead_code)]
 pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
-    let response: Response = CLIENT.get(url).send().await?;
+    let response: Response = send_with_retry(&CLIENT, url).await?;
 
     if !response.status().is_success() {
         anyhow::bail!("HTTP request failed: {} ({})", url, response.status());
@@ -70,7 +385,7 @@ pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
 /// Extracts filename from Content-Disposition header or URL
 #[allow(dead_code)]
 pub async fn download_file(url: &str) -> Result<(Vec<u8>, String)> {
-    let response: Response = CLIENT.get(url).send().await?;
+    let response: Response = send_with_retry(&CLIENT, url).await?;
 
     if !response.status().is_success() {
         anyhow::bail!("Download failed: {} ({})", url, response.status());
@@ -84,10 +399,22 @@ pub async fn download_file(url: &str) -> Result<(Vec<u8>, String)> {
 
 /// Download a file with full response access for custom handling
 pub async fn download_with_response(url: &str) -> Result<Response> {
-    let response: Response = CLIENT.get(url).send().await?;
+    let response: Response = send_with_retry(&CLIENT, url).await?;
     Ok(response)
 }
 
+/// Download a file starting at byte `offset`, to resume an interrupted download
+///
+/// Servers that support ranges answer with 206 Partial Content and only the
+/// remaining bytes; others send the whole file with 200, so callers must
+/// check the status before appending.
+pub async fn download_from_offset(url: &str, offset: u64) -> Result<Response> {
+    send_request_with_retry(url, || {
+        CLIENT.get(url).header(RANGE, format!("bytes={}-", offset))
+    })
+    .await
+}
+
 /// Extract filename from Content-Disposition header or URL
 pub fn extract_filename(response: &Response, url: &str) -> String {
     if let Some(header) = response
@@ -0,0 +1,97 @@
+// Local file source implementation for vendored or private JARs
+
+use crate::config;
+use crate::jar;
+use crate::sources::source_trait::{PluginSource, ResolvedVersion, SearchResult};
+use crate::sources::version_matcher;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+/// Prefix marking a lockfile URL as a path on disk
+const URL_PREFIX: &str = "local:";
+
+/// Version recorded when the JAR has no readable plugin.yml version
+const FALLBACK_VERSION: &str = "local";
+
+pub struct LocalSource;
+
+impl LocalSource {
+    /// Resolve a path from the manifest against the project root
+    pub fn resolve_path(path: &str) -> PathBuf {
+        let path = Path::new(path);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            Path::new(&config::config_dir()).join(path)
+        }
+    }
+
+    /// Filesystem path for a locked `local:<path>` URL
+    pub fn path_from_url(url: &str) -> Option<PathBuf> {
+        url.strip_prefix(URL_PREFIX).map(Self::resolve_path)
+    }
+}
+
+#[async_trait]
+impl PluginSource for LocalSource {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn validate_plugin_id(&self, plugin_id: &str) -> anyhow::Result<()> {
+        if plugin_id.trim().is_empty() {
+            anyhow::bail!("Local plugin path cannot be empty");
+        }
+        Ok(())
+    }
+
+    async fn search(&self, _query: &str, _limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        anyhow::bail!("The local source does not support searching")
+    }
+
+    async fn resolve_version(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        _minecraft_version: Option<&str>,
+    ) -> anyhow::Result<ResolvedVersion> {
+        let path = Self::resolve_path(plugin_id);
+        if !path.is_file() {
+            anyhow::bail!("Local plugin file '{}' not found", path.display());
+        }
+
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid local plugin path '{}'", plugin_id))?
+            .to_string();
+
+        // The version comes from the JAR itself; there is only ever one
+        let version = jar::read_plugin_yml_from_jar(&path)
+            .ok()
+            .and_then(|(_, version)| version)
+            .unwrap_or_else(|| FALLBACK_VERSION.to_string());
+        if let Some(requested) = requested_version
+            && !version_matcher::parse_constraint(requested)?.matches(&version)
+        {
+            anyhow::bail!(
+                "Local plugin '{}' is version '{}', which does not match '{}'",
+                plugin_id,
+                version,
+                requested
+            );
+        }
+
+        Ok(ResolvedVersion {
+            version,
+            filename,
+            url: format!("{}{}", URL_PREFIX, plugin_id),
+            urls: Vec::new(),
+            hash: jar::compute_sha256(&path)?,
+            size_bytes: Some(std::fs::metadata(&path)?.len()),
+            canonical_id: None,
+            zip_entry: None,
+            details: Default::default(),
+        })
+    }
+}
@@ -1,5 +1,7 @@
 // Sources module for package source implementations
 
+use crate::config;
+use crate::error::MpmError;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -8,6 +10,7 @@ pub mod source_trait;
 pub mod version_matcher;
 
 // Shared utilities (new)
+pub mod cache;
 pub mod hash;
 pub mod http;
 pub mod search;
@@ -17,17 +20,27 @@ pub mod version_selector;
 // Source implementations
 pub mod github;
 pub mod hangar;
+pub mod local;
 pub mod modrinth;
 pub mod spigot;
+pub mod url;
 
 pub use github::GitHubSource;
 pub use hangar::HangarSource;
+pub use local::LocalSource;
 pub use modrinth::ModrinthSource;
 pub use spigot::SpigotSource;
+pub use url::UrlSource;
 
 // Re-export the trait and types
 #[allow(unused_imports)] // ResolvedVersion is part of the public API
-pub use source_trait::{PluginSource, ResolvedVersion};
+pub use source_trait::{
+    AvailableVersion, PluginInfo, PluginSource, ResolutionDetails, ResolveOptions, ResolvedVersion,
+    SearchResult,
+};
+
+/// Sources tried, in order, when a plugin is given without a source
+const DEFAULT_PRIORITY: [&str; 4] = ["hangar", "modrinth", "spigot", "github"];
 
 /// Registry for plugin sources
 pub struct SourceRegistry {
@@ -46,6 +59,9 @@ impl SourceRegistry {
         registry.register(Arc::new(HangarSource));
         registry.register(Arc::new(SpigotSource));
         registry.register(Arc::new(GitHubSource));
+        // Only used when requested explicitly with a `url:` or `local:` prefix
+        registry.register(Arc::new(UrlSource));
+        registry.register(Arc::new(LocalSource));
 
         registry
     }
@@ -60,36 +76,25 @@ impl SourceRegistry {
 
     pub fn get_or_error(&self, source_name: &str) -> anyhow::Result<&Arc<dyn PluginSource>> {
         self.get(source_name).ok_or_else(|| {
-            anyhow::anyhow!(
-                "Unsupported source: '{}'. Supported sources: {}",
-                source_name,
-                self.sources
-                    .keys()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
+            MpmError::SourceNotSupported {
+                source: source_name.to_string(),
+                supported: self.sources.keys().cloned().collect(),
+            }
+            .into()
         })
     }
 
     /// Get sources in priority order for searching
-    /// Priority: hangar > modrinth > spigot > github
+    /// Default priority: hangar > modrinth > spigot > github, overridable
+    /// with `source_priority` in the global config
     pub fn get_priority_order(&self) -> Vec<&Arc<dyn PluginSource>> {
-        let mut sources = Vec::new();
-        // Add sources in priority order
-        if let Some(source) = self.get("hangar") {
-            sources.push(source);
-        }
-        if let Some(source) = self.get("modrinth") {
-            sources.push(source);
-        }
-        if let Some(source) = self.get("spigot") {
-            sources.push(source);
-        }
-        if let Some(source) = self.get("github") {
-            sources.push(source);
+        match &config::global().source_priority {
+            Some(priority) => priority.iter().filter_map(|name| self.get(name)).collect(),
+            None => DEFAULT_PRIORITY
+                .iter()
+                .filter_map(|name| self.get(name))
+                .collect(),
         }
-        sources
     }
 }
 
@@ -1,26 +1,227 @@
 // Modrinth source implementation
 
+use crate::constants;
+use crate::error::MpmError;
+use crate::sources::cache::{self, CacheBehavior};
 use crate::sources::hash::HashAlgorithm;
 use crate::sources::http;
-use crate::sources::source_trait::{PluginSource, ResolvedVersion};
+use crate::sources::source_trait::{
+    AvailableVersion, PluginInfo, PluginSource, ResolveOptions, ResolvedVersion, SearchResult,
+};
 use crate::sources::version_data::{DownloadInfo, NormalizedVersion};
 use crate::sources::version_selector::{self, SelectionConfig};
+use crate::ui;
 use async_trait::async_trait;
+use log::{debug, warn};
+use regex::Regex;
+use reqwest::header::HeaderMap;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long project and version responses are reused from the cache
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Most similar slugs suggested for a project that doesn't exist
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Requests left in the current rate-limit window
+const RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+
+/// Seconds until the rate-limit window resets
+const RATELIMIT_RESET: &str = "x-ratelimit-reset";
+
+lazy_static::lazy_static! {
+    /// Rate limit reported by the last Modrinth response, shared by all requests
+    static ref RATE_LIMIT: tokio::sync::Mutex<RateLimit> =
+        tokio::sync::Mutex::new(RateLimit::default());
+
+    /// Markdown syntax removed from changelogs, with the text to put in its place
+    static ref MARKDOWN_RULES: Vec<(Regex, &'static str)> = [
+        (r"<[^>]+>", ""),
+        (r"!?\[([^\]]*)\]\([^)]*\)", "$1"),
+        (r"(?m)^ {0,3}#{1,6}[ \t]*", ""),
+        (r"(?m)^ {0,3}>[ \t]?", ""),
+        (r"(?m)^([ \t]*)[*+][ \t]+", "$1- "),
+        (r"\*\*|__|`", ""),
+        (r"\*([^*\s][^*]*)\*", "$1"),
+        (r"\n{3,}", "\n\n"),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect();
+}
+
+/// Modrinth's rate limit as of the last response
+#[derive(Debug, Default)]
+struct RateLimit {
+    remaining: Option<u64>,
+    reset_at: Option<Instant>,
+    /// Whether the user has been told requests are being held back
+    warned: bool,
+}
+
+impl RateLimit {
+    /// Record the limit headers of a response; responses without them are ignored
+    fn record(&mut self, headers: &HeaderMap, now: Instant) {
+        let header =
+            |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+        if let Some(remaining) = header(RATELIMIT_REMAINING) {
+            self.remaining = Some(remaining);
+            self.reset_at = header(RATELIMIT_RESET).map(|secs| now + Duration::from_secs(secs));
+        }
+    }
+
+    /// How long to wait before the next request, if the limit is used up
+    fn delay(&self, now: Instant) -> Option<Duration> {
+        match (self.remaining, self.reset_at) {
+            (Some(0), Some(reset_at)) if reset_at > now => Some(reset_at - now),
+            _ => None,
+        }
+    }
+}
+
+/// Client for api.modrinth.com, authenticated when MODRINTH_TOKEN is set
+pub fn modrinth_client() -> &'static reqwest::Client {
+    http::client_for_source("modrinth")
+}
+
+/// Fetch JSON from the Modrinth API, waiting out its rate limit
+///
+/// Once `X-Ratelimit-Remaining` reaches 0, requests wait until the window
+/// resets instead of running into `429 Too Many Requests`. Cached responses
+/// don't count against the limit.
+async fn modrinth_fetch_json<T: DeserializeOwned>(
+    url: &str,
+    cache: CacheBehavior,
+) -> anyhow::Result<T> {
+    match cache.ttl() {
+        Some(ttl) => {
+            let value: serde_json::Value =
+                cache::get_cached(url, ttl, fetch_rate_limited(url)).await?;
+            Ok(serde_json::from_value(value)?)
+        }
+        None => fetch_rate_limited(url).await,
+    }
+}
+
+async fn fetch_rate_limited<T: DeserializeOwned>(url: &str) -> anyhow::Result<T> {
+    let response = send_rate_limited(url).await?;
+    http::json_response(url, response).await
+}
+
+async fn send_rate_limited(url: &str) -> anyhow::Result<reqwest::Response> {
+    {
+        // Holding the lock while sleeping queues every other request behind this one
+        let mut rate_limit = RATE_LIMIT.lock().await;
+        if let Some(delay) = rate_limit.delay(Instant::now()) {
+            if !rate_limit.warned {
+                ui::warning(&format!(
+                    "Modrinth rate limit reached, waiting {}s before continuing",
+                    delay.as_secs().max(1)
+                ));
+                rate_limit.warned = true;
+            }
+            debug!("Waiting {:?} for the Modrinth rate limit to reset", delay);
+            tokio::time::sleep(delay).await;
+            rate_limit.remaining = None;
+        }
+    }
+
+    let response = http::get_with(modrinth_client(), url).await?;
+    RATE_LIMIT
+        .lock()
+        .await
+        .record(response.headers(), Instant::now());
+    Ok(response)
+}
 
 #[derive(Debug, Deserialize)]
 pub struct Project {
-    #[allow(dead_code)]
     pub id: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub license: Option<License>,
+}
+
+impl Project {
+    /// Whether `plugin_id` is this project's slug or ID (slugs ignore case)
+    fn matches(&self, plugin_id: &str) -> bool {
+        self.id == plugin_id || self.slug.eq_ignore_ascii_case(plugin_id)
+    }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct License {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamMember {
+    role: String,
+    user: TeamUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    slug: String,
+    title: String,
+    description: String,
+    downloads: u64,
+    /// ID of the project's newest version
+    #[serde(default)]
+    latest_version: Option<String>,
+}
+
+/// Just enough of a version to name it
+#[derive(Debug, Deserialize)]
+struct VersionName {
+    id: String,
+    version_number: String,
+}
+
+/// Loaders that identify server plugins (as opposed to client mods) in search facets
+const PLUGIN_LOADERS: &[&str] = &[
+    "bukkit",
+    "spigot",
+    "paper",
+    "purpur",
+    "folia",
+    "bungeecord",
+    "waterfall",
+    "velocity",
+];
+
 #[derive(Debug, Deserialize)]
 pub struct Version {
+    #[serde(default)]
+    pub project_id: String,
     pub version_number: String,
     pub date_published: String,
     #[serde(rename = "game_versions")]
     pub game_versions: Vec<String>,
     pub files: Vec<VersionFile>,
+    /// Release notes in Markdown
+    #[serde(default)]
+    pub changelog: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,6 +229,11 @@ pub struct VersionFile {
     pub filename: String,
     pub url: String,
     pub hashes: FileHashes,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// The main download; other files are e.g. sources or API JARs
+    #[serde(default)]
+    pub primary: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,8 +245,10 @@ pub struct ModrinthSource;
 
 impl ModrinthSource {
     /// Normalize a Modrinth API version to our common format
+    ///
+    /// Uses the version's primary file, or its first file if none is marked.
     fn normalize_version(v: &Version) -> Option<NormalizedVersion> {
-        let file = v.files.first()?;
+        let file = v.files.iter().find(|f| f.primary).or(v.files.first())?;
         Some(NormalizedVersion {
             version: v.version_number.clone(),
             published_at: v.date_published.clone(),
@@ -49,26 +257,132 @@ impl ModrinthSource {
                 &file.url,
                 &file.filename,
                 crate::sources::hash::format_hash(&file.hashes.sha512, HashAlgorithm::Sha512),
-            ),
+            )
+            .with_size(file.size),
         })
     }
 
-    /// Fetch versions from the Modrinth API
-    async fn fetch_versions(
+    /// Fetch project details, mapping any failure to a not-found error
+    ///
+    /// Redirects are followed, so the project can come back under a different
+    /// slug than the one asked for; that is warned about, and callers should
+    /// use the returned `id` from then on.
+    async fn fetch_project(plugin_id: &str) -> anyhow::Result<Project> {
+        let project_url = format!("https://api.modrinth.com/v2/project/{}", plugin_id);
+        let project: Project =
+            modrinth_fetch_json(&project_url, CacheBehavior::CacheFor(CACHE_TTL))
+                .await
+                .map_err(|_| {
+                    anyhow::Error::from(MpmError::PluginNotFound {
+                        source: Some("modrinth".to_string()),
+                        id: plugin_id.to_string(),
+                    })
+                })?;
+        if !project.matches(plugin_id) {
+            warn!(
+                "Modrinth: slug '{}' resolved to project '{}'. Consider updating your manifest.",
+                plugin_id, project.slug
+            );
+        }
+        Ok(project)
+    }
+
+    /// Version numbers for version IDs, looked up in one request
+    ///
+    /// Search hits only carry the ID of a project's latest version. A failed
+    /// lookup leaves the versions out rather than failing the search.
+    async fn version_numbers(ids: &[&str]) -> HashMap<String, String> {
+        if ids.is_empty() {
+            return HashMap::new();
+        }
+        let url = match serde_json::to_string(ids) {
+            Ok(ids) => format!(
+                "https://api.modrinth.com/v2/versions?ids={}",
+                urlencoding::encode(&ids)
+            ),
+            Err(_) => return HashMap::new(),
+        };
+        match modrinth_fetch_json::<Vec<VersionName>>(&url, CacheBehavior::CacheFor(CACHE_TTL))
+            .await
+        {
+            Ok(versions) => versions
+                .into_iter()
+                .map(|v| (v.id, v.version_number))
+                .collect(),
+            Err(e) => {
+                debug!("Failed to look up latest versions: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Slugs of a few projects resembling `query`, to suggest when no
+    /// project has that slug
+    pub async fn suggest(query: &str) -> anyhow::Result<Vec<String>> {
+        let results = ModrinthSource.search(query, MAX_SUGGESTIONS + 1).await?;
+        Ok(results
+            .into_iter()
+            .map(|result| result.id)
+            .filter(|id| !id.eq_ignore_ascii_case(query))
+            .take(MAX_SUGGESTIONS)
+            .collect())
+    }
+
+    /// Username of the project owner, or the first team member if no owner is listed
+    async fn fetch_author(plugin_id: &str) -> Option<String> {
+        let members_url = format!("https://api.modrinth.com/v2/project/{}/members", plugin_id);
+        let members: Vec<TeamMember> =
+            modrinth_fetch_json(&members_url, CacheBehavior::CacheFor(CACHE_TTL))
+                .await
+                .ok()?;
+        members
+            .iter()
+            .find(|m| m.role == "Owner")
+            .or(members.first())
+            .map(|m| m.user.username.clone())
+    }
+
+    /// Build the version list URL, filtered by Minecraft version and loaders when given
+    fn versions_url(
         plugin_id: &str,
         minecraft_version: Option<&str>,
-    ) -> anyhow::Result<Vec<NormalizedVersion>> {
-        let mut url = format!("https://api.modrinth.com/v2/project/{}/version", plugin_id);
+        loaders: &[String],
+    ) -> anyhow::Result<String> {
+        let mut params = Vec::new();
 
         // Add game_versions filter if Minecraft version is provided
         if let Some(mc_version) = minecraft_version {
             let json_array = serde_json::to_string(&[mc_version])
                 .map_err(|e| anyhow::anyhow!("Failed to encode Minecraft version: {}", e))?;
-            let encoded = urlencoding::encode(&json_array);
-            url = format!("{}?game_versions={}", url, encoded);
+            params.push(format!(
+                "game_versions={}",
+                urlencoding::encode(&json_array)
+            ));
+        }
+
+        if !loaders.is_empty() {
+            let json_array = serde_json::to_string(loaders)
+                .map_err(|e| anyhow::anyhow!("Failed to encode loaders: {}", e))?;
+            params.push(format!("loaders={}", urlencoding::encode(&json_array)));
         }
 
-        let versions: Vec<Version> = http::fetch_json(&url).await?;
+        let url = format!("https://api.modrinth.com/v2/project/{}/version", plugin_id);
+        Ok(if params.is_empty() {
+            url
+        } else {
+            format!("{}?{}", url, params.join("&"))
+        })
+    }
+
+    /// Fetch versions from the Modrinth API
+    async fn fetch_versions(
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+        loaders: &[String],
+    ) -> anyhow::Result<Vec<NormalizedVersion>> {
+        let url = Self::versions_url(plugin_id, minecraft_version, loaders)?;
+        let versions: Vec<Version> =
+            modrinth_fetch_json(&url, CacheBehavior::CacheFor(CACHE_TTL)).await?;
         Ok(versions
             .iter()
             .filter_map(Self::normalize_version)
@@ -76,6 +390,93 @@ impl ModrinthSource {
     }
 }
 
+/// Identify a file by its SHA-512 hash (hex, without prefix)
+///
+/// Returns the project's slug and the version the file belongs to, or `None`
+/// if Modrinth doesn't know the file. Modrinth only indexes SHA-1 and
+/// SHA-512 hashes, so a SHA-256 can't be looked up.
+pub async fn lookup_file(sha512: &str) -> anyhow::Result<Option<(String, ResolvedVersion)>> {
+    let url = format!(
+        "https://api.modrinth.com/v2/version_file/{}?algorithm=sha512",
+        sha512
+    );
+    let response = send_rate_limited(&url).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let version: Version = http::json_response(&url, response).await?;
+    let Some(resolved) = resolved_file(&version, sha512) else {
+        return Ok(None);
+    };
+
+    let project = ModrinthSource::fetch_project(&version.project_id).await?;
+    let slug = if project.slug.is_empty() {
+        project.id.clone()
+    } else {
+        project.slug
+    };
+    Ok(Some((slug, resolved.with_canonical_id(project.id))))
+}
+
+/// The file of a version with the given SHA-512 hash, as a resolved version
+fn resolved_file(version: &Version, sha512: &str) -> Option<ResolvedVersion> {
+    let file = version
+        .files
+        .iter()
+        .find(|f| f.hashes.sha512.eq_ignore_ascii_case(sha512))?;
+    Some(ResolvedVersion {
+        version: version.version_number.clone(),
+        filename: file.filename.clone(),
+        url: file.url.clone(),
+        urls: Vec::new(),
+        hash: crate::sources::hash::format_hash(&file.hashes.sha512, HashAlgorithm::Sha512),
+        size_bytes: file.size,
+        canonical_id: None,
+        zip_entry: None,
+        details: Default::default(),
+    })
+}
+
+/// Changelog of one version of a project, as Markdown
+///
+/// Returns `None` when the version exists but has no changelog.
+pub async fn changelog(plugin_id: &str, version: &str) -> anyhow::Result<Option<String>> {
+    let url = ModrinthSource::versions_url(plugin_id, None, &[])?;
+    let versions: Vec<Version> =
+        modrinth_fetch_json(&url, CacheBehavior::CacheFor(CACHE_TTL)).await?;
+    let version = versions
+        .into_iter()
+        .find(|v| v.version_number == version)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Version '{}' of '{}' not found on Modrinth",
+                version,
+                plugin_id
+            )
+        })?;
+    Ok(version.changelog.filter(|c| !c.trim().is_empty()))
+}
+
+/// Changelog Markdown as plain text for the terminal
+pub fn strip_markdown(markdown: &str) -> String {
+    let text = markdown.replace("\r\n", "\n");
+    let text = MARKDOWN_RULES
+        .iter()
+        .fold(text, |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        });
+    text.trim().to_string()
+}
+
+/// First non-empty line of a changelog, as plain text
+pub fn changelog_summary(markdown: &str) -> Option<String> {
+    strip_markdown(markdown)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
 #[async_trait]
 impl PluginSource for ModrinthSource {
     fn name(&self) -> &'static str {
@@ -89,30 +490,136 @@ impl PluginSource for ModrinthSource {
         Ok(())
     }
 
+    async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        // Facets are AND-ed across inner arrays and OR-ed within one
+        let loaders: Vec<String> = PLUGIN_LOADERS
+            .iter()
+            .map(|loader| format!("categories:{}", loader))
+            .collect();
+        let facets = serde_json::to_string(&[loaders])
+            .map_err(|e| anyhow::anyhow!("Failed to encode search facets: {}", e))?;
+
+        let url = format!(
+            "https://api.modrinth.com/v2/search?query={}&limit={}&facets={}",
+            urlencoding::encode(query),
+            limit,
+            urlencoding::encode(&facets)
+        );
+
+        let response: SearchResponse = modrinth_fetch_json(&url, CacheBehavior::Bypass).await?;
+        let version_ids: Vec<&str> = response
+            .hits
+            .iter()
+            .filter_map(|hit| hit.latest_version.as_deref())
+            .collect();
+        let version_numbers = Self::version_numbers(&version_ids).await;
+        Ok(response
+            .hits
+            .into_iter()
+            .map(|hit| SearchResult {
+                latest_version: hit
+                    .latest_version
+                    .and_then(|id| version_numbers.get(&id).cloned()),
+                name: hit.title,
+                id: hit.slug,
+                source: self.name().to_string(),
+                description: Some(hit.description),
+                downloads: Some(hit.downloads),
+            })
+            .collect())
+    }
+
+    async fn info(
+        &self,
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+    ) -> anyhow::Result<PluginInfo> {
+        let project = Self::fetch_project(plugin_id).await?;
+        // Team membership can be hidden, so a missing author isn't an error
+        let author = Self::fetch_author(&project.id).await;
+
+        // Info is for discovery, so list builds for every loader
+        let versions = Self::fetch_versions(&project.id, None, &[]).await?;
+        let config = SelectionConfig::new(plugin_id);
+        let compatible_versions =
+            version_selector::compatible_versions(versions.clone(), minecraft_version, &config);
+        let latest =
+            version_selector::select_version(versions, None, minecraft_version, &config).await?;
+
+        Ok(PluginInfo {
+            id: plugin_id.to_string(),
+            source: self.name().to_string(),
+            name: project.title,
+            description: project.description,
+            author,
+            license: project
+                .license
+                .map(|l| l.name.filter(|n| !n.is_empty()).unwrap_or(l.id)),
+            latest,
+            compatible_versions,
+        })
+    }
+
+    async fn list_versions(
+        &self,
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+        options: &ResolveOptions,
+    ) -> anyhow::Result<Vec<AvailableVersion>> {
+        let project_id = Self::fetch_project(plugin_id).await?.id;
+        let versions = Self::fetch_versions(&project_id, None, &options.loaders).await?;
+        let config = SelectionConfig::new(plugin_id);
+        Ok(version_selector::available_versions(
+            versions,
+            minecraft_version,
+            &config,
+        ))
+    }
+
     async fn resolve_version(
         &self,
         plugin_id: &str,
         requested_version: Option<&str>,
         minecraft_version: Option<&str>,
     ) -> anyhow::Result<ResolvedVersion> {
-        // Verify plugin exists by fetching project info
-        let project_url = format!("https://api.modrinth.com/v2/project/{}", plugin_id);
-        let _project: Project = http::fetch_json(&project_url)
+        let options = ResolveOptions {
+            loaders: constants::DEFAULT_LOADERS
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            ..Default::default()
+        };
+        self.resolve_version_with(plugin_id, requested_version, minecraft_version, &options)
             .await
-            .map_err(|_| anyhow::anyhow!("Plugin '{}' not found in Modrinth", plugin_id))?;
+    }
+
+    async fn resolve_version_with(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        minecraft_version: Option<&str>,
+        options: &ResolveOptions,
+    ) -> anyhow::Result<ResolvedVersion> {
+        let loaders = &options.loaders;
+
+        // Verify plugin exists by fetching project info, then use its canonical
+        // ID so a renamed slug doesn't redirect every later request
+        let project_id = Self::fetch_project(plugin_id).await?.id;
 
         // Fetch versions (filtered if MC version provided)
-        let mut versions = Self::fetch_versions(plugin_id, minecraft_version).await?;
+        let mut versions = Self::fetch_versions(&project_id, minecraft_version, loaders).await?;
+        let mut api_url = Self::versions_url(&project_id, minecraft_version, loaders)?;
 
         // If no versions with filter, try without for better error message
         let all_versions = if versions.is_empty() && minecraft_version.is_some() {
-            Self::fetch_versions(plugin_id, None).await?
+            Self::fetch_versions(&project_id, None, loaders).await?
         } else {
             Vec::new()
         };
 
         // Combine for version selection
         let versions_for_selection = if versions.is_empty() && !all_versions.is_empty() {
+            api_url = Self::versions_url(&project_id, None, loaders)?;
             all_versions
         } else {
             std::mem::take(&mut versions)
@@ -126,5 +633,136 @@ impl PluginSource for ModrinthSource {
             &config,
         )
         .await
+        .map(|resolved| resolved.with_canonical_id(project_id).with_api_url(api_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_rate_limit_delay() {
+        let now = Instant::now();
+        let mut headers = HeaderMap::new();
+        headers.insert(RATELIMIT_REMAINING, HeaderValue::from_static("5"));
+        headers.insert(RATELIMIT_RESET, HeaderValue::from_static("30"));
+
+        let mut rate_limit = RateLimit::default();
+        rate_limit.record(&headers, now);
+        assert_eq!(rate_limit.delay(now), None);
+
+        headers.insert(RATELIMIT_REMAINING, HeaderValue::from_static("0"));
+        rate_limit.record(&headers, now);
+        assert_eq!(rate_limit.delay(now), Some(Duration::from_secs(30)));
+        assert_eq!(rate_limit.delay(now + Duration::from_secs(31)), None);
+
+        // Responses without the headers leave the limit as it was
+        rate_limit.record(&HeaderMap::new(), now);
+        assert_eq!(rate_limit.delay(now), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_normalize_version_prefers_primary_file() {
+        let version: Version = serde_json::from_value(serde_json::json!({
+            "version_number": "7.3.0",
+            "date_published": "2024-01-01T00:00:00Z",
+            "game_versions": ["1.21"],
+            "files": [
+                {
+                    "filename": "worldedit-7.3.0-sources.jar",
+                    "url": "https://cdn.modrinth.com/sources.jar",
+                    "hashes": { "sha512": "aa" },
+                    "primary": false
+                },
+                {
+                    "filename": "worldedit-7.3.0.jar",
+                    "url": "https://cdn.modrinth.com/main.jar",
+                    "hashes": { "sha512": "bb" },
+                    "primary": true
+                }
+            ]
+        }))
+        .unwrap();
+        let normalized = ModrinthSource::normalize_version(&version).unwrap();
+        assert_eq!(normalized.download.url, "https://cdn.modrinth.com/main.jar");
+        assert_eq!(
+            normalized.download.filename.as_deref(),
+            Some("worldedit-7.3.0.jar")
+        );
+    }
+
+    #[test]
+    fn test_resolved_file_uses_the_file_with_the_hash() {
+        let version: Version = serde_json::from_value(serde_json::json!({
+            "project_id": "1u6JkXh5",
+            "version_number": "7.3.0",
+            "date_published": "2024-01-01T00:00:00Z",
+            "game_versions": ["1.21"],
+            "files": [
+                {
+                    "filename": "worldedit-7.3.0.jar",
+                    "url": "https://cdn.modrinth.com/main.jar",
+                    "hashes": { "sha512": "aa" },
+                    "primary": true
+                },
+                {
+                    "filename": "worldedit-bukkit-7.3.0.jar",
+                    "url": "https://cdn.modrinth.com/bukkit.jar",
+                    "hashes": { "sha512": "bb" }
+                }
+            ]
+        }))
+        .unwrap();
+        let resolved = resolved_file(&version, "BB").unwrap();
+        assert_eq!(resolved.version, "7.3.0");
+        assert_eq!(resolved.url, "https://cdn.modrinth.com/bukkit.jar");
+        assert_eq!(resolved.hash, "sha512:bb");
+        assert!(resolved_file(&version, "cc").is_none());
+    }
+
+    #[test]
+    fn test_project_matches_slug_or_id() {
+        let project = Project {
+            id: "1u6JkXh5".to_string(),
+            slug: "worldedit".to_string(),
+            title: None,
+            description: None,
+            license: None,
+        };
+        assert!(project.matches("worldedit"));
+        assert!(project.matches("WorldEdit"));
+        assert!(project.matches("1u6JkXh5"));
+        assert!(!project.matches("world-edit"));
+    }
+
+    #[test]
+    fn test_strip_markdown() {
+        let markdown = "## Changes\r\n\r\n* **Fixed** a crash with `/we`\r\n* See [the wiki](https://example.com)\r\n\r\n\r\n> Thanks to *everyone*<br>";
+        assert_eq!(
+            strip_markdown(markdown),
+            "Changes\n\n- Fixed a crash with /we\n- See the wiki\n\nThanks to everyone"
+        );
+        assert_eq!(
+            changelog_summary("\n\n# v7.3.1\nBug fixes").as_deref(),
+            Some("v7.3.1")
+        );
+        assert_eq!(changelog_summary("  \n"), None);
+    }
+
+    #[test]
+    fn test_versions_url_filters() {
+        assert_eq!(
+            ModrinthSource::versions_url("worldedit", None, &[]).unwrap(),
+            "https://api.modrinth.com/v2/project/worldedit/version"
+        );
+
+        let loaders = vec!["paper".to_string(), "spigot".to_string()];
+        assert_eq!(
+            ModrinthSource::versions_url("worldedit", Some("1.21"), &loaders).unwrap(),
+            "https://api.modrinth.com/v2/project/worldedit/version\
+             ?game_versions=%5B%221.21%22%5D&loaders=%5B%22paper%22%2C%22spigot%22%5D"
+        );
     }
 }
@@ -1,6 +1,8 @@
 // Search utilities for plugin sources
 
+use crate::sources::source_trait::SearchResult;
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
 /// Trait for items that can be searched
 pub trait Searchable {
@@ -78,6 +80,22 @@ pub fn rank_search_results_stable<T: Searchable>(results: &mut [T], query: &str)
     });
 }
 
+/// Merge results from several sources into a single ranked list
+///
+/// `results` must already be in source priority order. Duplicate names
+/// (case-insensitive) keep only the entry from the highest priority source,
+/// and exact name matches are moved to the front.
+pub fn merge_search_results(results: Vec<SearchResult>, query: &str) -> Vec<SearchResult> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<SearchResult> = results
+        .into_iter()
+        .filter(|r| seen.insert(r.name.to_lowercase()))
+        .collect();
+
+    rank_search_results_stable(&mut merged, query);
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +188,55 @@ mod tests {
 
         assert_eq!(items[0].name, "World Edit");
     }
+
+    fn result(name: &str, source: &str) -> SearchResult {
+        SearchResult {
+            name: name.to_string(),
+            id: name.to_lowercase(),
+            source: source.to_string(),
+            description: None,
+            downloads: None,
+            latest_version: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_dedupes_by_priority() {
+        let merged = merge_search_results(
+            vec![
+                result("LuckPerms", "hangar"),
+                result("LuckPermsGUI", "hangar"),
+                result("luckperms", "modrinth"),
+                result("Vault", "spigot"),
+            ],
+            "perms",
+        );
+
+        let names: Vec<_> = merged
+            .iter()
+            .map(|r| (r.name.as_str(), r.source.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("LuckPerms", "hangar"),
+                ("LuckPermsGUI", "hangar"),
+                ("Vault", "spigot")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_moves_exact_match_first() {
+        let merged = merge_search_results(
+            vec![
+                result("WorldEditSUI", "hangar"),
+                result("WorldEdit", "modrinth"),
+            ],
+            "worldedit",
+        );
+
+        assert_eq!(merged[0].name, "WorldEdit");
+        assert_eq!(merged[1].name, "WorldEditSUI");
+    }
 }
@@ -1,14 +1,137 @@
 // Trait definition for plugin sources
 
+use crate::sources::search::Searchable;
 use anyhow::Result;
+use serde::Serialize;
 
 /// Result of resolving a plugin version
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ResolvedVersion {
     pub version: String,
     pub filename: String,
     pub url: String,
+    /// Other URLs serving the same file, tried by sync when `url` fails
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
     pub hash: String,
+    /// File size, if the source reports it or the file was downloaded
+    pub size_bytes: Option<u64>,
+    /// The source's own identifier for the project (Modrinth project ID,
+    /// Hangar `author/slug`, Spigot resource ID, GitHub `owner/repo`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_id: Option<String>,
+    /// Path of the JAR inside `url` when it points to a ZIP archive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip_entry: Option<String>,
+    /// How the version was chosen, for `mpm lock --verbose`
+    #[serde(skip)]
+    pub details: ResolutionDetails,
+}
+
+impl ResolvedVersion {
+    /// Record the source's canonical identifier for the project
+    pub fn with_canonical_id(mut self, canonical_id: impl Into<String>) -> Self {
+        self.canonical_id = Some(canonical_id.into());
+        self
+    }
+
+    /// Record the API endpoint the versions were listed from
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.details.api_url = Some(api_url.into());
+        self
+    }
+}
+
+/// What a source saw while resolving a version; fields it can't tell are `None`
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionDetails {
+    pub api_url: Option<String>,
+    /// Versions the source listed
+    pub versions_total: Option<usize>,
+    /// Versions left after filtering by Minecraft version
+    pub versions_compatible: Option<usize>,
+    /// When the selected version was published
+    pub published_at: Option<String>,
+}
+
+/// One version of a plugin, as listed by `mpm add --list-versions`
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableVersion {
+    pub version: String,
+    /// When the version was published, as the source reports it
+    pub published_at: String,
+    /// Minecraft versions the version supports (empty if the source doesn't say)
+    pub mc_versions: Vec<String>,
+}
+
+/// Per-plugin filters applied when resolving a version
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    /// Mod/plugin loaders the build must run on (used by Modrinth)
+    pub loaders: Vec<String>,
+    /// Platform to download for, e.g. `PAPER` or `VELOCITY` (used by Hangar)
+    pub platform: Option<String>,
+    /// Accept pre-releases when picking the latest or a matching release (used by GitHub)
+    pub prerelease: bool,
+    /// Regex the release asset's filename must match (used by GitHub)
+    pub asset_pattern: Option<String>,
+    /// Least stable release channel to accept: RELEASE, BETA or ALPHA (used by Hangar)
+    pub channel: Option<String>,
+    /// Regex the filename of the JAR to extract from a ZIP asset must match (used by GitHub)
+    pub unzip: Option<String>,
+}
+
+/// A single project returned by a source's search endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    /// Display name of the project
+    pub name: String,
+    /// Identifier to pass to `mpm add <source>:<id>`
+    pub id: String,
+    pub source: String,
+    pub description: Option<String>,
+    /// Total downloads, if the source reports them
+    pub downloads: Option<u64>,
+    /// Latest plugin version, if the source includes it in search results
+    pub latest_version: Option<String>,
+}
+
+impl Searchable for SearchResult {
+    fn search_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Descriptive metadata about a plugin, as shown by `mpm info`
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    pub id: String,
+    pub source: String,
+    /// Display name, if different from the ID
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub license: Option<String>,
+    /// Latest version compatible with the requested Minecraft version
+    pub latest: ResolvedVersion,
+    /// All compatible versions, newest first (empty if the source can't list them)
+    pub compatible_versions: Vec<String>,
+}
+
+impl PluginInfo {
+    /// Info containing only what `resolve_version` can tell us
+    pub fn minimal(source: &str, plugin_id: &str, latest: ResolvedVersion) -> Self {
+        Self {
+            id: plugin_id.to_string(),
+            source: source.to_string(),
+            name: None,
+            description: None,
+            author: None,
+            license: None,
+            latest,
+            compatible_versions: Vec::new(),
+        }
+    }
 }
 
 /// Trait for plugin sources (Modrinth, Hangar, GitHub, etc.)
@@ -30,6 +153,52 @@ pub trait PluginSource: Send + Sync {
         minecraft_version: Option<&str>,
     ) -> Result<ResolvedVersion>;
 
+    /// Resolve a plugin version matching the plugin's loader and platform filters
+    ///
+    /// Sources only apply the filters their API understands (loaders for
+    /// Modrinth, platform for Hangar); the default implementation ignores them.
+    async fn resolve_version_with(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        minecraft_version: Option<&str>,
+        _options: &ResolveOptions,
+    ) -> Result<ResolvedVersion> {
+        self.resolve_version(plugin_id, requested_version, minecraft_version)
+            .await
+    }
+
+    /// Search the source for projects matching a free-text query
+    ///
+    /// # Arguments
+    /// * `query` - Search terms
+    /// * `limit` - Maximum number of results to return
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>>;
+
+    /// Fetch descriptive metadata about a plugin
+    ///
+    /// The default implementation only resolves the latest version; sources
+    /// whose APIs expose project details override it to fill in the rest.
+    async fn info(&self, plugin_id: &str, minecraft_version: Option<&str>) -> Result<PluginInfo> {
+        let latest = self
+            .resolve_version(plugin_id, None, minecraft_version)
+            .await?;
+        Ok(PluginInfo::minimal(self.name(), plugin_id, latest))
+    }
+
+    /// List a plugin's versions compatible with a Minecraft version, newest first
+    ///
+    /// Only sources with a version list endpoint implement this; the default
+    /// implementation fails.
+    async fn list_versions(
+        &self,
+        _plugin_id: &str,
+        _minecraft_version: Option<&str>,
+        _options: &ResolveOptions,
+    ) -> Result<Vec<AvailableVersion>> {
+        anyhow::bail!("Source '{}' can't list a plugin's versions", self.name())
+    }
+
     /// Get the source name (e.g., "modrinth", "hangar", "github")
     fn name(&self) -> &'static str;
 
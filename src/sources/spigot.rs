@@ -1,25 +1,85 @@
 // Spigot source implementation (via Spiget API)
 
+use crate::sources::cache::CacheBehavior;
 use crate::sources::hash::{self, HashAlgorithm};
 use crate::sources::http;
 use crate::sources::search::{self, Searchable};
-use crate::sources::source_trait::{PluginSource, ResolvedVersion};
+use crate::sources::source_trait::{
+    AvailableVersion, PluginSource, ResolveOptions, ResolvedVersion, SearchResult,
+};
 use crate::sources::version_data::{DownloadInfo, NormalizedVersion};
 use crate::sources::version_selector::{self, SelectionConfig};
 use async_trait::async_trait;
+use futures::future::join_all;
+use log::debug;
 use serde::Deserialize;
+use std::time::Duration;
+
+/// How long project and version responses are reused from the cache
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Versions per page, the most Spiget returns
+const VERSIONS_PAGE_SIZE: usize = 1000;
+
+/// Most version pages fetched for one resource, in case the page count is bogus
+const MAX_VERSION_PAGES: usize = 50;
 
 #[derive(Debug, Deserialize)]
 struct ResourceFile {
+    /// `external` for resources hosted off SpigotMC, else the file extension
+    #[serde(rename = "type", default)]
+    file_type: Option<String>,
     #[serde(rename = "externalUrl")]
     external_url: Option<String>,
 }
 
+impl ResourceFile {
+    /// Fail early for an external resource with no URL to download it from
+    ///
+    /// Spiget can't serve these, and SpigotMC's own download page is behind
+    /// Cloudflare, so resolving versions would only fail later at download.
+    fn check_downloadable(&self, resource_id: i64) -> anyhow::Result<()> {
+        if self.file_type.as_deref() == Some("external") && self.external_url.is_none() {
+            anyhow::bail!(
+                "SpigotMC resource {} uses Cloudflare protection with no external download URL. \
+                This plugin cannot be automatically managed. Use the `url:` source with a direct download link instead.",
+                resource_id
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Number of version pages, from Spiget's `X-Page-Count` header
+///
+/// A missing or malformed header means the first page is all there is.
+fn page_count(headers: &reqwest::header::HeaderMap) -> usize {
+    headers
+        .get("x-page-count")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, MAX_VERSION_PAGES)
+}
+
 #[derive(Debug, Deserialize)]
 struct Resource {
     id: i64,
     name: String,
     file: Option<ResourceFile>,
+    /// Short description shown under the resource title
+    #[serde(default)]
+    tag: Option<String>,
+    #[serde(default)]
+    downloads: Option<u64>,
+    /// The resource's current version, by ID only
+    #[serde(default)]
+    version: Option<VersionRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct VersionRef {
+    id: i64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -59,18 +119,48 @@ impl SpigotSource {
 
     /// Fetch versions from the Spiget API
     async fn fetch_versions(resource_id: i64) -> anyhow::Result<Vec<NormalizedVersion>> {
-        let url = format!(
-            "https://api.spiget.org/v2/resources/{}/versions?size=1000",
-            resource_id
-        );
-
-        let versions: Vec<Version> = http::fetch_json(&url).await?;
+        let versions = Self::get_all_versions(resource_id).await?;
         Ok(versions
             .iter()
             .map(|v| Self::normalize_version(v, resource_id))
             .collect())
     }
 
+    /// Fetch every page of a resource's versions, newest first
+    ///
+    /// Spiget returns at most 1000 versions per page, oldest first, so a
+    /// resource with more would otherwise never show its latest versions.
+    /// The first page's `X-Page-Count` header says how many pages to fetch;
+    /// the rest are fetched in parallel.
+    async fn get_all_versions(resource_id: i64) -> anyhow::Result<Vec<Version>> {
+        let url = Self::versions_url(resource_id);
+        let response = http::get_with(http::client_for_source("spigot"), &url).await?;
+        let pages = page_count(response.headers());
+        let mut versions: Vec<Version> = http::json_response(&url, response).await?;
+
+        let handles: Vec<_> = (2..=pages)
+            .map(|page| {
+                let url = format!("{}&page={}", Self::versions_url(resource_id), page);
+                tokio::spawn(async move {
+                    http::fetch_json::<Vec<Version>>(&url, CacheBehavior::CacheFor(CACHE_TTL)).await
+                })
+            })
+            .collect();
+        for handle in handles {
+            versions.extend(handle.await??);
+        }
+
+        versions.sort_by_key(|v| std::cmp::Reverse(v.release_date));
+        Ok(versions)
+    }
+
+    fn versions_url(resource_id: i64) -> String {
+        format!(
+            "https://api.spiget.org/v2/resources/{}/versions?size={}",
+            resource_id, VERSIONS_PAGE_SIZE
+        )
+    }
+
     /// Search for a resource by name with hyphen variations
     async fn search_resource(&self, search_name: &str) -> anyhow::Result<Resource> {
         // Try the original search name first
@@ -207,11 +297,23 @@ impl SpigotSource {
         let data = response.bytes().await?;
         let hash_str = hash::compute_hash(&data, HashAlgorithm::Sha256);
 
+        // A direct external JAR is a fallback for when Spiget's download fails
+        let urls = external_url
+            .filter(|ext| *ext != final_url && ext.ends_with(".jar"))
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+
         Ok(ResolvedVersion {
             version: version.version.clone(),
             filename,
             url: final_url,
+            urls,
             hash: hash_str,
+            size_bytes: Some(data.len() as u64),
+            canonical_id: None,
+            zip_entry: None,
+            details: Default::default(),
         })
     }
 }
@@ -229,6 +331,68 @@ impl PluginSource for SpigotSource {
         Ok(())
     }
 
+    async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let url = format!(
+            "https://api.spiget.org/v2/search/resources/{}?size={}&sort=-downloads",
+            urlencoding::encode(query),
+            limit
+        );
+
+        // Spiget answers 404 when nothing matches
+        let resources: Vec<Resource> = http::fetch_json_optional(&url).await?.unwrap_or_default();
+
+        // Results only name their current version by ID, so look each one up;
+        // a failed lookup just leaves the version out
+        let version_names = join_all(resources.iter().map(|resource| async move {
+            let version = resource.version.as_ref()?;
+            let url = format!(
+                "https://api.spiget.org/v2/resources/{}/versions/{}",
+                resource.id, version.id
+            );
+            match http::fetch_json::<Version>(&url, CacheBehavior::CacheFor(CACHE_TTL)).await {
+                Ok(version) => Some(version.name),
+                Err(e) => {
+                    debug!(
+                        "Failed to look up version of resource {}: {}",
+                        resource.id, e
+                    );
+                    None
+                }
+            }
+        }))
+        .await;
+
+        Ok(resources
+            .into_iter()
+            .zip(version_names)
+            .map(|(resource, latest_version)| SearchResult {
+                name: resource.name,
+                id: resource.id.to_string(),
+                source: self.name().to_string(),
+                description: resource.tag.filter(|t| !t.is_empty()),
+                downloads: resource.downloads,
+                latest_version,
+            })
+            .collect())
+    }
+
+    async fn list_versions(
+        &self,
+        plugin_id: &str,
+        minecraft_version: Option<&str>,
+        _options: &ResolveOptions,
+    ) -> anyhow::Result<Vec<AvailableVersion>> {
+        let (resource_id, _) = self.resolve_resource_id(plugin_id).await?;
+        let versions = Self::fetch_versions(resource_id).await?;
+        // Like resolving, count versions without tested_versions as compatible
+        let config = SelectionConfig::new(resource_id.to_string()).treat_empty_as_compatible();
+        Ok(version_selector::available_versions(
+            versions,
+            minecraft_version,
+            &config,
+        ))
+    }
+
     async fn resolve_version(
         &self,
         plugin_id: &str,
@@ -240,11 +404,15 @@ impl PluginSource for SpigotSource {
 
         // Verify resource exists
         let resource_url = format!("https://api.spiget.org/v2/resources/{}", resource_id);
-        let resource: Resource = http::fetch_json(&resource_url)
-            .await
-            .map_err(|_| anyhow::anyhow!("Resource '{}' not found in Spigot", resource_id))?;
+        let resource: Resource =
+            http::fetch_json(&resource_url, CacheBehavior::CacheFor(CACHE_TTL))
+                .await
+                .map_err(|_| anyhow::anyhow!("Resource '{}' not found in Spigot", resource_id))?;
 
         // Get external URL from resource if not already have it
+        if let Some(file) = &resource.file {
+            file.check_downloadable(resource_id)?;
+        }
         let external_url = external_url.or_else(|| resource.file.and_then(|f| f.external_url));
 
         // Fetch all versions
@@ -273,7 +441,63 @@ impl PluginSource for SpigotSource {
             .find(|v| v.version == selected.version)
             .ok_or_else(|| anyhow::anyhow!("Version not found after selection"))?;
 
-        // Now download with hash computation
-        Self::download_with_hash(resource_id, normalized_version, external_url.as_deref()).await
+        // Now download with hash computation, keeping how the version was selected
+        Self::download_with_hash(resource_id, normalized_version, external_url.as_deref())
+            .await
+            .map(|resolved| {
+                ResolvedVersion {
+                    details: selected.details,
+                    ..resolved
+                }
+                .with_canonical_id(resource_id.to_string())
+                .with_api_url(Self::versions_url(resource_id))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_count_from_header() {
+        let headers = |value: &str| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("x-page-count", value.parse().unwrap());
+            headers
+        };
+        assert_eq!(page_count(&reqwest::header::HeaderMap::new()), 1);
+        assert_eq!(page_count(&headers("3")), 3);
+        assert_eq!(page_count(&headers("0")), 1);
+        assert_eq!(page_count(&headers("many")), 1);
+        assert_eq!(page_count(&headers("100000")), MAX_VERSION_PAGES);
+    }
+
+    fn file(json: &str) -> ResourceFile {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_external_resource_without_url_is_rejected() {
+        let blocked = file(r#"{"type": "external"}"#);
+        let err = blocked.check_downloadable(1234).unwrap_err().to_string();
+        assert!(err.contains("resource 1234"), "{}", err);
+        assert!(err.contains("`url:`"), "{}", err);
+
+        let linked = file(r#"{"type": "external", "externalUrl": "https://example.com/p.jar"}"#);
+        assert!(linked.check_downloadable(1234).is_ok());
+        assert!(file(r#"{"type": ".jar"}"#).check_downloadable(1234).is_ok());
+    }
+
+    #[test]
+    fn test_search_result_carries_current_version_id() {
+        let resource: Resource = serde_json::from_str(
+            r#"{"id": 1234, "name": "EssentialsX", "version": {"id": 5678, "uuid": "x"}}"#,
+        )
+        .unwrap();
+        assert_eq!(resource.version.map(|v| v.id), Some(5678));
+
+        let resource: Resource = serde_json::from_str(r#"{"id": 1, "name": "Bare"}"#).unwrap();
+        assert!(resource.version.is_none());
     }
 }
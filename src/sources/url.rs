@@ -0,0 +1,120 @@
+// Direct URL source implementation for plugins not published to any registry
+
+use crate::sources::hash::{self, HashAlgorithm};
+use crate::sources::http;
+use crate::sources::source_trait::{PluginSource, ResolvedVersion, SearchResult};
+use async_trait::async_trait;
+
+/// Version string recorded for URL plugins, which have no version metadata
+const URL_VERSION: &str = "url";
+
+pub struct UrlSource;
+
+#[async_trait]
+impl PluginSource for UrlSource {
+    fn name(&self) -> &'static str {
+        "url"
+    }
+
+    fn validate_plugin_id(&self, plugin_id: &str) -> anyhow::Result<()> {
+        let url = reqwest::Url::parse(plugin_id)
+            .map_err(|e| anyhow::anyhow!("Invalid plugin URL '{}': {}", plugin_id, e))?;
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            anyhow::bail!(
+                "Invalid plugin URL '{}': expected an http:// or https:// URL",
+                plugin_id
+            );
+        }
+        Ok(())
+    }
+
+    async fn search(&self, _query: &str, _limit: usize) -> anyhow::Result<Vec<SearchResult>> {
+        anyhow::bail!("The url source does not support searching")
+    }
+
+    async fn resolve_version(
+        &self,
+        plugin_id: &str,
+        requested_version: Option<&str>,
+        _minecraft_version: Option<&str>,
+    ) -> anyhow::Result<ResolvedVersion> {
+        // A URL always points at exactly one build
+        if let Some(version) = requested_version
+            && version != URL_VERSION
+        {
+            anyhow::bail!(
+                "URL plugins cannot be pinned to a version (got '{}'); change the URL instead",
+                version
+            );
+        }
+
+        self.validate_plugin_id(plugin_id)?;
+
+        let response = http::download_with_response(plugin_id).await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Failed to download '{}': HTTP {}",
+                plugin_id,
+                response.status()
+            );
+        }
+
+        // Sync and doctor only manage .jar files, so make sure the name has the extension
+        let filename = http::extract_filename(&response, plugin_id);
+        let filename = if filename.ends_with(".jar") {
+            filename
+        } else {
+            format!("{}.jar", filename)
+        };
+
+        let data = response.bytes().await?;
+        let hash_str = hash::compute_hash(&data, HashAlgorithm::Sha256);
+
+        Ok(ResolvedVersion {
+            version: URL_VERSION.to_string(),
+            filename,
+            url: plugin_id.to_string(),
+            urls: Vec::new(),
+            hash: hash_str,
+            size_bytes: Some(data.len() as u64),
+            canonical_id: None,
+            zip_entry: None,
+            details: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accepts_http_urls() {
+        assert!(
+            UrlSource
+                .validate_plugin_id("https://example.com/plugin.jar")
+                .is_ok()
+        );
+        assert!(
+            UrlSource
+                .validate_plugin_id("http://127.0.0.1:8080/build/plugin.jar")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_other_schemes() {
+        assert!(
+            UrlSource
+                .validate_plugin_id("ftp://example.com/plugin.jar")
+                .is_err()
+        );
+        assert!(
+            UrlSource
+                .validate_plugin_id("file:///tmp/plugin.jar")
+                .is_err()
+        );
+        assert!(UrlSource.validate_plugin_id("plugin.jar").is_err());
+        assert!(UrlSource.validate_plugin_id("").is_err());
+    }
+}
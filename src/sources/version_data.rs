@@ -28,6 +28,12 @@ pub struct DownloadInfo {
     /// Hash (if provided by API, None = compute after download)
     /// Format: "algorithm:hash" (e.g., "sha256:abc123..." or "sha512:...")
     pub hash: Option<String>,
+
+    /// File size in bytes (if provided by API)
+    pub size_bytes: Option<u64>,
+
+    /// Other URLs serving the same file, tried when `url` fails
+    pub mirrors: Vec<String>,
 }
 
 impl NormalizedVersion {
@@ -65,6 +71,8 @@ impl DownloadInfo {
             url: url.into(),
             filename: Some(filename.into()),
             hash: Some(hash.into()),
+            size_bytes: None,
+            mirrors: Vec::new(),
         }
     }
 
@@ -74,6 +82,8 @@ impl DownloadInfo {
             url: url.into(),
             filename,
             hash: None,
+            size_bytes: None,
+            mirrors: Vec::new(),
         }
     }
 
@@ -83,6 +93,20 @@ impl DownloadInfo {
             url: url.into(),
             filename: None,
             hash: None,
+            size_bytes: None,
+            mirrors: Vec::new(),
         }
     }
+
+    /// Set the file size reported by the API
+    pub fn with_size(mut self, size_bytes: Option<u64>) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    /// Add a URL serving the same file
+    pub fn with_mirror(mut self, url: impl Into<String>) -> Self {
+        self.mirrors.push(url.into());
+        self
+    }
 }
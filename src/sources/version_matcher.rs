@@ -1,4 +1,4 @@
-// Version matching utility for Minecraft version compatibility
+// Version matching utilities for Minecraft compatibility and version constraints
 
 /// Normalize a Minecraft version string for comparison
 ///
@@ -63,6 +63,183 @@ pub fn matches_mc_version(version: &str, target: &str) -> bool {
     false
 }
 
+/// A version constraint from a manifest entry's `version` field
+///
+/// Parsed at lock time by [`parse_constraint`]. Range operators compare
+/// dotted numeric versions (a leading `v` and any `-`/`+` suffix are ignored);
+/// `Exact` compares the raw version string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionConstraint {
+    /// `1.2.3` or `=1.2.3`
+    Exact(String),
+    /// `>=1.2.3`
+    GteQ(String),
+    /// `<=1.2.3`
+    LteQ(String),
+    /// `>1.2.3`
+    Gt(String),
+    /// `<1.2.3`
+    Lt(String),
+    /// `~1.2.3`: at least 1.2.3, below 1.3 (`~1` allows any 1.x)
+    Tilde(String),
+    /// `^1.2.3`: at least 1.2.3, below the next bump of the first non-zero component
+    Caret(String),
+    /// `*` or `latest`
+    Latest,
+}
+
+impl VersionConstraint {
+    /// Check whether a version string satisfies this constraint
+    pub fn matches(&self, version: &str) -> bool {
+        use std::cmp::Ordering;
+
+        // Versions without a numeric form can only match exactly
+        let numeric = || parse_numeric_version(version);
+
+        match self {
+            VersionConstraint::Exact(expected) => version == expected,
+            VersionConstraint::Latest => true,
+            VersionConstraint::GteQ(v) => {
+                numeric().is_some_and(|actual| compare_to(&actual, v) != Ordering::Less)
+            }
+            VersionConstraint::LteQ(v) => {
+                numeric().is_some_and(|actual| compare_to(&actual, v) != Ordering::Greater)
+            }
+            VersionConstraint::Gt(v) => {
+                numeric().is_some_and(|actual| compare_to(&actual, v) == Ordering::Greater)
+            }
+            VersionConstraint::Lt(v) => {
+                numeric().is_some_and(|actual| compare_to(&actual, v) == Ordering::Less)
+            }
+            VersionConstraint::Tilde(v) => {
+                let Some(actual) = numeric() else {
+                    return false;
+                };
+                let base = parse_numeric_version(v).unwrap_or_default();
+                // Major and minor are fixed (only major when no minor was given)
+                compare_numeric(&actual, &base) != Ordering::Less
+                    && same_prefix(&actual, &base, base.len().min(2))
+            }
+            VersionConstraint::Caret(v) => {
+                let Some(actual) = numeric() else {
+                    return false;
+                };
+                let base = parse_numeric_version(v).unwrap_or_default();
+                // Components up to and including the first non-zero one are fixed
+                let fixed = base
+                    .iter()
+                    .position(|&c| c != 0)
+                    .map_or(base.len(), |i| i + 1);
+                compare_numeric(&actual, &base) != Ordering::Less
+                    && same_prefix(&actual, &base, fixed)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionConstraint::Exact(v) => write!(f, "{}", v),
+            VersionConstraint::GteQ(v) => write!(f, ">={}", v),
+            VersionConstraint::LteQ(v) => write!(f, "<={}", v),
+            VersionConstraint::Gt(v) => write!(f, ">{}", v),
+            VersionConstraint::Lt(v) => write!(f, "<{}", v),
+            VersionConstraint::Tilde(v) => write!(f, "~{}", v),
+            VersionConstraint::Caret(v) => write!(f, "^{}", v),
+            VersionConstraint::Latest => write!(f, "latest"),
+        }
+    }
+}
+
+/// Parse a manifest version string into a constraint
+///
+/// # Examples
+/// ```text
+/// assert_eq!(parse_constraint("7.2.0")?, VersionConstraint::Exact("7.2.0".into()));
+/// assert_eq!(parse_constraint(">=7.2.0")?, VersionConstraint::GteQ("7.2.0".into()));
+/// assert_eq!(parse_constraint("~1.20")?, VersionConstraint::Tilde("1.20".into()));
+/// ```
+pub fn parse_constraint(s: &str) -> anyhow::Result<VersionConstraint> {
+    let s = s.trim();
+    if s.is_empty() {
+        anyhow::bail!("version constraint is empty");
+    }
+    if s == "*" || s.eq_ignore_ascii_case("latest") {
+        return Ok(VersionConstraint::Latest);
+    }
+
+    type Variant = fn(String) -> VersionConstraint;
+    const RANGE_OPERATORS: [(&str, Variant); 6] = [
+        (">=", VersionConstraint::GteQ),
+        ("<=", VersionConstraint::LteQ),
+        (">", VersionConstraint::Gt),
+        ("<", VersionConstraint::Lt),
+        ("~", VersionConstraint::Tilde),
+        ("^", VersionConstraint::Caret),
+    ];
+
+    for (operator, variant) in RANGE_OPERATORS {
+        if let Some(operand) = s.strip_prefix(operator) {
+            let operand = operand.trim();
+            if parse_numeric_version(operand).is_none() {
+                anyhow::bail!(
+                    "'{}' expects a numeric version like '{}1.2.3', got '{}'",
+                    operator,
+                    operator,
+                    operand
+                );
+            }
+            return Ok(variant(operand.to_string()));
+        }
+    }
+
+    let exact = s.strip_prefix('=').unwrap_or(s).trim();
+    if exact.is_empty()
+        || exact.starts_with(['=', '!', '<', '>', '~', '^'])
+        || exact.contains(char::is_whitespace)
+    {
+        anyhow::bail!(
+            "unrecognized version constraint '{}' (expected an exact version or one of >=, <=, >, <, ~, ^ followed by a version)",
+            s
+        );
+    }
+    Ok(VersionConstraint::Exact(exact.to_string()))
+}
+
+/// Parse the dotted numeric part of a version ("v7.2.0-SNAPSHOT" -> [7, 2, 0])
+fn parse_numeric_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let core = version.split(['-', '+']).next().unwrap_or(version);
+    if core.is_empty() {
+        return None;
+    }
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Component `i` of a numeric version, treating missing components as zero
+fn component(version: &[u64], i: usize) -> u64 {
+    version.get(i).copied().unwrap_or(0)
+}
+
+/// Compare numeric versions, treating missing components as zero
+fn compare_numeric(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| component(a, i).cmp(&component(b, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Check that the first `n` components of two numeric versions are equal
+fn same_prefix(a: &[u64], b: &[u64], n: usize) -> bool {
+    (0..n).all(|i| component(a, i) == component(b, i))
+}
+
+fn compare_to(actual: &[u64], bound: &str) -> std::cmp::Ordering {
+    compare_numeric(actual, &parse_numeric_version(bound).unwrap_or_default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +277,82 @@ mod tests {
         assert!(!matches_mc_version("1.20", "1.21"));
         assert!(!matches_mc_version("1.2", "1.20")); // Should not match "1.2" with "1.20"
     }
+
+    #[test]
+    fn test_parse_constraint_operators() {
+        use VersionConstraint::*;
+        assert_eq!(parse_constraint("7.2.0").unwrap(), Exact("7.2.0".into()));
+        assert_eq!(parse_constraint("=7.2.0").unwrap(), Exact("7.2.0".into()));
+        assert_eq!(parse_constraint(">=7.2.0").unwrap(), GteQ("7.2.0".into()));
+        assert_eq!(parse_constraint("<=7.2").unwrap(), LteQ("7.2".into()));
+        assert_eq!(parse_constraint(">7").unwrap(), Gt("7".into()));
+        assert_eq!(parse_constraint("< 8.0.0").unwrap(), Lt("8.0.0".into()));
+        assert_eq!(parse_constraint("~1.20").unwrap(), Tilde("1.20".into()));
+        assert_eq!(parse_constraint("^2.1.0").unwrap(), Caret("2.1.0".into()));
+        assert_eq!(parse_constraint("*").unwrap(), Latest);
+        assert_eq!(parse_constraint("latest").unwrap(), Latest);
+        // Non-numeric exact versions are still allowed
+        assert_eq!(
+            parse_constraint("v2.0-beta").unwrap(),
+            Exact("v2.0-beta".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_constraint_invalid() {
+        assert!(parse_constraint("").is_err());
+        assert!(parse_constraint(">=").is_err());
+        assert!(parse_constraint(">=abc").is_err());
+        assert!(parse_constraint("~1.x").is_err());
+        assert!(parse_constraint("=>1.0").is_err());
+        assert!(parse_constraint("!=1.0").is_err());
+        assert!(parse_constraint("1.0 2.0").is_err());
+    }
+
+    #[test]
+    fn test_constraint_comparisons() {
+        let gte = parse_constraint(">=7.2.0").unwrap();
+        assert!(gte.matches("7.2.0"));
+        assert!(gte.matches("7.2"));
+        assert!(gte.matches("7.10.0"));
+        assert!(gte.matches("v8.0.0-SNAPSHOT"));
+        assert!(!gte.matches("7.1.9"));
+        assert!(!gte.matches("nightly"));
+
+        let lt = parse_constraint("<2.0").unwrap();
+        assert!(lt.matches("1.9.9"));
+        assert!(!lt.matches("2.0.0"));
+
+        assert!(parse_constraint("<=2.0").unwrap().matches("2.0.0"));
+        assert!(!parse_constraint(">2.0").unwrap().matches("2.0.0"));
+        assert!(parse_constraint("*").unwrap().matches("anything"));
+        assert!(!parse_constraint("7.2.0").unwrap().matches("7.2"));
+    }
+
+    #[test]
+    fn test_constraint_tilde_and_caret() {
+        let tilde = parse_constraint("~1.20").unwrap();
+        assert!(tilde.matches("1.20"));
+        assert!(tilde.matches("1.20.4"));
+        assert!(!tilde.matches("1.21.0"));
+        assert!(!tilde.matches("1.19.9"));
+
+        let tilde_patch = parse_constraint("~1.20.3").unwrap();
+        assert!(tilde_patch.matches("1.20.5"));
+        assert!(!tilde_patch.matches("1.20.2"));
+        assert!(!tilde_patch.matches("1.21.0"));
+
+        let tilde_major = parse_constraint("~7").unwrap();
+        assert!(tilde_major.matches("7.3.1"));
+        assert!(!tilde_major.matches("8.0.0"));
+
+        let caret = parse_constraint("^2.1.0").unwrap();
+        assert!(caret.matches("2.9.0"));
+        assert!(!caret.matches("3.0.0"));
+        assert!(!caret.matches("2.0.9"));
+
+        let caret_zero = parse_constraint("^0.2.3").unwrap();
+        assert!(caret_zero.matches("0.2.9"));
+        assert!(!caret_zero.matches("0.3.0"));
+    }
 }
@@ -1,10 +1,11 @@
 // Unified version selection logic
 
+use crate::error::MpmError;
 use crate::sources::hash::{self, HashAlgorithm};
 use crate::sources::http;
-use crate::sources::source_trait::ResolvedVersion;
+use crate::sources::source_trait::{AvailableVersion, ResolvedVersion};
 use crate::sources::version_data::NormalizedVersion;
-use crate::sources::version_matcher;
+use crate::sources::version_matcher::{self, VersionConstraint};
 use anyhow::Result;
 
 /// Configuration for version selection
@@ -33,6 +34,7 @@ impl SelectionConfig {
 ///
 /// Handles:
 /// - Finding specific version vs latest
+/// - Version constraints such as `>=7.2.0` or `~1.20`
 /// - Minecraft version filtering
 /// - Sorting by publication date
 /// - Appropriate error messages
@@ -52,25 +54,76 @@ pub async fn select_version(
         versions
     };
 
-    let selected = if let Some(version_str) = requested_version {
-        select_specific_version(
+    let constraint = requested_version
+        .map(version_matcher::parse_constraint)
+        .transpose()
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid version constraint for plugin '{}': {}",
+                config.plugin_id,
+                e
+            )
+        })?
+        .unwrap_or(VersionConstraint::Latest);
+
+    let versions_compatible = filtered_versions.len();
+    let selected = match &constraint {
+        VersionConstraint::Exact(version_str) => select_specific_version(
             &filtered_versions,
             &all_versions,
             version_str,
             minecraft_version,
             config,
-        )?
-    } else {
-        select_latest_version(
+        )?,
+        VersionConstraint::Latest => select_latest_version(
             &mut filtered_versions,
             &all_versions,
             minecraft_version,
             config,
-        )?
+        )?,
+        range => select_matching_version(&mut filtered_versions, range, minecraft_version, config)?,
     };
 
     // Resolve to final ResolvedVersion (may need to download for hash)
-    resolve_download(selected, &config.plugin_id).await
+    let mut resolved = resolve_download(selected, &config.plugin_id).await?;
+    resolved.details.versions_total = Some(all_versions.len());
+    resolved.details.versions_compatible = Some(versions_compatible);
+    resolved.details.published_at = Some(selected.published_at.clone());
+    Ok(resolved)
+}
+
+/// List the versions compatible with a Minecraft version, newest first
+pub fn compatible_versions(
+    versions: Vec<NormalizedVersion>,
+    minecraft_version: Option<&str>,
+    config: &SelectionConfig,
+) -> Vec<String> {
+    available_versions(versions, minecraft_version, config)
+        .into_iter()
+        .map(|v| v.version)
+        .collect()
+}
+
+/// Like `compatible_versions`, but keeping each version's date and Minecraft versions
+pub fn available_versions(
+    versions: Vec<NormalizedVersion>,
+    minecraft_version: Option<&str>,
+    config: &SelectionConfig,
+) -> Vec<AvailableVersion> {
+    let mut filtered = if let Some(mc_version) = minecraft_version {
+        filter_by_mc_version(versions, mc_version, config.treat_empty_as_compatible)
+    } else {
+        versions
+    };
+    filtered.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+    filtered
+        .into_iter()
+        .map(|v| AvailableVersion {
+            version: v.version,
+            published_at: v.published_at,
+            mc_versions: v.mc_versions,
+        })
+        .collect()
 }
 
 /// Filter versions by Minecraft version compatibility
@@ -113,13 +166,13 @@ fn select_specific_version<'a>(
                 .iter()
                 .any(|gv| version_matcher::matches_mc_version(gv, mc_version));
             if !is_compatible {
-                anyhow::bail!(
-                    "Plugin '{}' version '{}' is not compatible with Minecraft {}. Compatible versions: {}",
-                    config.plugin_id,
-                    version_str,
-                    mc_version,
-                    v.mc_versions.join(", ")
-                );
+                return Err(MpmError::VersionNotCompatible {
+                    plugin: config.plugin_id.clone(),
+                    version: Some(version_str.to_string()),
+                    mc_version: mc_version.to_string(),
+                    supported: v.mc_versions.clone(),
+                }
+                .into());
             }
         }
         return Ok(v);
@@ -129,17 +182,13 @@ fn select_specific_version<'a>(
     if let Some(mc_version) = minecraft_version
         && let Some(incompatible_version) = all_versions.iter().find(|v| v.version == version_str)
     {
-        anyhow::bail!(
-            "Plugin '{}' version '{}' is not compatible with Minecraft {}. Compatible versions: {}",
-            config.plugin_id,
-            version_str,
-            mc_version,
-            if incompatible_version.mc_versions.is_empty() {
-                "unknown".to_string()
-            } else {
-                incompatible_version.mc_versions.join(", ")
-            }
-        );
+        return Err(MpmError::VersionNotCompatible {
+            plugin: config.plugin_id.clone(),
+            version: Some(version_str.to_string()),
+            mc_version: mc_version.to_string(),
+            supported: incompatible_version.mc_versions.clone(),
+        }
+        .into());
     }
 
     anyhow::bail!(
@@ -158,21 +207,16 @@ fn select_latest_version<'a>(
 ) -> Result<&'a NormalizedVersion> {
     if filtered_versions.is_empty() {
         if let Some(mc_version) = minecraft_version {
-            anyhow::bail!(
-                "No versions of plugin '{}' are compatible with Minecraft {}. Latest version supports: {}",
-                config.plugin_id,
-                mc_version,
-                all_versions
+            return Err(MpmError::VersionNotCompatible {
+                plugin: config.plugin_id.clone(),
+                version: None,
+                mc_version: mc_version.to_string(),
+                supported: all_versions
                     .first()
-                    .map(|v| {
-                        if v.mc_versions.is_empty() {
-                            "unknown".to_string()
-                        } else {
-                            v.mc_versions.join(", ")
-                        }
-                    })
-                    .unwrap_or_else(|| "unknown".to_string())
-            );
+                    .map(|v| v.mc_versions.clone())
+                    .unwrap_or_default(),
+            }
+            .into());
         } else {
             anyhow::bail!("No versions found for plugin '{}'", config.plugin_id);
         }
@@ -184,6 +228,34 @@ fn select_latest_version<'a>(
     Ok(filtered_versions.first().unwrap())
 }
 
+/// Select the newest version satisfying a range constraint
+fn select_matching_version<'a>(
+    filtered_versions: &'a mut [NormalizedVersion],
+    constraint: &VersionConstraint,
+    minecraft_version: Option<&str>,
+    config: &SelectionConfig,
+) -> Result<&'a NormalizedVersion> {
+    // Sort by published_at descending (newest first)
+    filtered_versions.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+
+    filtered_versions
+        .iter()
+        .find(|v| constraint.matches(&v.version))
+        .ok_or_else(|| match minecraft_version {
+            Some(mc_version) => anyhow::anyhow!(
+                "No version of plugin '{}' matching '{}' is compatible with Minecraft {}",
+                config.plugin_id,
+                constraint,
+                mc_version
+            ),
+            None => anyhow::anyhow!(
+                "No version of plugin '{}' matches '{}'",
+                config.plugin_id,
+                constraint
+            ),
+        })
+}
+
 /// Resolve a NormalizedVersion to a ResolvedVersion
 /// Downloads the file if hash is not available
 async fn resolve_download(version: &NormalizedVersion, plugin_id: &str) -> Result<ResolvedVersion> {
@@ -200,7 +272,12 @@ async fn resolve_download(version: &NormalizedVersion, plugin_id: &str) -> Resul
             version: version.version.clone(),
             filename,
             url: download.url.clone(),
+            urls: download.mirrors.clone(),
             hash: hash.clone(),
+            size_bytes: download.size_bytes,
+            canonical_id: None,
+            zip_entry: None,
+            details: Default::default(),
         })
     } else {
         // Need to download to compute hash
@@ -227,7 +304,12 @@ async fn resolve_download(version: &NormalizedVersion, plugin_id: &str) -> Resul
             version: version.version.clone(),
             filename,
             url: download.url.clone(),
+            urls: download.mirrors.clone(),
             hash,
+            size_bytes: Some(data.len() as u64),
+            canonical_id: None,
+            zip_entry: None,
+            details: Default::default(),
         })
     }
 }
@@ -279,4 +361,69 @@ mod tests {
         let filtered = filter_by_mc_version(versions, "1.20.1", true);
         assert_eq!(filtered.len(), 2);
     }
+
+    #[test]
+    fn test_compatible_versions_newest_first() {
+        let mut old = make_version("1.0", vec!["1.20.1"]);
+        old.published_at = "2023-01-01T00:00:00Z".to_string();
+        let versions = vec![
+            old,
+            make_version("2.0", vec!["1.21"]),
+            make_version("3.0", vec!["1.20.1"]),
+        ];
+
+        let config = SelectionConfig::new("test");
+        assert_eq!(
+            compatible_versions(versions.clone(), Some("1.20.1"), &config),
+            vec!["3.0", "1.0"]
+        );
+        assert_eq!(compatible_versions(versions, None, &config).len(), 3);
+    }
+
+    #[test]
+    fn test_select_matching_version_newest_in_range() {
+        let mut versions = vec![
+            make_version("7.1.0", vec!["1.20.1"]),
+            make_version("7.2.5", vec!["1.20.1"]),
+            make_version("8.0.0", vec!["1.20.1"]),
+        ];
+        versions[0].published_at = "2023-01-01T00:00:00Z".to_string();
+        versions[1].published_at = "2023-06-01T00:00:00Z".to_string();
+
+        let config = SelectionConfig::new("test");
+        let constraint = version_matcher::parse_constraint("~7.1").unwrap();
+        let selected = select_matching_version(&mut versions, &constraint, None, &config).unwrap();
+        assert_eq!(selected.version, "7.1.0");
+
+        let constraint = version_matcher::parse_constraint("^7.1").unwrap();
+        let selected = select_matching_version(&mut versions, &constraint, None, &config).unwrap();
+        assert_eq!(selected.version, "7.2.5");
+
+        let constraint = version_matcher::parse_constraint(">=9").unwrap();
+        let err = select_matching_version(&mut versions, &constraint, Some("1.20.1"), &config)
+            .unwrap_err();
+        assert!(err.to_string().contains("matching '>=9'"));
+    }
+
+    #[tokio::test]
+    async fn test_select_version_records_details() {
+        let mut versions = vec![
+            make_version("1.0", vec!["1.20.1"]),
+            make_version("2.0", vec!["1.21"]),
+            make_version("3.0", vec!["1.20.1"]),
+        ];
+        versions[2].published_at = "2024-06-01T00:00:00Z".to_string();
+
+        let config = SelectionConfig::new("test");
+        let resolved = select_version(versions, None, Some("1.20.1"), &config)
+            .await
+            .unwrap();
+        assert_eq!(resolved.version, "3.0");
+        assert_eq!(resolved.details.versions_total, Some(3));
+        assert_eq!(resolved.details.versions_compatible, Some(2));
+        assert_eq!(
+            resolved.details.published_at.as_deref(),
+            Some("2024-06-01T00:00:00Z")
+        );
+    }
 }
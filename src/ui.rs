@@ -6,19 +6,145 @@
 
 use console::{Style, Term, style};
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
-use std::time::Duration;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// Spinner style similar to uv/pnpm
 const SPINNER_CHARS: &str = "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏";
 
+/// Set by `--json` and `lock --check`: messages are buffered instead of printed
+static BUFFER_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+static BUFFERED: Mutex<Vec<BufferedMessage>> = Mutex::new(Vec::new());
+
+/// Set by `--verbose`: spinners are hidden so they don't interleave with log lines
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Set by `--log`: messages are also appended to this file as JSON lines
+static LOG: Mutex<Option<LogFile>> = Mutex::new(None);
+
+struct LogFile {
+    file: File,
+    started: Instant,
+}
+
+/// One line of the `--log` file
+#[derive(Serialize)]
+struct LogEntry<'a> {
+    timestamp: String,
+    level: &'a str,
+    message: &'a str,
+}
+
+/// A message collected while output is buffered
+#[derive(Debug, Clone, Serialize)]
+pub struct BufferedMessage {
+    pub level: &'static str,
+    pub message: String,
+}
+
 /// Check if stderr is a TTY (for interactive output)
 fn is_tty() -> bool {
-    Term::stderr().is_term()
+    Term::stderr().is_term() && !is_buffered()
+}
+
+/// Whether spinners and progress bars are drawn
+fn show_progress() -> bool {
+    is_tty() && !VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Hide spinners and progress bars, printing their results as plain lines
+pub fn set_verbose(enabled: bool) {
+    VERBOSE.store(enabled, Ordering::Relaxed);
+}
+
+/// Buffer messages instead of printing them, and hide spinners
+pub fn set_buffered(enabled: bool) {
+    BUFFER_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_buffered() -> bool {
+    BUFFER_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Append every message to a log file as JSON lines, starting with the command
+///
+/// The file is created if needed and never truncated, so a cron job can keep
+/// logging to the same file.
+pub fn set_log_file(path: &str, command: &str) -> std::io::Result<()> {
+    let file = OpenOptions::new().append(true).create(true).open(path)?;
+    *LOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(LogFile {
+        file,
+        started: Instant::now(),
+    });
+    log("info", &format!("mpm {}", command));
+    Ok(())
+}
+
+/// Append a message to the log file, if there is one
+pub fn log(level: &str, message: &str) {
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(log) = log.as_mut() {
+        let entry = LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            level,
+            message,
+        };
+        // A log that can't be written shouldn't fail the command
+        if let Ok(line) = serde_json::to_string(&entry) {
+            let _ = writeln!(log.file, "{}", line);
+        }
+    }
+}
+
+/// Log how long the command took and its exit code, then close the log file
+pub fn finish_log(exit_code: i32) {
+    let started = LOG
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|log| log.started);
+    if let Some(started) = started {
+        let level = if exit_code == 0 { "info" } else { "error" };
+        log(
+            level,
+            &format!(
+                "Finished in {:.2}s with exit code {}",
+                started.elapsed().as_secs_f64(),
+                exit_code
+            ),
+        );
+        *LOG.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Take the messages buffered so far
+pub fn take_buffered() -> Vec<BufferedMessage> {
+    std::mem::take(&mut *BUFFERED.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Buffer a message if buffering is on; returns false if it should be printed
+fn buffer(level: &'static str, message: &str) -> bool {
+    if !is_buffered() {
+        return false;
+    }
+    BUFFERED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(BufferedMessage {
+            level,
+            message: message.to_string(),
+        });
+    true
 }
 
 /// Create a styled spinner for async operations
 pub fn spinner(message: &str) -> ProgressBar {
-    let pb = if is_tty() {
+    let pb = if show_progress() {
         ProgressBar::new_spinner()
     } else {
         // In non-TTY mode, use a hidden draw target
@@ -36,7 +162,7 @@ pub fn spinner(message: &str) -> ProgressBar {
     );
     pb.set_message(message.to_string());
 
-    if is_tty() {
+    if show_progress() {
         pb.enable_steady_tick(Duration::from_millis(80));
     }
 
@@ -74,9 +200,17 @@ pub fn download_bar_indeterminate() -> ProgressBar {
 }
 
 /// Create a multi-progress bar manager
-#[allow(dead_code)]
 pub fn multi_progress() -> MultiProgress {
-    MultiProgress::new()
+    if is_buffered() || VERBOSE.load(Ordering::Relaxed) {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    } else {
+        MultiProgress::new()
+    }
+}
+
+/// Create a spinner that renders alongside the other bars in `multi`
+pub fn multi_spinner(multi: &MultiProgress, message: &str) -> ProgressBar {
+    multi.add(spinner(message))
 }
 
 /// Styles for different message types
@@ -110,24 +244,55 @@ impl Styles {
 
 /// Print a success message with checkmark
 pub fn success(message: &str) {
+    log("info", message);
+    if buffer("success", message) {
+        return;
+    }
     println!("{} {}", style("✓").green(), message);
 }
 
 /// Print an info/action message with arrow
 pub fn action(message: &str) {
+    log("info", message);
+    if buffer("action", message) {
+        return;
+    }
     println!("{} {}", style("→").cyan(), message);
 }
 
 /// Print a warning message
 pub fn warning(message: &str) {
+    log("warn", message);
+    if buffer("warning", message) {
+        return;
+    }
     eprintln!("{} {}", style("⚠").yellow(), message);
 }
 
 /// Print an error message
 pub fn error(message: &str) {
+    log("error", message);
+    if buffer("error", message) {
+        return;
+    }
     eprintln!("{} {}", style("✗").red(), message);
 }
 
+/// Ask a yes/no question, defaulting to no
+///
+/// Without an interactive terminal the answer is always no.
+pub fn confirm(question: &str) -> bool {
+    if !is_tty() || !std::io::stdin().is_terminal() {
+        return false;
+    }
+    eprint!("{} {} [y/N] ", style("?").cyan(), question);
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
 /// Print a header/section message
 #[allow(dead_code)]
 pub fn header(message: &str) {
@@ -136,19 +301,29 @@ pub fn header(message: &str) {
 
 /// Print a dimmed/secondary message
 pub fn dim(message: &str) {
+    if buffer("info", message) {
+        return;
+    }
     println!("{}", style(message).dim());
 }
 
 /// Print a status message (for dry-run, etc.)
 pub fn status(prefix: &str, message: &str) {
+    if buffer("info", &format!("{} {}", prefix, message)) {
+        return;
+    }
     println!("{} {}", style(prefix).cyan().bold(), message);
 }
 
 /// Finish a spinner with success
 #[allow(dead_code)]
 pub fn finish_spinner_success(pb: &ProgressBar, message: &str) {
+    if buffer("success", message) {
+        pb.finish_and_clear();
+        return;
+    }
     let msg = format!("{} {}", style("✓").green(), message);
-    if is_tty() {
+    if show_progress() {
         pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
         pb.finish_with_message(msg);
     } else {
@@ -159,8 +334,12 @@ pub fn finish_spinner_success(pb: &ProgressBar, message: &str) {
 
 /// Finish a spinner with the resolved version info
 pub fn finish_spinner_resolved(pb: &ProgressBar, name: &str, version: &str) {
+    if buffer("success", &format!("{} {}", name, version)) {
+        pb.finish_and_clear();
+        return;
+    }
     let msg = format!("{} {} {}", style("✓").green(), name, style(version).dim());
-    if is_tty() {
+    if show_progress() {
         pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
         pb.finish_with_message(msg);
     } else {
@@ -171,8 +350,12 @@ pub fn finish_spinner_resolved(pb: &ProgressBar, name: &str, version: &str) {
 
 /// Finish a spinner with error
 pub fn finish_spinner_error(pb: &ProgressBar, message: &str) {
+    if buffer("error", message) {
+        pb.finish_and_clear();
+        return;
+    }
     let msg = format!("{} {}", style("✗").red(), message);
-    if is_tty() {
+    if show_progress() {
         pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
         pb.finish_with_message(msg);
     } else {
@@ -183,13 +366,17 @@ pub fn finish_spinner_error(pb: &ProgressBar, message: &str) {
 
 /// Finish a download bar with success
 pub fn finish_download_success(pb: &ProgressBar, name: &str) {
+    if buffer("success", &format!("{} verified", name)) {
+        pb.finish_and_clear();
+        return;
+    }
     let msg = format!(
         "{} {} {}",
         style("✓").green(),
         name,
         style("verified").dim()
     );
-    if is_tty() {
+    if show_progress() {
         pb.set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
         pb.finish_with_message(msg);
     } else {
@@ -202,3 +389,38 @@ pub fn finish_download_success(pb: &ProgressBar, name: &str) {
 pub fn clear_bar(pb: &ProgressBar) {
     pb.finish_and_clear();
 }
+
+/// Print rows as left-aligned columns under a bold header
+///
+/// The last column is not padded, so it may contain styled text.
+pub fn table<const N: usize>(headers: [&str; N], rows: &[[String; N]]) {
+    let mut widths = headers.map(|h| h.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(cell));
+        }
+    }
+
+    let format_row = |cells: [&str; N]| {
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i + 1 == N {
+                line.push_str(cell);
+            } else {
+                line.push_str(&console::pad_str(
+                    cell,
+                    widths[i],
+                    console::Alignment::Left,
+                    None,
+                ));
+                line.push_str("  ");
+            }
+        }
+        line
+    };
+
+    println!("{}", style(format_row(headers)).bold());
+    for row in rows {
+        println!("{}", format_row(row.each_ref().map(String::as_str)));
+    }
+}
@@ -0,0 +1,55 @@
+// Workspace module for the manifest listing a server network's directories
+
+use crate::config;
+use crate::constants;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    /// Server name to directory, absolute or relative to the workspace
+    #[serde(default)]
+    pub servers: BTreeMap<String, String>,
+}
+
+impl Workspace {
+    pub fn load() -> anyhow::Result<Self> {
+        let path = config::workspace_path();
+        if !Path::new(&path).exists() {
+            anyhow::bail!(
+                "{} not found. Register a server with 'mpm workspace add <name> <path>' first.",
+                constants::WORKSPACE_FILE
+            );
+        }
+        let text = std::fs::read_to_string(&path)?;
+        toml::from_str(&text).map_err(|e| anyhow::anyhow!("Invalid {}: {}", path, e))
+    }
+
+    /// Load the workspace, or start an empty one when there is no file yet
+    pub fn load_or_default() -> anyhow::Result<Self> {
+        if Path::new(&config::workspace_path()).exists() {
+            Self::load()
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let dir = config::config_dir();
+        std::fs::create_dir_all(&dir)?;
+        let text = toml::to_string_pretty(self)?;
+        std::fs::write(config::workspace_path(), text)?;
+        Ok(())
+    }
+
+    /// Directory of a registered server, resolved against the workspace
+    pub fn server_dir(path: &str) -> PathBuf {
+        let path = Path::new(path);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            Path::new(&config::config_dir()).join(path)
+        }
+    }
+}